@@ -0,0 +1,37 @@
+//! Benchmarks for loading distro-info-data, which the command-line tools do on every invocation.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use distro_info::{DebianDistroInfo, DistroDataSource, DistroInfo, UbuntuDistroInfo};
+
+const UBUNTU_CSV: &str = include_str!("../testdata/ubuntu.csv");
+const DEBIAN_CSV: &str = include_str!("../testdata/debian.csv");
+
+fn parse(c: &mut Criterion) {
+    c.bench_function("ubuntu from_csv_str", |b| {
+        b.iter(|| UbuntuDistroInfo::from_csv_str(black_box(UBUNTU_CSV)).unwrap())
+    });
+    c.bench_function("debian from_csv_str", |b| {
+        b.iter(|| DebianDistroInfo::from_csv_str(black_box(DEBIAN_CSV)).unwrap())
+    });
+    c.bench_function("ubuntu from_csv_reader", |b| {
+        b.iter(|| {
+            UbuntuDistroInfo::from_csv_reader(
+                csv::ReaderBuilder::new()
+                    .flexible(true)
+                    .has_headers(true)
+                    .from_reader(black_box(UBUNTU_CSV.as_bytes())),
+            )
+            .unwrap()
+        })
+    });
+}
+
+fn query(c: &mut Criterion) {
+    let ubuntu_distro_info = UbuntuDistroInfo::from_csv_str(UBUNTU_CSV).unwrap();
+    let date = chrono::NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+    c.bench_function("ubuntu supported", |b| {
+        b.iter(|| ubuntu_distro_info.supported(black_box(date)))
+    });
+}
+
+criterion_group!(benches, parse, query);
+criterion_main!(benches);
@@ -1,21 +1,6 @@
-use clap::{App, Arg};
-use distro_info::{DebianDistroInfo, DistroInfo};
-use distro_info_binaries::{add_common_args, common_run};
-use failure::Error;
-
-fn run() -> Result<(), Error> {
-    let app = add_common_args(App::new("debian-distro-info"), &["testing"])
-        .arg(Arg::with_name("testing").short("t").long("testing"));
-    let matches = app.get_matches();
-    let debian_distro_info = DebianDistroInfo::new()?;
-    common_run(&matches, &debian_distro_info)
-}
+use distro_info::DebianDistroInfo;
+use distro_info_binaries::DistroInfoCommand;
 
 fn main() {
-    if let Err(ref e) = run() {
-        use std::io::Write;
-        let stderr = &mut ::std::io::stderr();
-        writeln!(stderr, "debian-distro-info: {}", e).unwrap();
-        ::std::process::exit(1);
-    }
+    DistroInfoCommand::debian().main::<DebianDistroInfo>();
 }
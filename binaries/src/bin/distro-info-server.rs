@@ -0,0 +1,13 @@
+use std::env;
+
+use distro_info_binaries::server;
+
+fn main() {
+    let address = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    if let Err(e) = server::serve(&address) {
+        eprintln!("distro-info-server: {}", e);
+        ::std::process::exit(distro_info_binaries::exit_status(&e));
+    }
+}
@@ -0,0 +1,29 @@
+//! `distro-info <distro> [OPTIONS]`: the `*-distro-info` tools behind a single binary, for
+//! installations which would rather ship one executable.
+use std::env;
+use std::process;
+
+use distro_info::{DebianDistroInfo, UbuntuDistroInfo};
+use distro_info_binaries::{DistroInfoCommand, EXIT_ERROR};
+
+fn main() {
+    let mut args = env::args_os();
+    let program = args.next().unwrap_or_else(|| "distro-info".into());
+    let distro = args.next();
+    // Parse the remaining arguments as though they had been passed to the distro's own tool
+    match distro.as_ref().and_then(|distro| distro.to_str()) {
+        Some("ubuntu") => DistroInfoCommand::ubuntu().main_from::<UbuntuDistroInfo, _>(
+            Some("ubuntu-distro-info".into()).into_iter().chain(args),
+        ),
+        Some("debian") => DistroInfoCommand::debian().main_from::<DebianDistroInfo, _>(
+            Some("debian-distro-info".into()).into_iter().chain(args),
+        ),
+        _ => {
+            eprintln!(
+                "usage: {} {{ubuntu,debian}} [OPTIONS]",
+                program.to_string_lossy()
+            );
+            process::exit(EXIT_ERROR);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Parsing `--date` values, which may be relative to today so that cron jobs can look ahead.
+//!
+//! The accepted forms are:
+//!
+//! * `YYYY-MM-DD`
+//! * `YYYY-Qn`: the first day of a quarter, e.g. `2024-Q3` for 2024-07-01
+//! * `today`, optionally with an offset: `today+30d`, `today-1y`
+//! * an offset alone, relative to today: `+2w`, `-1y`
+//!
+//! Offsets are a number of days (`d`), weeks (`w`), months (`m`) or years (`y`).  Month and year
+//! offsets from the end of a month are clamped to the end of the target month.
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+/// Parse `value`, with relative dates taken relative to `today`
+///
+/// Returns `None` if `value` is in none of the accepted forms, or gives a date out of range.
+pub fn parse_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date);
+    }
+    if let Some((year, quarter)) = value.split_once("-Q") {
+        let quarter: u32 = quarter.parse().ok().filter(|q| (1..=4).contains(q))?;
+        return NaiveDate::from_ymd_opt(year.parse().ok()?, 3 * quarter - 2, 1);
+    }
+    let offset = value.strip_prefix("today").unwrap_or(value);
+    if offset.is_empty() {
+        return (value == "today").then_some(today);
+    }
+    apply_offset(today, offset)
+}
+
+/// Apply an offset such as "+30d" or "-1y" to `date`
+fn apply_offset(date: NaiveDate, offset: &str) -> Option<NaiveDate> {
+    let (sign, rest) = match offset.as_bytes().first()? {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let unit = rest.chars().last()?;
+    let amount: u32 = rest[..rest.len() - unit.len_utf8()].parse().ok()?;
+    let months = |months: u32| {
+        if sign > 0 {
+            date.checked_add_months(Months::new(months))
+        } else {
+            date.checked_sub_months(Months::new(months))
+        }
+    };
+    match unit {
+        'd' => date.checked_add_signed(Duration::days(sign * i64::from(amount))),
+        'w' => date.checked_add_signed(Duration::weeks(sign * i64::from(amount))),
+        'm' => months(amount),
+        'y' => months(amount.checked_mul(12)?),
+        _ => None,
+    }
+    .filter(|result| result.year() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use distro_info::test_support;
+
+    use super::parse_date;
+
+    #[test]
+    fn forms() {
+        let today = test_support::date("2024-01-31");
+        let parse = |value| parse_date(value, today).map(|date| date.to_string());
+        assert_eq!(Some("2024-06-01".to_string()), parse("2024-06-01"));
+        assert_eq!(Some("2024-07-01".to_string()), parse("2024-Q3"));
+        assert_eq!(Some("2024-01-31".to_string()), parse("today"));
+        assert_eq!(Some("2024-03-01".to_string()), parse("today+30d"));
+        assert_eq!(Some("2024-02-14".to_string()), parse("+2w"));
+        assert_eq!(Some("2024-02-29".to_string()), parse("today+1m"));
+        assert_eq!(Some("2023-01-31".to_string()), parse("-1y"));
+        for invalid in [
+            "", "tomorrow", "today+", "+30", "30d", "2024-Q5", "today+1x", "+-1d",
+        ] {
+            assert_eq!(None, parse(invalid), "{}", invalid);
+        }
+    }
+}
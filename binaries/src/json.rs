@@ -0,0 +1,168 @@
+//! JSON output, for `--format json` and the server.
+//!
+//! With `--format json`, each release (or, with `--check`, the report on its support status) is
+//! printed as a JSON object on its own line, and errors are printed on stderr as
+//! `{"error": {"code": ..., "message": ...}}` (and warnings likewise, under "warning"), so that
+//! orchestration systems can tell them apart by `code` rather than by parsing the English message.
+use chrono::NaiveDate;
+use distro_info::parser::ParseError;
+use distro_info::prediction::PredictedVersion;
+use distro_info::status::SupportStatus;
+use distro_info::DistroRelease;
+use failure::Error;
+use serde_json::{json, Value};
+
+use crate::selectors::UnknownSeries;
+use crate::{InternalError, NoReleases};
+
+/// The `--format` which selects JSON output
+pub const FORMAT: &str = "json";
+
+/// The JSON representation of a release
+pub fn release_json(distro_release: &DistroRelease) -> Value {
+    let date = |date: &Option<NaiveDate>| date.map(|date| date.to_string());
+    json!({
+        "version": distro_release.version(),
+        "codename": distro_release.codename(),
+        "series": distro_release.series(),
+        "created": date(distro_release.created()),
+        "release": date(distro_release.release()),
+        "eol": date(distro_release.eol()),
+        "eol-server": date(distro_release.eol_server()),
+        "eol-lts": date(distro_release.eol_lts()),
+        "eol-elts": date(distro_release.eol_elts()),
+        "eol-esm": date(distro_release.eol_esm()),
+    })
+}
+
+/// The JSON representation of a version `--fallback=predict` predicted, which has the fields of a
+/// release that are known, and `"predicted": true`
+pub fn predicted_json(predicted: &PredictedVersion) -> Value {
+    json!({
+        "version": predicted.to_string(),
+        "release": predicted.expected_release.to_string(),
+        "predicted": true,
+    })
+}
+
+/// The JSON representation of the `--check` report on a release's support status
+pub fn status_json(status: &SupportStatus) -> Value {
+    json!({
+        "series": status.release().series(),
+        "supported": status.supported(),
+        "days-to-eol": status.days_to_eol(),
+        "esm-available": status.esm_available(),
+        "successor": status.successor().map(|successor| successor.series()),
+    })
+}
+
+/// A stable identifier for the kind of `error`, for machine consumers
+///
+/// This is one of "no-releases", "unknown-series", "data-unavailable" (the data file could not be
+/// read), "invalid-data" (it could not be parsed), "internal-error" or, for anything else (such
+/// as an invalid argument), "error".
+pub fn error_code(error: &Error) -> &'static str {
+    for cause in error.iter_chain() {
+        if cause.downcast_ref::<NoReleases>().is_some() {
+            return "no-releases";
+        } else if cause.downcast_ref::<UnknownSeries>().is_some() {
+            return "unknown-series";
+        } else if cause.downcast_ref::<std::io::Error>().is_some() {
+            return "data-unavailable";
+        } else if cause.downcast_ref::<ParseError>().is_some() {
+            return "invalid-data";
+        } else if cause.downcast_ref::<InternalError>().is_some() {
+            return "internal-error";
+        }
+    }
+    "error"
+}
+
+/// The JSON representation of a warning, identified by `code` (e.g. "stale-data")
+pub fn warning_json(code: &str, message: &str) -> Value {
+    json!({
+        "warning": {
+            "code": code,
+            "message": message,
+        }
+    })
+}
+
+/// The JSON representation of `error`
+pub fn error_json(error: &Error) -> Value {
+    json!({
+        "error": {
+            "code": error_code(error),
+            "message": error.to_string(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use distro_info::prediction::next_expected_ubuntu_version;
+    use distro_info::status::SupportStatus;
+    use distro_info::test_support;
+
+    use super::{error_code, error_json, predicted_json, status_json};
+    use crate::selectors::UnknownSeries;
+    use crate::{InternalError, NoReleases};
+    use failure::{format_err, Error, ResultExt};
+    use serde_json::json;
+
+    #[test]
+    fn error_codes() {
+        assert_eq!("no-releases", error_code(&NoReleases.into()));
+        assert_eq!(
+            "internal-error",
+            error_code(&InternalError("oops".to_string()).into())
+        );
+        let missing: Result<(), _> = Err(std::io::Error::from(std::io::ErrorKind::NotFound));
+        let missing: Error = missing.context("reading ubuntu.csv").unwrap_err().into();
+        assert_eq!("data-unavailable", error_code(&missing));
+        assert_eq!("error", error_code(&format_err!("bad --date")));
+        assert_eq!(
+            json!({
+                "error": {
+                    "code": "unknown-series",
+                    "message": "unknown distribution series `jamy'; did you mean `jammy'?",
+                }
+            }),
+            error_json(
+                &UnknownSeries {
+                    series: "jamy".to_string(),
+                    suggestion: Some("jammy".to_string()),
+                }
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn predicted_version() {
+        let predicted =
+            next_expected_ubuntu_version(&test_support::ubuntu(), test_support::date("2026-06-01"))
+                .unwrap();
+        assert_eq!(
+            json!({"version": "26.10", "release": "2026-10-23", "predicted": true}),
+            predicted_json(&predicted)
+        );
+    }
+
+    #[test]
+    fn check_report() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let date = test_support::date("2024-01-01");
+        let status = SupportStatus::for_codename(&ubuntu_distro_info, "jammy", date).unwrap();
+        assert_eq!(
+            json!({
+                "series": "jammy",
+                "supported": true,
+                "days-to-eol": 1247,
+                "esm-available": true,
+                "successor": "kinetic",
+            }),
+            status_json(&status)
+        );
+    }
+}
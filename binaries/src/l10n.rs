@@ -0,0 +1,178 @@
+//! Translation of the tools' messages (errors, warnings and `--help`), as the distro-info tools
+//! packaged in Debian and Ubuntu are translated.
+//!
+//! With the `l10n` feature, [`gettext`](fn.gettext.html) looks messages up in the catalog for the
+//! user's language (from `LANGUAGE`, `LC_ALL`, `LC_MESSAGES` or `LANG`, as gettext does), built
+//! from the PO files in `po/`, which are compiled into the binaries.  Without it, or if there is
+//! no translation, messages are returned as they are.
+use std::fmt;
+
+#[cfg(feature = "l10n")]
+use std::collections::HashMap;
+#[cfg(feature = "l10n")]
+use std::sync::OnceLock;
+
+/// The PO files compiled into the binaries, by language
+#[cfg(feature = "l10n")]
+const CATALOGS: [(&str, &str); 1] = [("de", include_str!("../po/de.po"))];
+
+/// Translations, by message
+#[cfg(feature = "l10n")]
+pub type Catalog = HashMap<String, String>;
+
+/// Translate `msgid` into the user's language, if there is a translation
+pub fn gettext(msgid: &str) -> &str {
+    #[cfg(feature = "l10n")]
+    {
+        static CATALOG: OnceLock<Option<Catalog>> = OnceLock::new();
+        let catalog = CATALOG.get_or_init(|| catalog_for(&user_languages()));
+        if let Some(msgstr) = catalog.as_ref().and_then(|catalog| catalog.get(msgid)) {
+            return msgstr;
+        }
+    }
+    msgid
+}
+
+/// Substitute `args` for the `{}`s in (translated) `template`, in order
+pub fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut filled = parts.next().unwrap_or("").to_string();
+    for (part, arg) in parts.zip(args.iter().map(Some).chain(std::iter::repeat(None))) {
+        if let Some(arg) = arg {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+/// The user's preferred languages, most preferred first
+///
+/// `LANGUAGE` (a colon-separated list) takes priority, then the first of `LC_ALL`,
+/// `LC_MESSAGES` and `LANG` which is set.  The "C" and "POSIX" locales disable translation.
+#[cfg(feature = "l10n")]
+fn user_languages() -> Vec<String> {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    if locale == "C" || locale == "POSIX" {
+        return vec![];
+    }
+    let mut languages: Vec<String> = std::env::var("LANGUAGE")
+        .unwrap_or_default()
+        .split(':')
+        .filter(|language| !language.is_empty())
+        .map(str::to_string)
+        .collect();
+    if !locale.is_empty() {
+        languages.push(locale);
+    }
+    languages
+}
+
+/// The catalog for the first of `languages` (e.g. "de_AT.UTF-8", which falls back to "de") for
+/// which there is one
+#[cfg(feature = "l10n")]
+pub fn catalog_for(languages: &[String]) -> Option<Catalog> {
+    languages.iter().find_map(|language| {
+        let language = language.split(['.', '@']).next().unwrap_or("");
+        let base = language.split('_').next().unwrap_or("");
+        CATALOGS
+            .iter()
+            .find(|(name, _)| *name == language)
+            .or_else(|| CATALOGS.iter().find(|(name, _)| *name == base))
+            .map(|(_, po)| parse_po(po))
+    })
+}
+
+/// The translations in the PO file `po`, skipping its header and untranslated messages
+#[cfg(feature = "l10n")]
+pub fn parse_po(po: &str) -> Catalog {
+    let mut catalog = Catalog::new();
+    let (mut msgid, mut msgstr) = (String::new(), String::new());
+    let mut in_msgstr = false;
+    let mut add = |msgid: &mut String, msgstr: &mut String| {
+        if !msgid.is_empty() && !msgstr.is_empty() {
+            catalog.insert(std::mem::take(msgid), std::mem::take(msgstr));
+        }
+        msgid.clear();
+        msgstr.clear();
+    };
+    for line in po.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("msgid ") {
+            add(&mut msgid, &mut msgstr);
+            msgid = unquote(rest);
+            in_msgstr = false;
+        } else if let Some(rest) = line.strip_prefix("msgstr ") {
+            msgstr = unquote(rest);
+            in_msgstr = true;
+        } else if line.starts_with('"') {
+            if in_msgstr {
+                msgstr.push_str(&unquote(line));
+            } else {
+                msgid.push_str(&unquote(line));
+            }
+        }
+    }
+    add(&mut msgid, &mut msgstr);
+    catalog
+}
+
+/// The contents of the quoted PO string `quoted`, unescaped
+#[cfg(feature = "l10n")]
+fn unquote(quoted: &str) -> String {
+    let inner = quoted
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'))
+        .unwrap_or(quoted);
+    let mut unquoted = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unquoted.push('\n'),
+            Some('t') => unquoted.push('\t'),
+            Some(c) => unquoted.push(c),
+            None => (),
+        }
+    }
+    unquoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fill;
+
+    #[test]
+    fn fill_placeholders() {
+        assert_eq!(
+            "unknown distribution series `jamy'",
+            fill("unknown distribution series `{}'", &[&"jamy"])
+        );
+        assert_eq!("a 1 b  c", fill("a {} b {} c", &[&1]));
+    }
+
+    #[cfg(feature = "l10n")]
+    #[test]
+    fn catalogs() {
+        use super::{catalog_for, parse_po};
+
+        let catalog = parse_po(
+            "# comment\nmsgid \"\"\nmsgstr \"Content-Type: text/plain\\n\"\n\n\
+             msgid \"a \"\n\"b\"\nmsgstr \"c\\n\"\n\"d\"\n\nmsgid \"untranslated\"\nmsgstr \"\"\n",
+        );
+        assert_eq!(1, catalog.len());
+        assert_eq!("c\nd", catalog["a b"]);
+        let de = catalog_for(&["fr_FR".to_string(), "de_AT.UTF-8".to_string()]).unwrap();
+        assert_eq!(
+            "neueste stabile Version",
+            de["latest stable version"].as_str()
+        );
+        assert!(catalog_for(&["fr_FR".to_string()]).is_none());
+    }
+}
@@ -1,313 +1,2199 @@
-use chrono::Datelike;
+//! The shared implementation of the `*-distro-info` command-line tools.
+//!
+//! [`DistroInfoCommand`](struct.DistroInfoCommand.html) implements the selectors and output
+//! options common to all of the tools; derivative distros can use it to build their own
+//! `$distro-distro-info` tool, registering any selectors or output modes specific to them:
+//!
+//! ```no_run
+//! use distro_info::{DistroInfo, UbuntuDistroInfo};
+//! use distro_info_binaries::DistroInfoCommand;
+//!
+//! DistroInfoCommand::new("mydistro-distro-info")
+//!     .distro_name("MyDistro")
+//!     .selector("first", "the first ever version", |distro_info, _date| {
+//!         Ok(distro_info.iter().take(1).collect())
+//!     })
+//!     .output_mode("upper", "print the series in upper case", |distro_release| {
+//!         distro_release.series().to_uppercase()
+//!     })
+//!     .main::<UbuntuDistroInfo>();
+//! ```
+use std::ffi::OsString;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
 use chrono::NaiveDate;
-use chrono::Utc;
-use clap::{App, Arg, ArgGroup, ArgMatches};
-use distro_info::Distro;
-use distro_info::{DistroInfo, DistroRelease};
+use chrono::{DateTime, Utc};
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
+use distro_info::cadence::predict_next_release;
+use distro_info::compression;
+use distro_info::diff::DistroInfoDiff;
+use distro_info::export::{gantt, to_ics, GanttFormat};
+use distro_info::flavours::{load_flavour_eols, FLAVOURS_CSV_PATH};
+use distro_info::milestones::Milestone;
+use distro_info::policy::DistroPolicy;
+use distro_info::prediction::{next_expected_ubuntu_version, PredictedVersion};
+use distro_info::source::DataSource;
+use distro_info::status::SupportStatus;
+use distro_info::{Distro, DistroDataSource, DistroInfo, DistroRelease};
 use failure::{bail, format_err, Error, ResultExt};
+use l10n::{fill, gettext};
+use selectors::UnknownSeries;
+
+pub mod dates;
+pub mod json;
+pub mod l10n;
+pub mod man;
+pub mod nagios;
+pub mod outputs;
+pub mod selectors;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod update;
+
+pub use outputs::{Output, OutputContext};
+pub use selectors::Selector;
 
 pub const OUTDATED_MSG: &str = "Distribution data outdated.
 Please check for an update for distro-info-data. See /usr/share/doc/distro-info-data/README.Debian for details.";
 
-pub enum DaysMode {
-    Created,
-    Eol,
-    EolServer,
-    Release,
+/// The status to exit with when the requested information could not be displayed
+pub const EXIT_ERROR: i32 = 1;
+/// The status to exit with when an internal inconsistency is detected
+pub const EXIT_INTERNAL_ERROR: i32 = 2;
+
+/// The status `--check` exits with when the series is not in the data
+///
+/// This is the same as `EXIT_INTERNAL_ERROR`, which only means an internal error outside `--check`.
+pub const EXIT_UNKNOWN_SERIES: i32 = 2;
+
+/// An internal inconsistency (i.e. a bug), rather than a problem with the input or data
+#[derive(Debug)]
+pub struct InternalError(pub String);
+
+impl fmt::Display for InternalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let template = gettext("internal error: {}; please report a bug");
+        write!(f, "{}", fill(template, &[&self.0]))
+    }
+}
+
+impl std::error::Error for InternalError {}
+
+/// There were no releases to display, which usually means the data is out of date
+#[derive(Debug)]
+pub struct NoReleases;
+
+impl fmt::Display for NoReleases {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", gettext(OUTDATED_MSG))
+    }
+}
+
+impl std::error::Error for NoReleases {}
+
+/// How much a command reports on stderr, besides errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// `--quiet`: no warnings, and exit without a message if there are no releases to display
+    Quiet,
+    /// Warnings, such as that the data looks out of date
+    Normal,
+    /// `--verbose`: also report the data file and date used
+    Verbose,
+}
+
+impl Verbosity {
+    /// The verbosity requested by `--quiet` or `--verbose` in `matches`
+    pub fn from_matches(matches: &ArgMatches) -> Self {
+        if matches.is_present("quiet") {
+            Verbosity::Quiet
+        } else if matches.is_present("verbose") {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+/// An option in the "selector" group which replaces selecting releases, printing a report of its
+/// own and returning the status to exit with
+type Mode = fn(&DistroInfoCommand, &ArgMatches, &dyn DistroInfo, NaiveDate) -> Result<i32, Error>;
+
+/// The options which run a `Mode` rather than selecting releases
+const MODES: [(&str, Mode); 3] = [
+    ("check", DistroInfoCommand::check),
+    ("check-eol", DistroInfoCommand::check_eol),
+    ("upcoming", DistroInfoCommand::print_upcoming),
+];
+
+/// An output option which prints the selected releases as a whole document, rather than a line
+/// per release
+type Document = fn(&ArgMatches, Vec<&DistroRelease>, &OutputContext) -> String;
+
+/// The options which print a `Document`
+const DOCUMENTS: [(&str, Document); 2] = [("calendar", calendar), ("gantt", gantt_chart)];
+
+/// Whether a predicate holds for a release
+type Predicate = fn(&DistroRelease, &OutputContext) -> bool;
+
+/// The `--is-*` predicates on `--series`, which print nothing and exit 0 if they hold for every
+/// series given and 1 otherwise
+const PREDICATES: [(&str, &str, Predicate); 3] = [
+    (
+        "is-devel",
+        "exit 0 if the series is in development, and 1 otherwise",
+        |distro_release, context| {
+            context
+                .policy
+                .devel(context.distro_info, context.date)
+                .iter()
+                .any(|devel| devel.series() == distro_release.series())
+        },
+    ),
+    (
+        "is-lts",
+        "exit 0 if the series is an LTS release, and 1 otherwise",
+        |distro_release, context| context.policy.is_lts(distro_release),
+    ),
+    (
+        "is-supported",
+        "exit 0 if the series is supported, and 1 otherwise",
+        |distro_release, context| distro_release.supported_at(context.date),
+    ),
+];
+
+/// The milestone `--days` counts down to
+pub type DaysMode = Milestone;
+
+/// How `output` displays each release
+pub enum OutputMode<'a> {
+    /// `--format json`: a JSON object per release; see the [`json`](json/index.html) module
+    Json,
+    /// A line of the fields an `Output` gives; see the [`outputs`](outputs/index.html) module
+    Fields(&'a dyn Output),
 }
 
-pub enum OutputMode {
-    Codename,
-    FullName,
-    Release,
-    Suppress,
+struct RegisteredSelector {
+    name: String,
+    short: Option<String>,
+    help: String,
+    selector: Box<dyn Selector>,
 }
 
-/// Add arguments common to both ubuntu- and debian-distro-info to `app`
-pub fn add_common_args<'a>(app: App<'a, 'a>, additional_selectors: &'a [&str]) -> App<'a, 'a> {
-    let mut selectors = vec![
-        "all",
-        "devel",
-        "series",
-        "stable",
-        "supported",
-        "unsupported",
-    ];
-    selectors.extend(additional_selectors);
-    app.version("0.1.0")
-        .author("Daniel Watkins <daniel@daniel-watkins.co.uk>")
-        .arg(
-            Arg::with_name("all")
-                .short("a")
-                .long("all")
-                .help("list all known versions"),
+struct RegisteredOutput {
+    name: String,
+    short: Option<String>,
+    help: String,
+    output: Box<dyn Output>,
+}
+
+/// A `*-distro-info` command-line tool
+///
+/// This provides the selectors (`--all`, `--check`, `--devel`, `--future`, `--series`, `--stable`,
+/// `--supported`, `--unsupported`, `--upcoming`) and output options (`--codename`, `--fullname`,
+/// `--release`, `--links`, `--days`) common to all of the tools; further selectors and output modes
+/// can be registered with [`selector`](#method.selector),
+/// [`register_selector`](#method.register_selector) and [`output_mode`](#method.output_mode) and
+/// [`register_output_mode`](#method.register_output_mode), and distro-specific behaviour changed
+/// with [`policy`](#method.policy).
+pub struct DistroInfoCommand {
+    command_name: String,
+    distro_name: Option<String>,
+    policy: Option<Rc<dyn DistroPolicy>>,
+    flavours: bool,
+    selectors: Vec<RegisteredSelector>,
+    output_modes: Vec<RegisteredOutput>,
+}
+
+impl DistroInfoCommand {
+    /// Create a command called `command_name` (which is used in `--help` and error messages)
+    pub fn new(command_name: &str) -> Self {
+        Self {
+            command_name: command_name.to_string(),
+            distro_name: None,
+            policy: None,
+            flavours: false,
+            selectors: vec![],
+            output_modes: vec![],
+        }
+        .register_selector(
+            "all",
+            Some("a"),
+            "list all versions (with --date, those known at that date)",
+            selectors::All {
+                include_future: false,
+            },
+        )
+        .register_selector(
+            "devel",
+            Some("d"),
+            "latest development version",
+            selectors::Devel,
+        )
+        .register_selector(
+            "stable",
+            Some("s"),
+            "latest stable version",
+            selectors::Stable,
         )
-        .arg(
-            Arg::with_name("devel")
-                .short("d")
-                .long("devel")
-                .help("latest development version"),
+        .register_selector(
+            "supported",
+            None,
+            "list of all supported stable versions",
+            selectors::Supported,
         )
-        .arg(
-            Arg::with_name("series")
-                .long("series")
-                .takes_value(true)
-                .help("series to calculate the version for"),
+        .register_selector(
+            "unsupported",
+            None,
+            "list of all unsupported stable versions",
+            selectors::Unsupported,
         )
-        .arg(
-            Arg::with_name("stable")
-                .short("s")
-                .long("stable")
-                .help("latest stable version"),
+        .register_selector(
+            "future",
+            None,
+            "list of all versions planned but not yet released",
+            selectors::Future,
         )
-        .arg(
-            Arg::with_name("supported")
-                .long("supported")
-                .help("list of all supported stable versions"),
+        .register_selector(
+            "series",
+            None,
+            "series to calculate the version for (may be repeated)",
+            selectors::Series,
         )
-        .arg(
-            Arg::with_name("unsupported")
-                .long("unsupported")
-                .help("list of all unsupported stable versions"),
+        .register_selector(
+            "codename-of",
+            None,
+            "print the codename of a series, full codename or version",
+            selectors::Lookup(outputs::Codename),
         )
-        .arg(
-            Arg::with_name("codename")
-                .short("c")
-                .long("codename")
-                .help("print the codename (default)"),
+        .register_selector(
+            "release-of",
+            None,
+            "print the version of a series, full codename or version",
+            selectors::Lookup(outputs::Release),
         )
-        .arg(
-            Arg::with_name("fullname")
-                .short("f")
-                .long("fullname")
-                .help("print the full name"),
+        .register_selector(
+            "supported-until",
+            None,
+            "list the versions which will still be supported at <date> (as --date, but relative \
+             to --date)",
+            selectors::SupportedUntil,
         )
-        .arg(
-            Arg::with_name("release")
-                .short("r")
-                .long("release")
-                .help("print the release version"),
+        .register_output_mode(
+            "codename",
+            Some("c"),
+            "print the codename (default)",
+            outputs::Codename,
         )
-        .arg(
-            Arg::with_name("date")
-                .long("date")
-                .takes_value(true)
-                .help("date for calculating the version (default: today)"),
+        .register_output_mode(
+            "fullname",
+            Some("f"),
+            "print the full name",
+            outputs::FullName,
         )
-        .arg(
-            Arg::with_name("days")
-                .short("y")
-                .long("days")
-                .takes_value(true)
-                .default_value("release")
-                .possible_values(&["created", "eol", "eol-server", "release"])
-                .value_name("milestone")
-                .help("additionally, display days until milestone"),
+        .register_output_mode(
+            "release",
+            Some("r"),
+            "print the release version",
+            outputs::Release,
         )
-        .group(
+        .register_output_mode(
+            "links",
+            None,
+            "print links to the announcement, release notes and upgrade notes",
+            outputs::Links,
+        )
+        .register_output_mode(
+            "support-length",
+            None,
+            "print the series, its length of standard support in days and its support class \
+             (interim, lts, esm or elts)",
+            outputs::SupportLength,
+        )
+        .register_output_mode(
+            "phase",
+            None,
+            "print the series and the phase of support it is in (full, security, lts, elts or eol)",
+            outputs::Phase,
+        )
+        .register_output_mode(
+            "eol-age",
+            None,
+            "print the series and the number of days since its support ended",
+            outputs::EolAge,
+        )
+    }
+
+    /// The `ubuntu-distro-info` command
+    pub fn ubuntu() -> Self {
+        Self::new("ubuntu-distro-info")
+            .flavours()
+            .selector_with_short(
+                "latest",
+                "l",
+                "latest development or stable version",
+                |distro_info, date| {
+                    let devel_result = distro_info.ubuntu_devel(date);
+                    Ok(devel_result
+                        .last()
+                        .copied()
+                        .or_else(|| distro_info.stable(date))
+                        .into_iter()
+                        .collect())
+                },
+            )
+            .selector(
+                "lts",
+                "latest long term support (LTS) version",
+                |distro_info, date| match distro_info.latest_lts(date) {
+                    Some(distro_release) => Ok(vec![distro_release]),
+                    None => bail!("no LTS version known at {}", date),
+                },
+            )
+            .selector(
+                "previous-lts",
+                "the long term support (LTS) version before the latest",
+                |distro_info, date| match distro_info.previous_lts(date) {
+                    Some(distro_release) => Ok(vec![distro_release]),
+                    None => bail!("no previous LTS version known at {}", date),
+                },
+            )
+    }
+
+    /// The `debian-distro-info` command
+    pub fn debian() -> Self {
+        Self::new("debian-distro-info").selector_with_short(
+            "testing",
+            "t",
+            "current testing version",
+            // d-d-i --testing selection matches u-d-i --devel
+            |distro_info, date| Ok(distro_info.ubuntu_devel(date)),
+        )
+    }
+
+    /// Use `distro_name` in `--fullname` output, rather than the name of the `DistroInfo`'s distro
+    pub fn distro_name(mut self, distro_name: &str) -> Self {
+        self.distro_name = Some(distro_name.to_string());
+        self
+    }
+
+    /// Use `policy` rather than the built-in policy of the `DistroInfo`'s distro
+    ///
+    /// This determines the `--fullname` output (unless [`distro_name`](#method.distro_name) is
+    /// also given) and the releases `--devel` selects.
+    pub fn policy<P: DistroPolicy + 'static>(mut self, policy: P) -> Self {
+        let policy: Rc<dyn DistroPolicy> = Rc::new(policy);
+        self.policy = Some(policy.clone());
+        self.selector_with_short(
+            "devel",
+            "d",
+            "latest development version",
+            move |distro_info, date| Ok(policy.devel(distro_info, date)),
+        )
+    }
+
+    /// Accept `--flavour`, which answers for a flavour of the distro (e.g. Xubuntu) using the
+    /// EOLs in the supplementary [flavour table](../distro_info/flavours/index.html)
+    pub fn flavours(mut self) -> Self {
+        self.flavours = true;
+        self
+    }
+
+    /// Register a `--name` selector, displaying the releases returned by `select`
+    pub fn selector<F>(self, name: &str, help: &str, select: F) -> Self
+    where
+        F: for<'a> Fn(&'a dyn DistroInfo, NaiveDate) -> Result<Vec<&'a DistroRelease>, Error>
+            + 'static,
+    {
+        self.register_selector(name, None, help, select)
+    }
+
+    /// Register a `-short`/`--name` selector, displaying the releases returned by `select`
+    pub fn selector_with_short<F>(self, name: &str, short: &str, help: &str, select: F) -> Self
+    where
+        F: for<'a> Fn(&'a dyn DistroInfo, NaiveDate) -> Result<Vec<&'a DistroRelease>, Error>
+            + 'static,
+    {
+        self.register_selector(name, Some(short), help, select)
+    }
+
+    /// Register `selector` as the `--name` (and `-short`, if given) option
+    ///
+    /// Registering a selector with the name of an existing one replaces it.
+    pub fn register_selector<S: Selector + 'static>(
+        mut self,
+        name: &str,
+        short: Option<&str>,
+        help: &str,
+        selector: S,
+    ) -> Self {
+        let registered = RegisteredSelector {
+            name: name.to_string(),
+            short: short.map(|short| short.to_string()),
+            help: help.to_string(),
+            selector: Box::new(selector),
+        };
+        match self.selectors.iter_mut().find(|s| s.name == name) {
+            Some(existing) => *existing = registered,
+            None => self.selectors.push(registered),
+        }
+        self
+    }
+
+    /// Register a `--name` output mode, displaying the text returned by `output` for each release
+    pub fn output_mode<F>(self, name: &str, help: &str, output: F) -> Self
+    where
+        F: Fn(&DistroRelease) -> String + 'static,
+    {
+        self.register_output_mode(name, None, help, output)
+    }
+
+    /// Register `output` as the `--name` (and `-short`, if given) output mode
+    ///
+    /// Registering an output mode with the name of an existing one replaces it.
+    pub fn register_output_mode<O: Output + 'static>(
+        mut self,
+        name: &str,
+        short: Option<&str>,
+        help: &str,
+        output: O,
+    ) -> Self {
+        let registered = RegisteredOutput {
+            name: name.to_string(),
+            short: short.map(|short| short.to_string()),
+            help: help.to_string(),
+            output: Box::new(output),
+        };
+        match self.output_modes.iter_mut().find(|o| o.name == name) {
+            Some(existing) => *existing = registered,
+            None => self.output_modes.push(registered),
+        }
+        self
+    }
+
+    /// The names of the options in the "selector" group, of which exactly one must be given
+    fn selector_names(&self) -> Vec<&str> {
+        let mut selectors: Vec<&str> = MODES.iter().map(|(name, _)| *name).collect();
+        selectors.extend(self.selectors.iter().map(|s| s.name.as_str()));
+        selectors
+    }
+
+    /// The names of the options in the "output" group, of which at most one may be given
+    fn output_names(&self) -> Vec<&str> {
+        let mut outputs = vec!["format"];
+        outputs.extend(DOCUMENTS.iter().map(|(name, _)| *name));
+        outputs.extend(PREDICATES.iter().map(|(name, _, _)| *name));
+        outputs.extend(self.output_modes.iter().map(|o| o.name.as_str()));
+        outputs
+    }
+
+    /// A roff man page for this command, documenting all of its options
+    ///
+    /// This is what the `generate-man` command prints; see the [`man`](man/index.html) module.
+    pub fn man_page(&self) -> String {
+        let mut help = vec![];
+        self.app()
+            .set_term_width(0)
+            .write_help(&mut help)
+            .expect("writing to a Vec cannot fail");
+        man::render(
+            &self.command_name,
+            &String::from_utf8_lossy(&help),
+            &self.selector_names(),
+            &self.output_names(),
+        )
+    }
+
+    /// Build the clap `App` for this command
+    pub fn app(&self) -> App<'_, '_> {
+        let selectors = self.selector_names();
+        let outputs = self.output_names();
+
+        let mut app = App::new(self.command_name.as_str())
+            .version("0.1.0")
+            .author("Daniel Watkins <daniel@daniel-watkins.co.uk>")
+            // The commands work on the data as a whole, rather than selecting releases
+            .settings(&[
+                AppSettings::SubcommandsNegateReqs,
+                AppSettings::DisableHelpSubcommand,
+                AppSettings::VersionlessSubcommands,
+            ])
+            .subcommand(
+                SubCommand::with_name("generate-man")
+                    .about(gettext("print a man page for this command, in roff")),
+            )
+            .subcommand(update::subcommand())
+            .subcommand(SubCommand::with_name("data-version").about(gettext(
+                "report the path, modification time and package version of the data",
+            )))
+            .subcommand(
+                SubCommand::with_name("diff")
+                    .about(gettext(
+                        "list the releases added, removed and changed between two data files \
+                         (exits 1 if there are any differences)",
+                    ))
+                    .arg(Arg::with_name("old").required(true))
+                    .arg(Arg::with_name("new").required(true)),
+            )
+            .arg(
+                Arg::with_name("include-future")
+                    .long("include-future")
+                    .requires("all")
+                    .help(gettext(
+                        "with --all, also list versions not yet created at --date",
+                    )),
+            )
+            .arg(
+                Arg::with_name("check")
+                    .long("check")
+                    .takes_value(true)
+                    .value_name("codename")
+                    .help(gettext(
+                        "report on the support status of a series; exits 0 if it is supported, \
+                         1 if it is EOL and 2 if it is unknown",
+                    )),
+            )
+            .arg(
+                Arg::with_name("check-eol")
+                    .long("check-eol")
+                    .takes_value(true)
+                    .value_name("series")
+                    .help(gettext(
+                        "check a series' end of life as a Nagios plugin, exiting 0 (OK), 1 \
+                         (WARNING), 2 (CRITICAL) or 3 (UNKNOWN)",
+                    )),
+            )
+            .arg(
+                Arg::with_name("warn-days")
+                    .long("warn-days")
+                    .takes_value(true)
+                    .value_name("days")
+                    .requires("check-eol")
+                    .help(gettext(
+                        "with --check-eol, warn this many days before the EOL (default: 90)",
+                    )),
+            )
+            .arg(
+                Arg::with_name("crit-days")
+                    .long("crit-days")
+                    .takes_value(true)
+                    .value_name("days")
+                    .requires("check-eol")
+                    .help(gettext(
+                        "with --check-eol, be critical this many days before the EOL \
+                         (default: 30)",
+                    )),
+            )
+            .arg(
+                Arg::with_name("upcoming")
+                    .long("upcoming")
+                    .takes_value(true)
+                    .value_name("days")
+                    .help(gettext(
+                        "list the milestones of all releases in the next <days> days",
+                    )),
+            )
+            .arg(Arg::with_name("strict").long("strict").help(gettext(
+                "match --series, --codename-of, --release-of, --check and --check-eol exactly, \
+                 including case",
+            )))
+            .arg(
+                Arg::with_name("format")
+                    .long("format")
+                    .takes_value(true)
+                    .value_name("template")
+                    .help(gettext(
+                        "print each release using a template such as '{series}\\t{eol}'; \
+                         placeholders are {series}, {codename}, {version}, {created}, \
+                         {release}, {eol}, {eol-server}, {eol-lts}, {eol-esm}, {eol-elts}, \
+                         {eol-legacy} and {days-to-eol}, and {{ and }} print braces.  'json' \
+                         prints a JSON object per release, and reports errors as JSON on stderr",
+                    )),
+            )
+            .args(&PREDICATES.map(|(name, help, _)| {
+                Arg::with_name(name)
+                    .long(name)
+                    .requires("series")
+                    .help(gettext(help))
+            }))
+            .arg(Arg::with_name("calendar").long("calendar").help(gettext(
+                "print an iCalendar (.ics) feed of the releases' release and end-of-life \
+                         dates",
+            )))
+            .arg(
+                Arg::with_name("gantt")
+                    .long("gantt")
+                    .takes_value(true)
+                    .value_name("format")
+                    .possible_values(&GanttFormat::NAMES)
+                    .help(gettext(
+                        "print the releases' support windows, one bar per support tier, as a \
+                         Gantt chart dataset in json, mermaid or plantuml",
+                    )),
+            )
+            .arg(
+                Arg::with_name("reverse")
+                    .long("reverse")
+                    .help(gettext("display the newest versions first")),
+            )
+            .arg(
+                Arg::with_name("limit")
+                    .long("limit")
+                    .takes_value(true)
+                    .value_name("N")
+                    .help(gettext("display at most N versions")),
+            )
+            .arg(
+                Arg::with_name("fallback")
+                    .long("fallback")
+                    .takes_value(true)
+                    .possible_values(&["predict"])
+                    .requires("devel")
+                    .conflicts_with("days")
+                    .help(gettext(
+                        "with --devel and --release or --format json, if the data has no \
+                         development version, print the version predicted from the release \
+                         cadence (Ubuntu only)",
+                    )),
+            )
+            .arg(
+                Arg::with_name("quiet")
+                    .short("q")
+                    .long("quiet")
+                    .conflicts_with("verbose")
+                    .help(gettext(
+                        "don't print warnings, or a message if there is nothing to display",
+                    )),
+            )
+            .arg(
+                Arg::with_name("verbose")
+                    .short("v")
+                    .long("verbose")
+                    .help(gettext("report the data file and date used on stderr")),
+            )
+            .arg(
+                Arg::with_name("csv")
+                    .long("csv")
+                    .takes_value(true)
+                    .value_name("path")
+                    .allow_hyphen_values(true)
+                    .help(gettext(
+                        "read the release data from a CSV file (or stdin, if <path> is -) rather \
+                         than the installed distro-info-data",
+                    )),
+            )
+            .arg(
+                Arg::with_name("datadir")
+                    .long("datadir")
+                    .takes_value(true)
+                    .value_name("dir")
+                    .conflicts_with("csv")
+                    .help(gettext(
+                        "read the release data from the files in <dir> rather than the installed \
+                         distro-info-data",
+                    )),
+            )
+            .arg(
+                Arg::with_name("no-stale-warning")
+                    .long("no-stale-warning")
+                    .help(gettext(
+                        "don't warn on stderr if the data looks out of date",
+                    )),
+            )
+            .arg(
+                Arg::with_name("date")
+                    .long("date")
+                    .takes_value(true)
+                    .allow_hyphen_values(true)
+                    .help(gettext(
+                        "date for calculating the version (default: today); YYYY-MM-DD, \
+                         YYYY-Qn or relative, e.g. today+30d or -1y",
+                    )),
+            )
+            .arg(
+                Arg::with_name("days")
+                    .short("y")
+                    .long("days")
+                    .takes_value(true)
+                    .default_value("release")
+                    .value_name("milestone")
+                    .help(gettext(
+                        "additionally, display days until milestone (created, release, eol, \
+                         or the distro's other EOLs, e.g. eol-esm)",
+                    )),
+            );
+        if self.flavours {
+            app = app.arg(
+                Arg::with_name("flavour")
+                    .long("flavour")
+                    .takes_value(true)
+                    .value_name("flavour")
+                    .help(gettext("use the support period of a flavour, e.g. xubuntu")),
+            );
+        }
+        for selector in &self.selectors {
+            let mut arg = Arg::with_name(selector.name.as_str())
+                .long(selector.name.as_str())
+                .help(gettext(&selector.help));
+            if let Some(short) = &selector.short {
+                arg = arg.short(short.as_str());
+            }
+            app = app.arg(selector.selector.arg(arg));
+        }
+        for output_mode in &self.output_modes {
+            let mut arg = Arg::with_name(output_mode.name.as_str())
+                .long(output_mode.name.as_str())
+                .help(gettext(&output_mode.help));
+            if let Some(short) = &output_mode.short {
+                arg = arg.short(short.as_str());
+            }
+            app = app.arg(arg);
+        }
+        app.group(
             ArgGroup::with_name("selector")
                 .args(&selectors)
                 .required(true),
         )
-        .group(ArgGroup::with_name("output").args(&["codename", "fullname", "release"]))
+        .group(ArgGroup::with_name("output").args(&outputs))
+    }
+
+    /// Run the command with the given parsed arguments against `distro_info`
+    ///
+    /// Returns the status the process should exit with.
+    pub fn run(&self, matches: &ArgMatches, distro_info: &dyn DistroInfo) -> Result<i32, Error> {
+        let date = match matches.value_of("date") {
+            Some(date_str) => dates::parse_date(date_str, today()).ok_or_else(|| {
+                format_err!(
+                    "Failed to parse date '{}'; must be YYYY-MM-DD, YYYY-Qn or relative to \
+                     today, e.g. today+30d",
+                    date_str
+                )
+            })?,
+            None => today(),
+        };
+        let verbosity = Verbosity::from_matches(matches);
+        if verbosity == Verbosity::Verbose {
+            eprintln!("{}: using date {}", self.command_name, date);
+        }
+        if verbosity > Verbosity::Quiet
+            && !matches.is_present("no-stale-warning")
+            && distro_info.is_probably_stale(date)
+        {
+            self.warn(matches, "stale-data", OUTDATED_MSG);
+        }
+        if let Some((_, mode)) = MODES.iter().find(|(name, _)| matches.is_present(name)) {
+            return mode(self, matches, distro_info, date);
+        }
+        let selector = self.given_selector(matches)?;
+        // A prediction has only a version and an expected release date to display
+        if matches.is_present("fallback")
+            && !(matches.is_present("release") || matches.value_of("format") == Some(json::FORMAT))
+        {
+            bail!("--fallback=predict needs --release or --format json");
+        }
+        let explicit_output = self
+            .output_names()
+            .into_iter()
+            .find(|output| matches.is_present(output));
+        if let (Some(_), Some(output)) = (selector.selector.output(), explicit_output) {
+            bail!("--{} cannot be used with --{}", selector.name, output);
+        }
+        let distro_releases = self.select(matches, date, distro_info)?;
+        let policy = match &self.policy {
+            Some(policy) => policy.as_ref(),
+            None => distro_info.distro().policy(),
+        };
+        let renamed;
+        let policy = match &self.distro_name {
+            Some(distro_name) => {
+                renamed = Renamed {
+                    name: distro_name,
+                    policy,
+                };
+                &renamed as &dyn DistroPolicy
+            }
+            None => policy,
+        };
+        let context = OutputContext {
+            date,
+            distro: distro_info.distro(),
+            distro_info,
+            policy,
+        };
+        if distro_releases.is_empty() && matches.value_of("fallback") == Some("predict") {
+            if let Some(predicted) = predict_devel(distro_info, date) {
+                return self.print_predicted(matches, predicted, &context);
+            }
+        }
+        if let Some((_, _, holds)) = PREDICATES
+            .iter()
+            .find(|(predicate, _, _)| matches.is_present(predicate))
+        {
+            let all_hold = distro_releases
+                .into_iter()
+                .all(|distro_release| holds(distro_release, &context));
+            return Ok(if all_hold { 0 } else { EXIT_ERROR });
+        }
+        if let Some((_, document)) = DOCUMENTS.iter().find(|(name, _)| matches.is_present(name)) {
+            if distro_releases.is_empty() {
+                return Err(NoReleases.into());
+            }
+            let document = document(matches, distro_releases, &context);
+            write!(io::stdout().lock(), "{}", document)?;
+            return Ok(0);
+        }
+        self.print_lines(matches, selector, distro_releases, &context)
+    }
+
+    /// Print the version `--fallback=predict` predicted (as a JSON object with `--format json`),
+    /// with a warning that it is a prediction
+    fn print_predicted(
+        &self,
+        matches: &ArgMatches,
+        predicted: PredictedVersion,
+        context: &OutputContext,
+    ) -> Result<i32, Error> {
+        if Verbosity::from_matches(matches) > Verbosity::Quiet {
+            let mut message = format!(
+                "no development version in the data; {} is a prediction",
+                predicted
+            );
+            if let Some(release) = predict_next_release(context.distro_info, context.date) {
+                let (earliest, latest) = release.window;
+                message += &format!(
+                    ", expected between {} and {} ({})",
+                    earliest,
+                    latest,
+                    release.notes.join("; ")
+                );
+            }
+            self.warn(matches, "predicted", &message);
+        }
+        if matches.value_of("format") == Some(json::FORMAT) {
+            writeln!(io::stdout().lock(), "{}", json::predicted_json(&predicted))?;
+        } else {
+            writeln!(io::stdout().lock(), "{}", predicted)?;
+        }
+        Ok(0)
+    }
+
+    /// Print a line (or JSON object) for each of `distro_releases`, as the output options in
+    /// `matches` (or those implied by `selector`) say
+    fn print_lines(
+        &self,
+        matches: &ArgMatches,
+        selector: &RegisteredSelector,
+        distro_releases: Vec<&DistroRelease>,
+        context: &OutputContext,
+    ) -> Result<i32, Error> {
+        let days_mode = match matches.value_of("days") {
+            _ if matches.occurrences_of("days") == 0 => None,
+            Some(value) => Some(days_mode(context.policy, value)?),
+            None => None,
+        };
+        let registered_output = self
+            .output_modes
+            .iter()
+            .find(|output_mode| matches.is_present(&output_mode.name));
+        let format;
+        let output_mode = if let Some(output) = selector.selector.output() {
+            OutputMode::Fields(output)
+        } else if matches.value_of("format") == Some(json::FORMAT) {
+            OutputMode::Json
+        } else if let Some(template) = matches.value_of("format") {
+            format = outputs::Format(template);
+            OutputMode::Fields(&format)
+        } else if let Some(output_mode) = registered_output {
+            OutputMode::Fields(output_mode.output.as_ref())
+        } else if days_mode.is_none() {
+            // This should be the default output _unless_ --days is specified
+            OutputMode::Fields(&outputs::Codename)
+        } else {
+            OutputMode::Fields(&outputs::Suppress)
+        };
+        output(distro_releases, &output_mode, &days_mode, context)?;
+        Ok(0)
+    }
+
+    /// `--check-eol`: report on a series' EOL as a Nagios plugin
+    fn check_eol(
+        &self,
+        matches: &ArgMatches,
+        distro_info: &dyn DistroInfo,
+        date: NaiveDate,
+    ) -> Result<i32, Error> {
+        let days = |name, default| match matches.value_of(name) {
+            Some(days) => days
+                .parse::<i64>()
+                .context(format!("Failed to parse '{}' as a number of days", days)),
+            None => Ok(default),
+        };
+        let thresholds = nagios::Thresholds {
+            warn_days: days("warn-days", 90)?,
+            crit_days: days("crit-days", 30)?,
+        };
+        let codename = matches.value_of("check-eol").unwrap_or_default();
+        let mode = selectors::match_mode(matches);
+        let (status, line) = nagios::check_eol(distro_info, codename, mode, date, thresholds);
+        writeln!(io::stdout().lock(), "{}", line)?;
+        Ok(status)
+    }
+
+    /// `--upcoming`: list the milestones in the next days
+    fn print_upcoming(
+        &self,
+        matches: &ArgMatches,
+        distro_info: &dyn DistroInfo,
+        date: NaiveDate,
+    ) -> Result<i32, Error> {
+        let horizon = matches.value_of("upcoming").unwrap_or_default();
+        let horizon = horizon
+            .parse::<u32>()
+            .context(format!("Failed to parse '{}' as a number of days", horizon))?;
+        let mut stdout = io::stdout().lock();
+        for line in upcoming(distro_info, date, horizon) {
+            writeln!(stdout, "{}", line)?;
+        }
+        Ok(0)
+    }
+
+    /// Select releases using whichever of the command's selectors is present in `matches`, in
+    /// the order given by `--reverse` and `--limit`
+    pub fn select<'a>(
+        &self,
+        matches: &ArgMatches,
+        date: NaiveDate,
+        distro_info: &'a dyn DistroInfo,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        let limit = match matches.value_of("limit") {
+            Some(limit) => Some(
+                limit
+                    .parse::<NonZeroUsize>()
+                    .context(format!(
+                        "Failed to parse '{}' as a number of versions (at least 1)",
+                        limit
+                    ))?
+                    .get(),
+            ),
+            None => None,
+        };
+        let ordering = selectors::Ordering {
+            reverse: matches.is_present("reverse"),
+            limit,
+        };
+        Ok(ordering.apply(self.select_unordered(matches, date, distro_info)?))
+    }
+
+    fn select_unordered<'a>(
+        &self,
+        matches: &ArgMatches,
+        date: NaiveDate,
+        distro_info: &'a dyn DistroInfo,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        let selector = self.given_selector(matches)?;
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("selector", name = %selector.name, %date).entered();
+        let args = selectors::SelectorArgs {
+            values: matches
+                .values_of(&selector.name)
+                .map(|values| values.collect())
+                .unwrap_or_default(),
+            matches: Some(matches),
+        };
+        selector.selector.select(distro_info, date, &args)
+    }
+
+    /// The registered selector present in `matches`
+    fn given_selector(&self, matches: &ArgMatches) -> Result<&RegisteredSelector, Error> {
+        self.selectors
+            .iter()
+            .find(|selector| matches.is_present(&selector.name))
+            .ok_or_else(|| InternalError("no selector given".to_string()).into())
+    }
+
+    /// Print a warning on stderr, as JSON (with `code`) if `--format json` was given
+    fn warn(&self, matches: &ArgMatches, code: &str, message: &str) {
+        if matches.value_of("format") == Some(json::FORMAT) {
+            eprintln!("{}", json::warning_json(code, message));
+        } else {
+            let template = gettext("{}: warning: {}");
+            eprintln!(
+                "{}",
+                fill(template, &[&self.command_name, &gettext(message)])
+            );
+        }
+    }
+
+    /// `--check`: print a support status report for a series
+    ///
+    /// Returns 0 if it is supported and 1 if it has reached its EOL; if it is unknown, the error
+    /// is an `UnknownSeries`, for which the process exits with `EXIT_UNKNOWN_SERIES`.
+    fn check(
+        &self,
+        matches: &ArgMatches,
+        distro_info: &dyn DistroInfo,
+        date: NaiveDate,
+    ) -> Result<i32, Error> {
+        let codename = matches.value_of("check").unwrap_or_default();
+        let mode = selectors::match_mode(matches);
+        let status = match SupportStatus::for_codename_with(distro_info, codename, mode, date) {
+            Some(status) => status,
+            None => {
+                return Err(UnknownSeries {
+                    series: codename.to_string(),
+                    suggestion: distro_info
+                        .suggest_series(codename)
+                        .first()
+                        .map(|suggestion| suggestion.to_string()),
+                }
+                .into())
+            }
+        };
+        if matches.value_of("format") == Some(json::FORMAT) {
+            writeln!(io::stdout().lock(), "{}", json::status_json(&status))?;
+            return Ok(if status.supported() { 0 } else { 1 });
+        }
+        let yes_no = |value| if value { "yes" } else { "no" };
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "series: {}", status.release().series())?;
+        writeln!(stdout, "supported: {}", yes_no(status.supported()))?;
+        match status.days_to_eol() {
+            Some(days) => writeln!(stdout, "days-to-eol: {}", days)?,
+            None => writeln!(stdout, "days-to-eol: (unknown)")?,
+        }
+        writeln!(stdout, "esm-available: {}", yes_no(status.esm_available()))?;
+        if let Some(successor) = status.successor() {
+            writeln!(stdout, "successor: {}", successor.series())?;
+        }
+        Ok(if status.supported() { 0 } else { 1 })
+    }
+
+    /// Load the data for `T` (from the `--csv` file or `--datadir` in `matches`, if any), as seen
+    /// by users of the `--flavour` in `matches` (if any)
+    pub fn load<T: DistroDataSource>(&self, matches: &ArgMatches) -> Result<T, Error> {
+        let distro_info = match matches.value_of("csv") {
+            Some("-") => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read CSV data from stdin")?;
+                T::from_csv_str(&input)?
+            }
+            Some(path) => {
+                T::from_path(path).map_err(|e| format_err!("failed to read {}: {}", path, e))?
+            }
+            None => match datadir_path(matches, T::csv_path()) {
+                Some(path) => T::from_path(&path)
+                    .map_err(|e| format_err!("failed to read {}: {}", path.display(), e))?,
+                None => load_distro_info::<T>()?,
+            },
+        };
+        match matches.value_of("flavour") {
+            Some(flavour) => {
+                let flavours_path = datadir_path(matches, FLAVOURS_CSV_PATH)
+                    .unwrap_or_else(|| PathBuf::from(FLAVOURS_CSV_PATH));
+                Ok(distro_info
+                    .with_flavour_eols(&load_flavour_eols(flavours_path)?)
+                    .for_flavour(flavour))
+            }
+            None => Ok(distro_info),
+        }
+    }
+
+    /// Parse the process's arguments, load the data for `T` and run the command
+    ///
+    /// Errors are reported on stderr, and cause the process to exit with the status given by
+    /// [`exit_status`](fn.exit_status.html); otherwise the process exits with the status returned
+    /// by [`run`](#method.run).
+    pub fn main<T: DistroDataSource>(&self) {
+        self.main_from::<T, _>(::std::env::args_os())
+    }
+
+    /// As [`main`](#method.main), but parsing `args` (which start with the program name) rather
+    /// than the process's arguments
+    pub fn main_from<T: DistroDataSource, I>(&self, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<OsString> + Clone,
+    {
+        let matches = self.app().get_matches_from(args);
+        let verbosity = Verbosity::from_matches(&matches);
+        let result = match matches.subcommand() {
+            ("generate-man", _) => write_stdout(&self.man_page()),
+            ("data-version", _) => {
+                let data_source = match datadir_path(&matches, T::csv_path()) {
+                    Some(path) => DataSource::for_csv_path(&path),
+                    None => T::data_source(),
+                };
+                write_stdout(&data_version(&data_source))
+            }
+            ("update-data", Some(update_matches)) => {
+                let public_key = update_matches.value_of("public-key");
+                update::update_data::<T>(update::DATA_URL, public_key).and_then(|path| {
+                    write_stdout(&format!(
+                        "{}: updated {}\n",
+                        self.command_name,
+                        path.display()
+                    ))
+                })
+            }
+            ("diff", Some(diff_matches)) => {
+                match (diff_matches.value_of("old"), diff_matches.value_of("new")) {
+                    (Some(old), Some(new)) => diff::<T>(old, new),
+                    _ => Err(InternalError("diff needs two paths".to_string()).into()),
+                }
+            }
+            _ => {
+                if verbosity == Verbosity::Verbose {
+                    let path = match matches.value_of("csv") {
+                        Some("-") => "stdin".to_string(),
+                        Some(path) => path.to_string(),
+                        None => datadir_path(&matches, T::csv_path())
+                            .unwrap_or_else(|| T::data_source().path)
+                            .display()
+                            .to_string(),
+                    };
+                    eprintln!("{}: using data from {}", self.command_name, path);
+                }
+                self.load::<T>(&matches)
+                    .and_then(|distro_info| self.run(&matches, &distro_info))
+            }
+        };
+        match result {
+            Ok(0) => {}
+            Ok(status) => ::std::process::exit(status),
+            // The reader went away (e.g. `| head -1`), having read all it wanted
+            Err(ref e) if is_broken_pipe(e) => {}
+            // Monitoring expects a status line, and UNKNOWN, for any failure of a plugin
+            Err(ref e) if matches.is_present("check-eol") => {
+                let line = nagios::status_line(nagios::UNKNOWN, &e.to_string(), None);
+                let _ = writeln!(io::stdout().lock(), "{}", line);
+                ::std::process::exit(nagios::UNKNOWN);
+            }
+            Err(ref e)
+                if verbosity == Verbosity::Quiet && e.downcast_ref::<NoReleases>().is_some() =>
+            {
+                ::std::process::exit(mode_exit_status(&matches, e));
+            }
+            Err(ref e) if matches.value_of("format") == Some(json::FORMAT) => {
+                eprintln!("{}", json::error_json(e));
+                ::std::process::exit(mode_exit_status(&matches, e));
+            }
+            Err(ref e) => {
+                eprintln!("{}: {}", self.command_name, e);
+                ::std::process::exit(mode_exit_status(&matches, e));
+            }
+        }
+    }
 }
 
-pub fn common_run(matches: &ArgMatches, distro_info: &impl DistroInfo) -> Result<(), Error> {
-    let date = match matches.value_of("date") {
-        Some(date_str) => NaiveDate::parse_from_str(date_str, "%Y-%m-%d").context(format!(
-            "Failed to parse date '{}'; must be YYYY-MM-DD format",
-            date_str
-        ))?,
-        None => today(),
-    };
-    let distro_releases_iter = select_distro_releases(&matches, date, distro_info)?;
-    let days_mode = if matches.occurrences_of("days") == 0 {
-        None
+/// Write `text` to stdout, returning the status to exit with
+fn write_stdout(text: &str) -> Result<i32, Error> {
+    write!(io::stdout().lock(), "{}", text)?;
+    Ok(0)
+}
+
+/// Whether `error` is from writing to a pipe whose reader has gone away
+fn is_broken_pipe(error: &Error) -> bool {
+    error
+        .downcast_ref::<io::Error>()
+        .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+}
+
+/// The status a process should exit with after encountering `error`
+///
+/// This is `EXIT_INTERNAL_ERROR` for an [`InternalError`](struct.InternalError.html), and
+/// `EXIT_ERROR` for anything else (such as there being no releases to display).
+pub fn exit_status(error: &Error) -> i32 {
+    if error.downcast_ref::<InternalError>().is_some() {
+        EXIT_INTERNAL_ERROR
     } else {
-        matches.value_of("days").map(|value| match value {
-            "created" => DaysMode::Created,
-            "eol" => DaysMode::Eol,
-            "eol-server" => DaysMode::EolServer,
-            "release" => DaysMode::Release,
-            _ => panic!("unknown days mode found; please report a bug"),
-        })
-    };
-    let distro_name = distro_info.distro().to_string();
-    if matches.is_present("fullname") {
-        output(
-            distro_name,
-            distro_releases_iter,
-            &OutputMode::FullName,
-            &days_mode,
-            date,
-        )?;
-    } else if matches.is_present("release") {
-        output(
-            distro_name,
-            distro_releases_iter,
-            &OutputMode::Release,
-            &days_mode,
-            date,
-        )?;
-    } else if matches.is_present("codename") || days_mode.is_none() {
-        // This should be the default output _unless_ --days is specified
-        output(
-            distro_name,
-            distro_releases_iter,
-            &OutputMode::Codename,
-            &days_mode,
-            date,
-        )?;
+        EXIT_ERROR
+    }
+}
+
+/// The status to exit with after encountering `error` with the arguments in `matches`
+///
+/// This is as [`exit_status`](fn.exit_status.html) says, except that `--check` exits with
+/// `EXIT_UNKNOWN_SERIES` if its series is not in the data.
+fn mode_exit_status(matches: &ArgMatches, error: &Error) -> i32 {
+    if matches.is_present("check") && error.downcast_ref::<UnknownSeries>().is_some() {
+        EXIT_UNKNOWN_SERIES
     } else {
-        output(
-            distro_name,
-            distro_releases_iter,
-            &OutputMode::Suppress,
-            &days_mode,
-            date,
-        )?;
+        exit_status(error)
     }
-    Ok(())
+}
+
+/// A policy which overrides the display name of another
+struct Renamed<'a> {
+    name: &'a str,
+    policy: &'a dyn DistroPolicy,
+}
+
+impl<'a> DistroPolicy for Renamed<'a> {
+    fn display_name(&self) -> &str {
+        self.name
+    }
+
+    fn is_lts(&self, distro_release: &DistroRelease) -> bool {
+        self.policy.is_lts(distro_release)
+    }
+
+    fn devel<'b>(
+        &self,
+        distro_info: &'b dyn DistroInfo,
+        date: NaiveDate,
+    ) -> Vec<&'b DistroRelease> {
+        self.policy.devel(distro_info, date)
+    }
+
+    fn max_series_gap(&self) -> Option<u32> {
+        self.policy.max_series_gap()
+    }
+
+    fn supported_milestones(&self) -> &'static [Milestone] {
+        self.policy.supported_milestones()
+    }
+
+    fn resolve_alias<'b>(
+        &self,
+        distro_info: &'b dyn DistroInfo,
+        alias: &str,
+        date: NaiveDate,
+    ) -> Option<&'b DistroRelease> {
+        self.policy.resolve_alias(distro_info, alias, date)
+    }
+}
+
+/// Load the data for a distro, using the binary cache of parsed data when it is enabled
+///
+/// If the distro's CSV file is missing, what release data can be is inferred from apt's metadata.
+pub fn load_distro_info<T: DistroDataSource>() -> Result<T, Error> {
+    if !T::data_path().exists() {
+        return T::new_or_inferred();
+    }
+    #[cfg(feature = "cache")]
+    return T::new_cached();
+    #[cfg(not(feature = "cache"))]
+    return T::new();
 }
 
 fn determine_day_delta(current_date: NaiveDate, target_date: NaiveDate) -> i64 {
     target_date.signed_duration_since(current_date).num_days()
 }
 
+/// Print a line for each of `distro_releases`, with the `--days` count (if any) for `context`'s
+/// date
 pub fn output(
-    distro_name: &str,
     distro_releases: Vec<&DistroRelease>,
     output_mode: &OutputMode,
     days_mode: &Option<DaysMode>,
-    date: NaiveDate,
+    context: &OutputContext,
 ) -> Result<(), Error> {
-    if distro_releases.len() == 0 {
-        bail!(OUTDATED_MSG);
+    if distro_releases.is_empty() {
+        return Err(NoReleases.into());
     }
+    let date = context.date;
+    let mut stdout = io::stdout().lock();
     for distro_release in distro_releases {
-        let mut output_parts = vec![];
-        match output_mode {
-            OutputMode::Codename => output_parts.push(distro_release.series().to_string()),
-            OutputMode::Release => output_parts.push(
-                distro_release
-                    .version()
-                    .as_ref()
-                    .unwrap_or_else(|| distro_release.series())
-                    .to_string(),
-            ),
-            OutputMode::FullName => output_parts.push(format!(
-                "{} {} \"{}\"",
-                distro_name,
-                match distro_release.version() {
-                    Some(version) => version,
-                    None => "",
-                },
-                &distro_release.codename()
-            )),
-            OutputMode::Suppress => (),
-        }
+        let mut output_parts = match output_mode {
+            OutputMode::Fields(output) => output.fields(distro_release, context)?,
+            // Printed below, with any --days count as a field
+            OutputMode::Json => vec![],
+        };
         let target_date = match days_mode {
             Some(DaysMode::Created) => Some(distro_release.created().ok_or(format_err!(
                 "No creation date found for {}",
                 &distro_release.series()
             ))?),
-            Some(DaysMode::Eol) => *distro_release.eol(),
-            Some(DaysMode::EolServer) => *distro_release.eol_server(),
             Some(DaysMode::Release) => Some(distro_release.release().ok_or(format_err!(
                 "No release date found for {}",
                 &distro_release.series()
             ))?),
+            Some(milestone) => milestone.date(distro_release),
             None => None,
         };
+        if let OutputMode::Json = output_mode {
+            let mut value = json::release_json(distro_release);
+            if days_mode.is_some() {
+                value["days"] = target_date
+                    .map(|target_date| determine_day_delta(date, target_date))
+                    .into();
+            }
+            writeln!(stdout, "{}", value)?;
+            continue;
+        }
         match target_date {
             Some(target_date) => {
                 output_parts.push(format!("{}", determine_day_delta(date, target_date)));
             }
-            None => match days_mode {
-                Some(DaysMode::EolServer) | Some(DaysMode::Eol) => {
+            None => {
+                if days_mode.is_some() {
                     output_parts.push("(unknown)".to_string())
                 }
-                _ => (),
-            },
+            }
         };
         if !output_parts.is_empty() {
-            println!("{}", output_parts.join(" "));
+            writeln!(stdout, "{}", output_parts.join(" "))?;
         }
     }
     Ok(())
 }
 
-pub fn select_distro_releases<'a>(
-    matches: &ArgMatches,
+/// Expand a `--format` template for `distro_release`
+///
+/// The placeholders are `{series}`, `{codename}`, `{version}`, `{created}`, `{release}`, `{eol}`,
+/// `{eol-server}`, `{eol-lts}`, `{eol-esm}`, `{eol-elts}`, `{eol-legacy}` and `{days-to-eol}`
+/// (from `date`); unknown values expand to nothing, except `{days-to-eol}`, which expands to
+/// "(unknown)" as with `--days`.  `{{` and `}}` are replaced by `{` and `}`, and `\t`, `\n` and
+/// `\\` by a tab, newline and backslash respectively.
+pub fn format_release(
+    template: &str,
+    distro_release: &DistroRelease,
     date: NaiveDate,
-    distro_info: &'a impl DistroInfo,
-) -> Result<Vec<&'a DistroRelease>, Error> {
-    Ok(if matches.is_present("all") {
-        distro_info.iter().collect()
-    } else if matches.is_present("supported") {
-        distro_info.supported(date)
-    } else if matches.is_present("unsupported") {
-        distro_info.unsupported(date)
-    } else if matches.is_present("devel") {
-        match distro_info.distro() {
-            Distro::Ubuntu => distro_info.ubuntu_devel(date),
-            Distro::Debian => distro_info.debian_devel(date),
-        }
-    } else if matches.is_present("testing") {
-        // d-d-i --testing selection matches u-d-i --devel
-        distro_info.ubuntu_devel(date)
-    } else if matches.is_present("latest") {
-        let devel_result = distro_info.ubuntu_devel(date);
-        if devel_result.len() > 0 {
-            vec![*devel_result.last().unwrap()]
-        } else {
-            distro_info
-                .latest(date)
-                .map(|distro_release| vec![distro_release])
-                .unwrap_or_else(|| vec![])
-        }
-    } else if matches.is_present("lts") {
-        let mut lts_releases = vec![];
-        for distro_release in distro_info.all_at(date) {
-            if distro_release.is_lts() {
-                lts_releases.push(distro_release);
+) -> Result<String, Error> {
+    let date_value = |value: &Option<NaiveDate>| value.map(|d| d.to_string()).unwrap_or_default();
+    let mut formatted = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.next_if_eq(&'{').is_some() => formatted.push('{'),
+            '}' if chars.next_if_eq(&'}').is_some() => formatted.push('}'),
+            '}' => bail!("unmatched }} in --format template; use }}}} for a literal }}"),
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => bail!(
+                            "unterminated placeholder {{{} in --format template",
+                            placeholder
+                        ),
+                    }
+                }
+                formatted.push_str(&match placeholder.as_str() {
+                    "series" => distro_release.series().to_string(),
+                    "codename" => distro_release.codename().to_string(),
+                    "version" => distro_release.version().clone().unwrap_or_default(),
+                    "created" => date_value(distro_release.created()),
+                    "release" => date_value(distro_release.release()),
+                    "eol" => date_value(distro_release.eol()),
+                    "eol-server" => date_value(distro_release.eol_server()),
+                    "eol-lts" => date_value(distro_release.eol_lts()),
+                    "eol-esm" => date_value(distro_release.eol_esm()),
+                    "eol-elts" => date_value(distro_release.eol_elts()),
+                    "eol-legacy" => date_value(distro_release.eol_legacy()),
+                    "days-to-eol" => match distro_release.time_to_eol(date) {
+                        Some(time_to_eol) => time_to_eol.num_days().to_string(),
+                        None => "(unknown)".to_string(),
+                    },
+                    _ => bail!(
+                        "unknown placeholder {{{}}} in --format template",
+                        placeholder
+                    ),
+                });
             }
+            '\\' => match chars.next() {
+                Some('t') => formatted.push('\t'),
+                Some('n') => formatted.push('\n'),
+                Some(other) => formatted.push(other),
+                None => formatted.push('\\'),
+            },
+            c => formatted.push(c),
         }
-        match lts_releases.last() {
-            Some(release) => vec![*release],
-            None => bail!(OUTDATED_MSG),
-        }
-    } else if matches.is_present("stable") {
-        distro_info
-            .latest(date)
-            .map(|distro_release| vec![distro_release])
-            .unwrap_or_else(|| vec![])
-    } else if matches.is_present("series") {
-        match matches.value_of("series") {
-            Some(needle_series) => {
-                if !needle_series.chars().all(|c| c.is_lowercase()) {
-                    bail!("invalid distribution series `{}'", needle_series);
-                };
-                let candidates: Vec<&DistroRelease> = distro_info
-                    .iter()
-                    .filter(|distro_release| distro_release.series() == needle_series)
-                    .collect();
-                if candidates.is_empty() {
-                    bail!("unknown distribution series `{}'", needle_series);
-                };
-                Ok(candidates)
-            }
-            None => Err(format_err!(
-                "--series requires an argument; please report a bug about this \
-                 error"
-            )),
-        }?
-    } else {
-        panic!("clap prevent us from reaching here; report a bug if you see this")
-    })
+    }
+    Ok(formatted)
+}
+
+/// The file in the `--datadir` in `matches` (if one was given) to read the data usually at
+/// `csv_path` from
+fn datadir_path(matches: &ArgMatches, csv_path: &str) -> Option<PathBuf> {
+    let csv_path = Path::new(csv_path);
+    let file_name = csv_path.file_name().unwrap_or(csv_path.as_os_str());
+    let dir = Path::new(matches.value_of_os("datadir")?);
+    Some(compression::find(&dir.join(file_name)))
+}
+
+/// The report the `data-version` command prints
+fn data_version(data_source: &DataSource) -> String {
+    let modified = match data_source.modified {
+        Some(modified) => DateTime::<Utc>::from(modified).to_rfc3339(),
+        None => "(missing)".to_string(),
+    };
+    format!(
+        "path: {}\nmodified: {}\npackage-version: {}\n",
+        data_source.path.display(),
+        modified,
+        data_source
+            .package_version
+            .as_deref()
+            .unwrap_or("(unknown)")
+    )
+}
+
+/// Print the differences between the data files at `old` and `new`, returning 1 if there are any
+/// (as diff(1) does)
+fn diff<T: DistroDataSource>(old: &str, new: &str) -> Result<i32, Error> {
+    let read =
+        |path: &str| T::from_path(path).map_err(|e| format_err!("failed to read {}: {}", path, e));
+    let diff = DistroInfoDiff::between(&read(old)?, &read(new)?);
+    write!(io::stdout().lock(), "{}", diff)?;
+    Ok(if diff.is_empty() { 0 } else { EXIT_ERROR })
+}
+
+/// The milestone named by `--days`, which must be one of those `policy` supports
+fn days_mode(policy: &dyn DistroPolicy, name: &str) -> Result<DaysMode, Error> {
+    let supported = policy.supported_milestones();
+    match Milestone::from_name(name) {
+        Some(milestone) if supported.contains(&milestone) => Ok(milestone),
+        _ => bail!(
+            "unknown --days milestone `{}' for {}; valid milestones are: {}",
+            name,
+            policy.display_name(),
+            supported
+                .iter()
+                .map(|milestone| milestone.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// `--calendar`: an iCalendar feed of the releases' release and end-of-life dates
+fn calendar(
+    _: &ArgMatches,
+    distro_releases: Vec<&DistroRelease>,
+    context: &OutputContext,
+) -> String {
+    to_ics(context.policy.display_name(), distro_releases, context.date)
+}
+
+/// `--gantt`: the releases' support windows as a Gantt chart, in the format given
+fn gantt_chart(
+    matches: &ArgMatches,
+    distro_releases: Vec<&DistroRelease>,
+    context: &OutputContext,
+) -> String {
+    let format = matches
+        .value_of("gantt")
+        .and_then(GanttFormat::from_name)
+        .unwrap_or(GanttFormat::Json);
+    let title = format!("{} releases", context.policy.display_name());
+    format.render(&title, &gantt(distro_releases))
+}
+
+/// The version `--fallback=predict` prints, if `distro_info` is for a distro with a predictable
+/// cadence
+fn predict_devel(distro_info: &dyn DistroInfo, date: NaiveDate) -> Option<PredictedVersion> {
+    match distro_info.distro() {
+        Distro::Ubuntu => next_expected_ubuntu_version(distro_info, date),
+        _ => None,
+    }
+}
+
+/// The lines `--upcoming` prints: the date, series and name of each milestone in the next `horizon`
+/// days
+fn upcoming(distro_info: &dyn DistroInfo, date: NaiveDate, horizon: u32) -> Vec<String> {
+    distro_info
+        .upcoming_milestones(date, horizon)
+        .into_iter()
+        .map(|(milestone, distro_release, milestone_date)| {
+            format!(
+                "{} {} {}",
+                milestone_date,
+                distro_release.series(),
+                milestone.name()
+            )
+        })
+        .collect()
 }
 
 fn today() -> NaiveDate {
-    let now = Utc::now();
-    NaiveDate::from_ymd(now.year(), now.month(), now.day())
+    Utc::now().date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::path::PathBuf;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use chrono::NaiveDate;
+    use distro_info::milestones::Milestone;
+    use distro_info::policy::DistroPolicy;
+    use distro_info::source::DataSource;
+    use distro_info::{
+        test_support, DebianDistroInfo, Distro, DistroInfo, DistroRelease, UbuntuDistroInfo,
+    };
+
+    use clap::Arg;
+    use failure::format_err;
+
+    use super::selectors::SelectorArgs;
+    use super::{
+        data_version, days_mode, exit_status, format_release, is_broken_pipe, mode_exit_status,
+        outputs, predict_devel, upcoming, DistroInfoCommand, InternalError, NoReleases, Output,
+        OutputContext, Selector, Verbosity, EXIT_ERROR, EXIT_INTERNAL_ERROR, EXIT_UNKNOWN_SERIES,
+    };
+
+    fn select(args: &[&str]) -> Vec<String> {
+        let command = DistroInfoCommand::new("test-distro-info");
+        let matches = command
+            .app()
+            .get_matches_from_safe(["test-distro-info"].iter().chain(args.iter()))
+            .unwrap();
+        let date = test_support::date(matches.value_of("date").unwrap());
+        let ubuntu_distro_info = test_support::ubuntu();
+        command
+            .select(&matches, date, &ubuntu_distro_info)
+            .unwrap()
+            .iter()
+            .map(|distro_release| distro_release.series().clone())
+            .collect()
+    }
+
+    #[test]
+    fn reverse_and_limit() {
+        assert_eq!(
+            vec!["breezy", "hoary"],
+            select(&["--all", "--date", "2005-04-08", "--reverse", "--limit", "2"])
+        );
+        let command = DistroInfoCommand::new("test-distro-info");
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--supported", "--limit", "0"])
+            .unwrap();
+        let err = command
+            .select(
+                &matches,
+                test_support::date("2024-01-01"),
+                &test_support::ubuntu(),
+            )
+            .unwrap_err();
+        assert!(err.downcast_ref::<NoReleases>().is_none());
+        assert_eq!(
+            "Failed to parse '0' as a number of versions (at least 1)",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn supported_until() {
+        assert_eq!(
+            vec!["jammy", "noble", "oracular"],
+            select(&["--supported-until", "2025-06-01", "--date", "2024-07-01"])
+        );
+        assert_eq!(
+            vec!["jammy", "noble"],
+            select(&["--supported-until", "+1y", "--date", "2025-02-01"])
+        );
+    }
+
+    #[test]
+    fn all_honors_date() {
+        assert_eq!(
+            vec!["warty", "hoary", "breezy"],
+            select(&["--all", "--date", "2005-04-08"])
+        );
+        let command = DistroInfoCommand::new("test-distro-info");
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--all"])
+            .unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
+        let all = command
+            .select(
+                &matches,
+                test_support::date("2005-04-08"),
+                &ubuntu_distro_info,
+            )
+            .unwrap();
+        assert_eq!(ubuntu_distro_info.releases().len(), all.len());
+    }
+
+    #[test]
+    fn upcoming_milestones() {
+        assert_eq!(
+            vec![
+                "2024-04-25 trusty eol-esm",
+                "2024-04-25 noble release",
+                "2024-04-25 oracular created",
+            ],
+            upcoming(
+                &test_support::ubuntu(),
+                test_support::date("2024-04-01"),
+                60
+            )
+        );
+    }
+
+    #[test]
+    fn common_flags_accepted_by_all_tools() {
+        for command in [DistroInfoCommand::ubuntu(), DistroInfoCommand::debian()] {
+            for args in [
+                &["--all", "--fullname"][..],
+                &["--devel", "--fallback", "predict", "--release"],
+                &["--stable", "--days", "eol"],
+                &["--supported", "--date", "2024-01-01"],
+                &["--unsupported", "--release"],
+                &["--series", "focal", "--is-supported"],
+                &["--upcoming", "30"],
+                &["data-version"],
+                &["--datadir", "../testdata", "diff", "a.csv", "b.csv"],
+                &["update-data"],
+                &["generate-man"],
+                &["--stable", "--quiet"],
+                &["--all", "--csv", "-"],
+                &["--all", "--datadir", "../testdata"],
+                &["--stable", "-v"],
+            ] {
+                let result = command
+                    .app()
+                    .get_matches_from_safe(["distro-info"].iter().chain(args.iter()));
+                assert!(result.is_ok(), "{:?}: {:?}", args, result);
+            }
+        }
+    }
+
+    #[test]
+    fn previous_lts_selector() {
+        let command = DistroInfoCommand::ubuntu();
+        let ubuntu_distro_info = test_support::ubuntu();
+        let select = |args: &[&str]| {
+            let matches = command
+                .app()
+                .get_matches_from(["ubuntu-distro-info"].iter().chain(args.iter()));
+            command.select(
+                &matches,
+                test_support::date("2023-01-01"),
+                &ubuntu_distro_info,
+            )
+        };
+        assert_eq!("focal", select(&["--previous-lts"]).unwrap()[0].series());
+        assert_eq!("jammy", select(&["--lts"]).unwrap()[0].series());
+        assert!(DistroInfoCommand::debian()
+            .app()
+            .get_matches_from_safe(["debian-distro-info", "--previous-lts"].iter())
+            .is_err());
+    }
+
+    #[test]
+    fn load_from_csv_option() {
+        let command = DistroInfoCommand::debian();
+        let matches = command.app().get_matches_from(
+            [
+                "debian-distro-info",
+                "--all",
+                "--csv",
+                "../testdata/debian.csv",
+            ]
+            .iter(),
+        );
+        let debian_distro_info = command.load::<DebianDistroInfo>(&matches).unwrap();
+        assert_eq!(
+            test_support::debian().releases().len(),
+            debian_distro_info.releases().len()
+        );
+    }
+
+    #[test]
+    fn load_from_datadir_option() {
+        let command = DistroInfoCommand::ubuntu();
+        let matches = command
+            .app()
+            .get_matches_from(["ubuntu-distro-info", "--all", "--datadir", "../testdata"].iter());
+        let ubuntu_distro_info = command.load::<UbuntuDistroInfo>(&matches).unwrap();
+        assert_eq!(
+            test_support::ubuntu().releases().len(),
+            ubuntu_distro_info.releases().len()
+        );
+        let matches = command
+            .app()
+            .get_matches_from(["ubuntu-distro-info", "--all", "--datadir", "/nonexistent"].iter());
+        assert!(command.load::<UbuntuDistroInfo>(&matches).is_err());
+        assert!(command
+            .app()
+            .get_matches_from_safe(
+                [
+                    "ubuntu-distro-info",
+                    "--all",
+                    "--datadir",
+                    "x",
+                    "--csv",
+                    "y"
+                ]
+                .iter()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn verbosity() {
+        let command = DistroInfoCommand::ubuntu();
+        let verbosity = |args: &[&str]| {
+            Verbosity::from_matches(
+                &command
+                    .app()
+                    .get_matches_from(["ubuntu-distro-info"].iter().chain(args.iter())),
+            )
+        };
+        assert_eq!(Verbosity::Normal, verbosity(&["--stable"]));
+        assert_eq!(Verbosity::Quiet, verbosity(&["--stable", "-q"]));
+        assert_eq!(Verbosity::Verbose, verbosity(&["--stable", "--verbose"]));
+        assert!(command
+            .app()
+            .get_matches_from_safe(["ubuntu-distro-info", "--stable", "-q", "-v"].iter())
+            .is_err());
+    }
+
+    #[test]
+    fn days_milestones_per_distro() {
+        let ubuntu = Distro::Ubuntu.policy();
+        let debian = Distro::Debian.policy();
+        assert_eq!(Milestone::EolEsm, days_mode(ubuntu, "eol-esm").unwrap());
+        assert_eq!(Milestone::EolElts, days_mode(debian, "eol-elts").unwrap());
+        assert_eq!(
+            "unknown --days milestone `eol-esm' for Debian; valid milestones are: created, \
+             release, eol, eol-lts, eol-elts",
+            days_mode(debian, "eol-esm").unwrap_err().to_string()
+        );
+        assert!(days_mode(ubuntu, "bogus").is_err());
+    }
+
+    #[test]
+    fn predicted_devel() {
+        let date = test_support::date("2026-06-01");
+        assert_eq!(
+            "26.10",
+            predict_devel(&test_support::ubuntu(), date)
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(None, predict_devel(&test_support::debian(), date));
+    }
+
+    #[test]
+    fn predicted_devel_output_modes() {
+        let run = |args: &[&str]| {
+            let command = DistroInfoCommand::new("test-distro-info");
+            let matches = command
+                .app()
+                .get_matches_from_safe(
+                    ["test-distro-info", "--devel", "--fallback", "predict", "-q"]
+                        .iter()
+                        .chain(args.iter()),
+                )
+                .unwrap();
+            command.run(&matches, &test_support::ubuntu())
+        };
+        let date = ["--date", "2026-06-01"];
+        assert_eq!(0, run(&[&date[..], &["--release"]].concat()).unwrap());
+        assert_eq!(
+            0,
+            run(&[&date[..], &["--format", "json"]].concat()).unwrap()
+        );
+        for output in ["--codename", "--fullname"] {
+            assert_eq!(
+                "--fallback=predict needs --release or --format json",
+                run(&[&date[..], &[output]].concat())
+                    .unwrap_err()
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn data_version_report() {
+        let data_source = DataSource {
+            path: PathBuf::from("/usr/share/distro-info/ubuntu.csv"),
+            modified: Some(UNIX_EPOCH + Duration::from_secs(86400)),
+            package_version: None,
+        };
+        assert_eq!(
+            "path: /usr/share/distro-info/ubuntu.csv\n\
+             modified: 1970-01-02T00:00:00+00:00\n\
+             package-version: (unknown)\n",
+            data_version(&data_source)
+        );
+    }
+
+    #[test]
+    fn format_template() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info.lookup("jammy").unwrap();
+        let date = test_support::date("2027-01-01");
+        assert_eq!(
+            "jammy\t2027-06-01\t151",
+            format_release("{series}\\t{eol}\\t{days-to-eol}", jammy, date).unwrap()
+        );
+        assert_eq!(
+            "22.04 LTS (Jammy Jellyfish) {}",
+            format_release("{version} ({codename}) {{}}", jammy, date).unwrap()
+        );
+        assert_eq!(
+            "2032-04-21 2034-04-25",
+            format_release("{eol-esm} {eol-legacy}", jammy, date).unwrap()
+        );
+        assert!(format_release("{eol_server}", jammy, date).is_err());
+        assert_eq!(
+            "unterminated placeholder {series in --format template",
+            format_release("{series", jammy, date)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(format_release("{series}}", jammy, date).is_err());
+    }
+
+    #[test]
+    fn predicates() {
+        let run = |args: &[&str]| {
+            let command = DistroInfoCommand::new("test-distro-info");
+            let matches = command
+                .app()
+                .get_matches_from_safe(["test-distro-info"].iter().chain(args.iter()))
+                .unwrap();
+            command.run(&matches, &test_support::ubuntu()).unwrap()
+        };
+        let date = ["--date", "2024-01-01"];
+        let with_date = |args: &[&'static str]| [args, &date[..]].concat();
+        assert_eq!(0, run(&with_date(&["--series", "focal", "--is-supported"])));
+        assert_eq!(
+            EXIT_ERROR,
+            run(&with_date(&["--series", "bionic", "--is-supported"]))
+        );
+        assert_eq!(0, run(&with_date(&["--series", "focal,jammy", "--is-lts"])));
+        assert_eq!(
+            EXIT_ERROR,
+            run(&with_date(&["--series", "focal,mantic", "--is-lts"]))
+        );
+        assert_eq!(0, run(&with_date(&["--series", "noble", "--is-devel"])));
+        assert_eq!(
+            EXIT_ERROR,
+            run(&with_date(&["--series", "mantic", "--is-devel"]))
+        );
+        assert!(DistroInfoCommand::new("test-distro-info")
+            .app()
+            .get_matches_from_safe(["test-distro-info", "--stable", "--is-lts"].iter())
+            .is_err());
+    }
+
+    #[test]
+    fn gantt() {
+        let run = |args: &[&str]| {
+            let command = DistroInfoCommand::new("test-distro-info");
+            let matches = command
+                .app()
+                .get_matches_from_safe(["test-distro-info"].iter().chain(args.iter()))
+                .unwrap();
+            command.run(&matches, &test_support::ubuntu())
+        };
+        assert_eq!(
+            0,
+            run(&["--series", "jammy", "--gantt", "mermaid"]).unwrap()
+        );
+        assert_eq!(0, run(&["--supported", "--calendar"]).unwrap());
+        assert!(run(&["--stable", "--gantt", "json", "--date", "1990-01-01"]).is_err());
+        assert_eq!(
+            "--codename-of cannot be used with --gantt",
+            run(&["--codename-of", "22.04", "--gantt", "json"])
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "--release-of cannot be used with --phase",
+            run(&["--release-of", "jammy", "--phase"])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn all_include_future() {
+        let all = select(&["--all", "--include-future", "--date", "2005-04-08"]);
+        assert_eq!(test_support::ubuntu().releases().len(), all.len());
+    }
+
+    #[test]
+    fn series_multiple_values() {
+        assert_eq!(
+            vec!["focal", "jammy", "noble"],
+            select(&[
+                "--series",
+                "focal,jammy",
+                "--series",
+                "noble",
+                "--date",
+                "2024-01-01"
+            ])
+        );
+    }
+
+    #[test]
+    fn series_ignores_case_unless_strict() {
+        assert_eq!(
+            vec!["focal"],
+            select(&["--series", "FOCAL", "--date", "2024-01-01"])
+        );
+        let command = DistroInfoCommand::new("test-distro-info");
+        let matches = command
+            .app()
+            .get_matches_from_safe(["test-distro-info", "--series", "Focal", "--strict"])
+            .unwrap();
+        let date = test_support::date("2024-01-01");
+        assert!(command
+            .select(&matches, date, &test_support::ubuntu())
+            .is_err());
+    }
+
+    #[test]
+    fn codename_of_and_release_of() {
+        assert_eq!(
+            vec!["jammy", "kinetic"],
+            select(&[
+                "--codename-of",
+                "22.04",
+                "--codename-of",
+                "Kinetic Kudu",
+                "--date",
+                "2024-01-01"
+            ])
+        );
+        assert_eq!(
+            vec!["focal"],
+            select(&["--release-of", "focal", "--date", "2024-01-01"])
+        );
+        let command = DistroInfoCommand::new("test-distro-info");
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec![
+                "test-distro-info",
+                "--release-of",
+                "focal",
+                "--fullname",
+            ])
+            .unwrap();
+        assert!(command.run(&matches, &test_support::ubuntu()).is_err());
+    }
+
+    #[test]
+    fn registered_selector_replaces_builtin() {
+        let command = DistroInfoCommand::new("test-distro-info").selector(
+            "stable",
+            "the first ever version",
+            |distro_info, _date| Ok(distro_info.iter().take(1).collect()),
+        );
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--stable"])
+            .unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
+        let selected = command
+            .select(
+                &matches,
+                test_support::date("2020-01-01"),
+                &ubuntu_distro_info,
+            )
+            .unwrap();
+        assert_eq!("warty", selected[0].series());
+    }
+
+    #[test]
+    fn registered_selector_takes_values() {
+        struct Nth;
+        impl Selector for Nth {
+            fn select<'a>(
+                &self,
+                distro_info: &'a dyn DistroInfo,
+                _date: NaiveDate,
+                args: &SelectorArgs,
+            ) -> Result<Vec<&'a DistroRelease>, failure::Error> {
+                let n: usize = args.values[0].parse()?;
+                Ok(distro_info.iter().skip(n).take(1).collect())
+            }
+
+            fn arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+                arg.takes_value(true)
+            }
+        }
+        let command =
+            DistroInfoCommand::new("test-distro-info").register_selector("nth", None, "", Nth);
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--nth", "1"])
+            .unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
+        let selected = command
+            .select(
+                &matches,
+                test_support::date("2020-01-01"),
+                &ubuntu_distro_info,
+            )
+            .unwrap();
+        assert_eq!("hoary", selected[0].series());
+    }
+
+    #[test]
+    fn registered_output_modes() {
+        struct AssetId;
+        impl Output for AssetId {
+            fn fields(
+                &self,
+                distro_release: &DistroRelease,
+                context: &OutputContext,
+            ) -> Result<Vec<String>, failure::Error> {
+                Ok(vec![format!(
+                    "{}/{}",
+                    context.distro.to_string().to_lowercase(),
+                    distro_release.series()
+                )])
+            }
+        }
+        let command = DistroInfoCommand::new("test-distro-info").register_output_mode(
+            "asset-id",
+            Some("i"),
+            "print the asset ID",
+            AssetId,
+        );
+        for args in [
+            &["--stable", "--asset-id"][..],
+            &["--stable", "-i"],
+            &["--stable", "-c"],
+            &["--stable", "-f"],
+        ] {
+            let result = command
+                .app()
+                .get_matches_from_safe(["test-distro-info"].iter().chain(args.iter()));
+            assert!(result.is_ok(), "{:?}: {:?}", args, result);
+        }
+        assert!(command
+            .app()
+            .get_matches_from_safe(["test-distro-info", "--stable", "-i", "-f"].iter())
+            .is_err());
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info.lookup("jammy").unwrap();
+        let context = OutputContext {
+            date: test_support::date("2024-01-01"),
+            distro: &Distro::Ubuntu,
+            distro_info: &ubuntu_distro_info,
+            policy: Distro::Ubuntu.policy(),
+        };
+        assert_eq!(
+            vec!["ubuntu/jammy"],
+            AssetId.fields(jammy, &context).unwrap()
+        );
+        assert_eq!(
+            vec!["Ubuntu 22.04 LTS \"Jammy Jellyfish\""],
+            outputs::FullName.fields(jammy, &context).unwrap()
+        );
+        assert_eq!(
+            vec!["jammy", "full"],
+            outputs::Phase.fields(jammy, &context).unwrap()
+        );
+        let mantic = ubuntu_distro_info.lookup("mantic").unwrap();
+        assert_eq!(
+            vec!["mantic", "(supported)"],
+            outputs::EolAge.fields(mantic, &context).unwrap()
+        );
+        assert_eq!(
+            vec!["impish", "535"],
+            outputs::EolAge
+                .fields(ubuntu_distro_info.lookup("impish").unwrap(), &context)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn empty_selection_is_an_error() {
+        let command = DistroInfoCommand::new("test-distro-info");
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--stable", "--date", "1990-01-01"])
+            .unwrap();
+        let err = command.run(&matches, &test_support::ubuntu()).unwrap_err();
+        assert_eq!(EXIT_ERROR, exit_status(&err));
+    }
+
+    #[test]
+    fn exit_statuses() {
+        assert_eq!(EXIT_ERROR, exit_status(&format_err!("no such series")));
+        assert_eq!(
+            EXIT_INTERNAL_ERROR,
+            exit_status(&InternalError("oops".to_string()).into())
+        );
+        assert!(is_broken_pipe(
+            &io::Error::from(io::ErrorKind::BrokenPipe).into()
+        ));
+        assert!(!is_broken_pipe(&format_err!("no such series")));
+        let command = DistroInfoCommand::new("test-distro-info");
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--check", "jamy"])
+            .unwrap();
+        let err = command.run(&matches, &test_support::ubuntu()).unwrap_err();
+        assert_eq!(
+            "unknown distribution series `jamy'; did you mean `jammy'?",
+            err.to_string()
+        );
+        assert_eq!(EXIT_UNKNOWN_SERIES, mode_exit_status(&matches, &err));
+        assert_eq!(2, EXIT_UNKNOWN_SERIES);
+    }
+
+    #[test]
+    fn policy_overrides_devel() {
+        struct FirstIsDevel;
+        impl DistroPolicy for FirstIsDevel {
+            fn display_name(&self) -> &str {
+                "First"
+            }
+            fn devel<'a>(
+                &self,
+                distro_info: &'a dyn DistroInfo,
+                _date: NaiveDate,
+            ) -> Vec<&'a DistroRelease> {
+                distro_info.iter().take(1).collect()
+            }
+        }
+        let command = DistroInfoCommand::new("test-distro-info").policy(FirstIsDevel);
+        let matches = command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--devel"])
+            .unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
+        let selected = command
+            .select(
+                &matches,
+                test_support::date("2020-01-01"),
+                &ubuntu_distro_info,
+            )
+            .unwrap();
+        assert_eq!("warty", selected[0].series());
+    }
+
+    #[test]
+    fn commands_are_not_selectors() {
+        let command = DistroInfoCommand::ubuntu();
+        let err = command
+            .app()
+            .get_matches_from_safe(vec!["ubuntu-distro-info", "--oldstable"])
+            .unwrap_err();
+        assert!(!err.message.contains("--update-data"), "{}", err.message);
+        assert!(command
+            .app()
+            .get_matches_from_safe(vec!["ubuntu-distro-info", "diff", "a.csv"])
+            .is_err());
+    }
+
+    #[test]
+    fn include_future_requires_all() {
+        let command = DistroInfoCommand::new("test-distro-info");
+        assert!(command
+            .app()
+            .get_matches_from_safe(vec!["test-distro-info", "--supported", "--include-future"])
+            .is_err());
+    }
 }
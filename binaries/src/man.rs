@@ -0,0 +1,177 @@
+//! Man pages for the tools, generated from their `--help`, so that packaging can build them from
+//! the binaries themselves (with `generate-man`) and they always document every option,
+//! including those registered by derivative distros.
+use std::fmt::Write;
+
+/// Escape `text` for use as roff text
+fn escape(text: &str) -> String {
+    let text = text.replace('\\', "\\e");
+    if text.starts_with('.') || text.starts_with('\'') {
+        format!("\\&{}", text)
+    } else {
+        text
+    }
+}
+
+/// Format an option's `--help` synopsis (e.g. "-y, --days <milestone>") in roff, with its flags
+/// in bold and its values in italics
+fn synopsis(spec: &str) -> String {
+    spec.split(' ')
+        .map(|word| {
+            let word = escape(word).replace('-', "\\-");
+            if word.starts_with("\\-") {
+                format!("\\fB{}\\fR", word)
+            } else {
+                format!("\\fI{}\\fR", word)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// An option listed by `--help`
+struct Entry<'a> {
+    /// The option's long name, without the leading "--"
+    name: &'a str,
+    spec: &'a str,
+    help: &'a str,
+}
+
+/// The options listed in clap's (unwrapped) `--help` output
+fn entries(help: &str) -> Vec<Entry<'_>> {
+    help.lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('-'))
+        .filter_map(|line| {
+            let (spec, help) = line.split_once("  ")?;
+            let name = spec.split_once("--")?.1.split(' ').next()?;
+            Some(Entry {
+                name,
+                spec,
+                help: help.trim(),
+            })
+        })
+        .collect()
+}
+
+/// The commands listed in the SUBCOMMANDS section of clap's (unwrapped) `--help` output, with
+/// their help
+fn commands(help: &str) -> Vec<(&str, &str)> {
+    help.lines()
+        .skip_while(|line| *line != "SUBCOMMANDS:")
+        .skip(1)
+        .take_while(|line| line.starts_with(' '))
+        .filter_map(|line| {
+            let (name, help) = line.trim().split_once("  ")?;
+            Some((name, help.trim()))
+        })
+        .collect()
+}
+
+/// Render the man page for `command_name` from its clap `help` (which must not be wrapped),
+/// listing the options named in `selectors` and `outputs` in sections of their own
+pub fn render(command_name: &str, help: &str, selectors: &[&str], outputs: &[&str]) -> String {
+    let version = help
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix(command_name))
+        .map(str::trim)
+        .unwrap_or("");
+    let name = command_name.replace('-', "\\-");
+    let mut page = String::new();
+    let _ = writeln!(
+        page,
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"",
+        command_name.to_uppercase(),
+        name,
+        version
+    );
+    let _ = writeln!(
+        page,
+        ".SH NAME\n{} \\- provides information about a distribution's releases",
+        name
+    );
+    let _ = writeln!(
+        page,
+        ".SH SYNOPSIS\n.B {}\n[\\fIOPTIONS\\fR] \\fISELECTOR\\fR\n.br\n.B {}\n\
+         [\\fIOPTIONS\\fR] \\fICOMMAND\\fR",
+        name, name
+    );
+    let _ = writeln!(
+        page,
+        ".SH DESCRIPTION\n\\fB{}\\fR prints information about the releases of a distribution, from \
+         the data in distro-info-data.  Exactly one selector must be given, which chooses the \
+         releases to display; by default, their series are printed, which the output options \
+         change.",
+        name
+    );
+    let entries = entries(help);
+    let sections = [
+        ("SELECTORS", Some(selectors)),
+        ("OUTPUT OPTIONS", Some(outputs)),
+        ("OPTIONS", None),
+    ];
+    for (title, names) in sections {
+        let _ = writeln!(page, ".SH {}", title);
+        for entry in entries.iter().filter(|entry| match names {
+            Some(names) => names.contains(&entry.name),
+            None => !selectors.contains(&entry.name) && !outputs.contains(&entry.name),
+        }) {
+            let _ = writeln!(
+                page,
+                ".TP\n{}\n{}",
+                synopsis(entry.spec),
+                escape(entry.help)
+            );
+        }
+    }
+    let _ = writeln!(page, ".SH COMMANDS");
+    for (command, help) in commands(help) {
+        let _ = writeln!(
+            page,
+            ".TP\n\\fB{}\\fR\n{}",
+            command.replace('-', "\\-"),
+            escape(help)
+        );
+    }
+    let _ = writeln!(
+        page,
+        ".SH EXIT STATUS\n0 on success, 1 if the requested information could not be displayed \
+         (or a \\fB\\-\\-is\\-*\\fR test failed), and 2 on an internal error or if the series \
+         given to \\fB\\-\\-check\\fR is unknown."
+    );
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DistroInfoCommand;
+
+    #[test]
+    fn man_page() {
+        let page = DistroInfoCommand::debian()
+            .output_mode(
+                "upper",
+                "print the series in upper case",
+                |distro_release| distro_release.series().to_uppercase(),
+            )
+            .man_page();
+        assert!(page.starts_with(".TH DEBIAN-DISTRO-INFO 1 \"\" \"debian\\-distro\\-info 0.1.0\""));
+        let section = |title: &str| {
+            let start = page.find(&format!(".SH {}\n", title)).unwrap();
+            let rest = &page[start + 1..];
+            rest[..rest.find("\n.SH").unwrap()].to_string()
+        };
+        let selectors = section("SELECTORS");
+        assert!(selectors
+            .contains(".TP\n\\fB\\-t,\\fR \\fB\\-\\-testing\\fR\ncurrent testing version\n"));
+        assert!(selectors.contains("\\fB\\-\\-check\\fR \\fI<codename>\\fR"));
+        assert!(section("COMMANDS").contains(".TP\n\\fBdata\\-version\\fR\nreport the path"));
+        assert!(section("OUTPUT OPTIONS").contains("\\fB\\-\\-upper\\fR\nprint the series"));
+        let options = section("OPTIONS");
+        assert!(options.contains("\\fB\\-\\-date\\fR \\fI<date>\\fR"));
+        assert!(!options.contains("\\-\\-testing"));
+        // Help is not wrapped
+        assert!(page.contains("'{series}\\et{eol}'; placeholders are {series}"));
+    }
+}
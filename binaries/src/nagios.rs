@@ -0,0 +1,167 @@
+//! `--check-eol`: a Nagios (and Icinga) check plugin for a series' end of life, so monitoring can
+//! warn ahead of it.
+//!
+//! The plugin prints one status line, with the days until the end of life as perfdata, and exits
+//! with the standard plugin status codes.
+use chrono::NaiveDate;
+use distro_info::matching::MatchMode;
+use distro_info::status::SupportStatus;
+use distro_info::{DistroInfo, SupportTier};
+
+use crate::l10n::{fill, gettext};
+
+pub const OK: i32 = 0;
+pub const WARNING: i32 = 1;
+pub const CRITICAL: i32 = 2;
+pub const UNKNOWN: i32 = 3;
+
+/// The numbers of days before the end of life at which the check warns and becomes critical
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thresholds {
+    pub warn_days: i64,
+    pub crit_days: i64,
+}
+
+/// The name of a plugin status code
+fn status_name(status: i32) -> &'static str {
+    match status {
+        OK => "OK",
+        WARNING => "WARNING",
+        CRITICAL => "CRITICAL",
+        _ => "UNKNOWN",
+    }
+}
+
+/// The line reporting `status` with `message` (and `perfdata`, if any)
+pub fn status_line(status: i32, message: &str, perfdata: Option<&str>) -> String {
+    match perfdata {
+        Some(perfdata) => format!("EOL {} - {} | {}", status_name(status), message, perfdata),
+        None => format!("EOL {} - {}", status_name(status), message),
+    }
+}
+
+/// Check the end of life of the release `codename` at `date`, returning the plugin's status code
+/// and output line
+///
+/// The status is `UNKNOWN` if the series or its end of life isn't known.
+pub fn check_eol(
+    distro_info: &dyn DistroInfo,
+    codename: &str,
+    mode: MatchMode,
+    date: NaiveDate,
+    thresholds: Thresholds,
+) -> (i32, String) {
+    let status = match SupportStatus::for_codename_with(distro_info, codename, mode, date) {
+        Some(status) => status,
+        None => {
+            let message = fill(gettext("unknown distribution series `{}'"), &[&codename]);
+            return (UNKNOWN, status_line(UNKNOWN, &message, None));
+        }
+    };
+    let series = status.release().series();
+    let (days, eol) = match (
+        status.days_to_eol(),
+        status.release().support_end(SupportTier::Standard),
+    ) {
+        (Some(days), Some(eol)) => (days, eol),
+        _ => {
+            let message = format!("no end of life date is known for {}", series);
+            return (UNKNOWN, status_line(UNKNOWN, &message, None));
+        }
+    };
+    let code = if days <= thresholds.crit_days {
+        CRITICAL
+    } else if days <= thresholds.warn_days {
+        WARNING
+    } else {
+        OK
+    };
+    let message = if days < 0 {
+        format!(
+            "{} reached end of life {} days ago ({})",
+            series, -days, eol
+        )
+    } else {
+        format!("{} reaches end of life in {} days ({})", series, days, eol)
+    };
+    // The thresholds are ranges ("N:"), as the plugin alerts when the days fall below them
+    let perfdata = format!(
+        "days_to_eol={};{}:;{}:",
+        days, thresholds.warn_days, thresholds.crit_days
+    );
+    (code, status_line(code, &message, Some(&perfdata)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_eol, MatchMode, Thresholds, CRITICAL, OK, UNKNOWN, WARNING};
+    use distro_info::test_support;
+
+    #[test]
+    fn check_eol_thresholds() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let thresholds = Thresholds {
+            warn_days: 90,
+            crit_days: 30,
+        };
+        let check = |date| {
+            check_eol(
+                &ubuntu_distro_info,
+                "jammy",
+                MatchMode::Relaxed,
+                test_support::date(date),
+                thresholds,
+            )
+        };
+        assert_eq!(
+            (
+                OK,
+                "EOL OK - jammy reaches end of life in 151 days (2027-06-01) | days_to_eol=151;90:;30:"
+                    .to_string()
+            ),
+            check("2027-01-01")
+        );
+        assert_eq!(WARNING, check("2027-04-01").0);
+        assert_eq!(CRITICAL, check("2027-05-10").0);
+        assert_eq!(
+            "EOL CRITICAL - jammy reached end of life 30 days ago (2027-06-01) | \
+             days_to_eol=-30;90:;30:",
+            check("2027-07-01").1
+        );
+        assert_eq!(
+            (
+                UNKNOWN,
+                "EOL UNKNOWN - unknown distribution series `gentoo'".to_string()
+            ),
+            check_eol(
+                &ubuntu_distro_info,
+                "gentoo",
+                MatchMode::Relaxed,
+                test_support::date("2027-01-01"),
+                thresholds
+            )
+        );
+        assert_eq!(
+            OK,
+            check_eol(
+                &ubuntu_distro_info,
+                "Jammy",
+                MatchMode::Relaxed,
+                test_support::date("2027-01-01"),
+                thresholds
+            )
+            .0
+        );
+        assert_eq!(
+            UNKNOWN,
+            check_eol(
+                &ubuntu_distro_info,
+                "Jammy",
+                MatchMode::Strict,
+                test_support::date("2027-01-01"),
+                thresholds
+            )
+            .0
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Output modes: the options which choose how a command displays each release it selects.
+//!
+//! Every output mode which prints a line per release, built-in or registered with
+//! [`DistroInfoCommand::output_mode`](../struct.DistroInfoCommand.html#method.output_mode),
+//! implements [`Output`](trait.Output.html); closures returning the text for a release implement it
+//! automatically.  `--format json` and the options which print a whole document (such as
+//! `--calendar`) are handled separately.
+use chrono::NaiveDate;
+use distro_info::policy::DistroPolicy;
+use distro_info::{Distro, DistroInfo, DistroRelease};
+use failure::Error;
+
+use crate::format_release;
+
+/// What an output mode may need to display a release, besides the release itself
+pub struct OutputContext<'a> {
+    /// The date the command is run for (`--date`, or today)
+    pub date: NaiveDate,
+    pub distro: &'a Distro,
+    /// The data the releases were selected from
+    pub distro_info: &'a dyn DistroInfo,
+    /// The policy of the distro, as renamed by `DistroInfoCommand::distro_name`
+    pub policy: &'a dyn DistroPolicy,
+}
+
+/// Gives the fields (joined by spaces, before any `--days` count) to display for a release
+pub trait Output {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        context: &OutputContext,
+    ) -> Result<Vec<String>, Error>;
+}
+
+impl<F> Output for F
+where
+    F: Fn(&DistroRelease) -> String,
+{
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        _context: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![self(distro_release)])
+    }
+}
+
+/// `--codename`: the series (the default)
+pub struct Codename;
+
+impl Output for Codename {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        _: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![distro_release.series().to_string()])
+    }
+}
+
+/// `--release`: the version, or the series of releases without one
+pub struct Release;
+
+impl Output for Release {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        _: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![distro_release
+            .version()
+            .as_ref()
+            .unwrap_or_else(|| distro_release.series())
+            .to_string()])
+    }
+}
+
+/// `--fullname`: the full name, as the distro's policy gives it
+pub struct FullName;
+
+impl Output for FullName {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        context: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![context.policy.full_name(distro_release)])
+    }
+}
+
+/// `--links`: links to the announcement, release notes and upgrade notes, where known
+pub struct Links;
+
+impl Output for Links {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        context: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(distro_release
+            .links(context.distro)
+            .known()
+            .map(|link| link.to_string())
+            .collect())
+    }
+}
+
+/// `--format`: a template; see [`format_release`](../fn.format_release.html)
+pub struct Format<'a>(pub &'a str);
+
+impl<'a> Output for Format<'a> {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        context: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![format_release(self.0, distro_release, context.date)?])
+    }
+}
+
+/// `--support-length`: the series, its standard support length in days and its `SupportClass`
+pub struct SupportLength;
+
+impl Output for SupportLength {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        _: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![
+            distro_release.series().to_string(),
+            match distro_release.support_duration() {
+                Some(duration) => duration.num_days().to_string(),
+                None => "(unknown)".to_string(),
+            },
+            match distro_release.support_class() {
+                Some(support_class) => support_class.name().to_string(),
+                None => "(unknown)".to_string(),
+            },
+        ])
+    }
+}
+
+/// `--eol-age`: the series and the number of days it had been unsupported at the date
+pub struct EolAge;
+
+impl Output for EolAge {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        context: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![
+            distro_release.series().to_string(),
+            match distro_release.eol_age(context.date) {
+                Some(age) => age.num_days().to_string(),
+                None => "(supported)".to_string(),
+            },
+        ])
+    }
+}
+
+/// `--phase`: the series and the `SupportPhase` it is in at the date
+pub struct Phase;
+
+impl Output for Phase {
+    fn fields(
+        &self,
+        distro_release: &DistroRelease,
+        context: &OutputContext,
+    ) -> Result<Vec<String>, Error> {
+        Ok(vec![
+            distro_release.series().to_string(),
+            match context
+                .distro_info
+                .support_timeline(distro_release)
+                .phase_at(context.date)
+            {
+                Some(phase) => phase.name().to_string(),
+                None => "(unreleased)".to_string(),
+            },
+        ])
+    }
+}
+
+/// Nothing, e.g. for `--days` to print only the count
+pub struct Suppress;
+
+impl Output for Suppress {
+    fn fields(&self, _: &DistroRelease, _: &OutputContext) -> Result<Vec<String>, Error> {
+        Ok(vec![])
+    }
+}
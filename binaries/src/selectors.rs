@@ -0,0 +1,386 @@
+//! Selectors: the options which choose which releases a command displays.
+//!
+//! Every selector, built-in or registered with
+//! [`DistroInfoCommand::selector`](../struct.DistroInfoCommand.html#method.selector), implements
+//! [`Selector`](trait.Selector.html); closures of the right shape implement it automatically.
+use std::fmt;
+
+use chrono::NaiveDate;
+use clap::{Arg, ArgMatches};
+use distro_info::matching::MatchMode;
+use distro_info::{DistroInfo, DistroRelease};
+use failure::{bail, format_err, Error};
+
+use crate::l10n::{fill, gettext};
+use crate::{dates, Output};
+
+/// The command-line arguments a selector was given
+#[derive(Default)]
+pub struct SelectorArgs<'m> {
+    /// The values given to the selector's own option, if it takes any
+    pub values: Vec<&'m str>,
+    /// All of the command's arguments, if the selector is used by a command
+    pub matches: Option<&'m ArgMatches<'m>>,
+}
+
+impl<'m> SelectorArgs<'m> {
+    /// Whether the option `name` was given to the command
+    pub fn is_present(&self, name: &str) -> bool {
+        self.matches.is_some_and(|matches| matches.is_present(name))
+    }
+
+    /// The value given to the option `name`, if any
+    pub fn value_of(&self, name: &str) -> Option<&'m str> {
+        self.matches.and_then(|matches| matches.value_of(name))
+    }
+
+    /// How `--series`, `--codename-of` and `--release-of` compare their values to the data
+    pub fn match_mode(&self) -> MatchMode {
+        self.matches.map_or(MatchMode::Relaxed, match_mode)
+    }
+}
+
+/// How the command's options compare their values to the data, as chosen by `--strict`
+pub fn match_mode(matches: &ArgMatches) -> MatchMode {
+    if matches.is_present("strict") {
+        MatchMode::Strict
+    } else {
+        MatchMode::Relaxed
+    }
+}
+
+/// Chooses the releases to display for a date
+pub trait Selector {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        args: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error>;
+
+    /// Configure the selector's option, e.g. to take values; by default it is a flag
+    fn arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg
+    }
+
+    /// The output mode the selector implies, if any, in which case no other may be given
+    fn output(&self) -> Option<&dyn Output> {
+        None
+    }
+}
+
+impl<F> Selector for F
+where
+    F: for<'a> Fn(&'a dyn DistroInfo, NaiveDate) -> Result<Vec<&'a DistroRelease>, Error>,
+{
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        _: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        self(distro_info, date)
+    }
+}
+
+/// `--all`: all versions, or only those known at the date if the command was given `--date` (or
+/// the selector is used outside a command), unless `include_future` is set or `--include-future`
+/// is given
+pub struct All {
+    pub include_future: bool,
+}
+
+impl Selector for All {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        args: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        let dated = args
+            .matches
+            .is_none_or(|matches| matches.is_present("date"));
+        Ok(
+            if self.include_future || args.is_present("include-future") || !dated {
+                distro_info.iter().collect()
+            } else {
+                distro_info.all_at(date)
+            },
+        )
+    }
+}
+
+/// `--devel`: the latest development version(s), as determined by the distro's `DistroPolicy`
+pub struct Devel;
+
+impl Selector for Devel {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        _: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        Ok(distro_info.distro().policy().devel(distro_info, date))
+    }
+}
+
+/// `--stable`: the latest stable version
+pub struct Stable;
+
+impl Selector for Stable {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        _: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        Ok(distro_info.stable(date).into_iter().collect())
+    }
+}
+
+/// `--supported`: all supported stable versions
+pub struct Supported;
+
+impl Selector for Supported {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        _: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        Ok(distro_info.supported(date))
+    }
+}
+
+/// `--unsupported`: all unsupported stable versions
+pub struct Unsupported;
+
+impl Selector for Unsupported {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        _: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        Ok(distro_info.unsupported(date))
+    }
+}
+
+/// `--future`: all versions in the data which have not been released, including those whose
+/// dates are not yet known
+pub struct Future;
+
+impl Selector for Future {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        _: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        Ok(distro_info.planned(date))
+    }
+}
+
+/// `--supported-until`: the versions which will still be supported at a later date, given as
+/// `--date` is but relative to the date
+pub struct SupportedUntil;
+
+impl Selector for SupportedUntil {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+        args: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        let until = args.values.first().copied().unwrap_or_default();
+        let until = dates::parse_date(until, date)
+            .ok_or_else(|| format_err!("Failed to parse date '{}'", until))?;
+        Ok(distro_info.supported_at_future(date, until))
+    }
+
+    fn arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg.takes_value(true)
+            .allow_hyphen_values(true)
+            .value_name("date")
+    }
+}
+
+/// A series given to `--series` (or a lookup) is not in the data
+#[derive(Debug)]
+pub struct UnknownSeries {
+    pub series: String,
+    /// A similar series which is in the data, if there is one
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownSeries {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let template = gettext("unknown distribution series `{}'");
+        write!(f, "{}", fill(template, &[&self.series]))?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(
+                f,
+                "{}",
+                fill(gettext("; did you mean `{}'?"), &[suggestion])
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownSeries {}
+
+/// `--series`: the versions with each of the given series (the option may be repeated)
+pub struct Series;
+
+impl Selector for Series {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        _date: NaiveDate,
+        args: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        let mut distro_releases = vec![];
+        for needle_series in &args.values {
+            distro_releases.extend(series(distro_info, needle_series, args.match_mode())?);
+        }
+        Ok(distro_releases)
+    }
+
+    fn arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        arg.takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .use_delimiter(true)
+    }
+}
+
+/// The versions with the series `needle_series`, compared according to `match_mode`
+pub fn series<'a>(
+    distro_info: &'a dyn DistroInfo,
+    needle_series: &str,
+    match_mode: MatchMode,
+) -> Result<Vec<&'a DistroRelease>, Error> {
+    if match_mode == MatchMode::Strict && !needle_series.chars().all(|c| c.is_lowercase()) {
+        bail!("invalid distribution series `{}'", needle_series);
+    };
+    let candidates: Vec<&DistroRelease> = distro_info
+        .iter()
+        .filter(|distro_release| match_mode.matches(distro_release.series(), needle_series))
+        .collect();
+    if candidates.is_empty() {
+        return Err(UnknownSeries {
+            series: needle_series.to_string(),
+            suggestion: distro_info
+                .suggest_series(needle_series)
+                .first()
+                .map(|suggestion| suggestion.to_string()),
+        }
+        .into());
+    };
+    Ok(candidates)
+}
+
+/// `--codename-of`/`--release-of`: the versions identified by each of the given series, full
+/// codenames or versions, displayed with the `Output` (`outputs::Codename` or `outputs::Release`)
+pub struct Lookup<O>(pub O);
+
+impl<O: Output> Selector for Lookup<O> {
+    fn select<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        _date: NaiveDate,
+        args: &SelectorArgs,
+    ) -> Result<Vec<&'a DistroRelease>, Error> {
+        let match_mode = args.match_mode();
+        let mut distro_releases = vec![];
+        for needle in &args.values {
+            match distro_info.lookup_with(needle, match_mode) {
+                Some(distro_release) => distro_releases.push(distro_release),
+                // Fall back to --series for its "did you mean" suggestions
+                None => distro_releases.extend(series(distro_info, needle, match_mode)?),
+            }
+        }
+        Ok(distro_releases)
+    }
+
+    fn arg<'a, 'b>(&self, arg: Arg<'a, 'b>) -> Arg<'a, 'b> {
+        Series.arg(arg).value_name("release")
+    }
+
+    fn output(&self) -> Option<&dyn Output> {
+        Some(&self.0)
+    }
+}
+
+/// `--reverse`/`--limit`: the order in which selected versions are displayed, and how many
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ordering {
+    /// Display the newest version first, rather than the oldest
+    pub reverse: bool,
+    /// Display at most this many versions (counted from the start of the reordered list); `--limit`
+    /// rejects 0, as displaying nothing would be reported as the data being outdated
+    pub limit: Option<usize>,
+}
+
+impl Ordering {
+    /// Reorder and truncate the (oldest-first) result of a selector
+    pub fn apply<'a>(&self, mut distro_releases: Vec<&'a DistroRelease>) -> Vec<&'a DistroRelease> {
+        if self.reverse {
+            distro_releases.reverse();
+        }
+        if let Some(limit) = self.limit {
+            distro_releases.truncate(limit);
+        }
+        distro_releases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distro_info::test_support;
+
+    use super::{Ordering, Selector, SelectorArgs, Series, Supported};
+
+    #[test]
+    fn series_suggests_alternative() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let date = test_support::date("2020-01-01");
+        let args = SelectorArgs {
+            values: vec!["focla"],
+            matches: None,
+        };
+        let err = Series.select(&ubuntu_distro_info, date, &args).unwrap_err();
+        assert_eq!(
+            "unknown distribution series `focla'; did you mean `focal'?",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let supported = Supported
+            .select(
+                &ubuntu_distro_info,
+                test_support::date("2024-06-01"),
+                &SelectorArgs::default(),
+            )
+            .unwrap();
+        let series = |ordering: Ordering| -> Vec<String> {
+            ordering
+                .apply(supported.clone())
+                .iter()
+                .map(|distro_release| distro_release.series().clone())
+                .collect()
+        };
+        assert_eq!(supported.len(), series(Ordering::default()).len());
+        assert_eq!(
+            vec!["oracular", "noble", "mantic"],
+            series(Ordering {
+                reverse: true,
+                limit: Some(3),
+            })
+        );
+    }
+}
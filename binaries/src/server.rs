@@ -0,0 +1,175 @@
+//! A small HTTP service answering queries about the release data, for infrastructure that would
+//! rather ask one service than install distro-info-data everywhere.
+//!
+//! Endpoints return JSON, and take an optional `date=YYYY-MM-DD` query parameter (default:
+//! today):
+//!
+//! * `/v1/{distro}/{all,devel,stable,supported,unsupported}`: a list of releases
+//! * `/v1/{distro}/series/{series}`: a single release
+//!
+//! where `{distro}` is `ubuntu` or `debian`.  The data is reloaded whenever its file changes.
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use distro_info::snapshot::{DistroSnapshot, SnapshotWatcher};
+use distro_info::{DebianDistroInfo, DistroDataSource, DistroInfo, UbuntuDistroInfo};
+use failure::{format_err, Error};
+use serde_json::{json, Value};
+
+pub use crate::json::release_json;
+use crate::selectors::SelectorArgs;
+use crate::{load_distro_info, selectors, today, Selector};
+
+/// A response to a request: the HTTP status code and JSON body
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message }),
+        }
+    }
+}
+
+/// Answer a request for `url` (a path with an optional query string), at `default_date` unless
+/// the query gives a date
+pub fn route(
+    url: &str,
+    ubuntu: &dyn DistroInfo,
+    debian: &dyn DistroInfo,
+    default_date: NaiveDate,
+) -> Response {
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (url, ""),
+    };
+    let date = match query
+        .split('&')
+        .find_map(|parameter| parameter.strip_prefix("date="))
+    {
+        Some(date) => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(date) => date,
+            Err(_) => return Response::error(400, "date must be in YYYY-MM-DD format"),
+        },
+        None => default_date,
+    };
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (distro_info, rest) = match segments.as_slice() {
+        ["v1", "ubuntu", rest @ ..] => (ubuntu, rest),
+        ["v1", "debian", rest @ ..] => (debian, rest),
+        _ => return Response::error(404, "unknown distro"),
+    };
+    let selected = match rest {
+        ["series", series] => {
+            return match distro_info.lookup(series) {
+                Some(distro_release) => Response::ok(release_json(distro_release)),
+                None => Response::error(404, "unknown series"),
+            }
+        }
+        ["all"] => selectors::All {
+            include_future: false,
+        }
+        .select(distro_info, date, &SelectorArgs::default()),
+        ["devel"] => selectors::Devel.select(distro_info, date, &SelectorArgs::default()),
+        ["stable"] => selectors::Stable.select(distro_info, date, &SelectorArgs::default()),
+        ["supported"] => selectors::Supported.select(distro_info, date, &SelectorArgs::default()),
+        ["unsupported"] => {
+            selectors::Unsupported.select(distro_info, date, &SelectorArgs::default())
+        }
+        _ => return Response::error(404, "unknown endpoint"),
+    };
+    match selected {
+        Ok(distro_releases) => Response::ok(Value::Array(
+            distro_releases.into_iter().map(release_json).collect(),
+        )),
+        Err(e) => Response::error(404, &e.to_string()),
+    }
+}
+
+/// The current snapshot of `watcher`'s data, reloaded first if its file has changed
+///
+/// If reloading fails, the previous data continues to be used.
+fn refreshed<T: DistroDataSource>(watcher: &SnapshotWatcher<T>) -> Arc<DistroSnapshot<T>> {
+    if let Err(e) = watcher.refresh() {
+        eprintln!("failed to reload release data: {}", e);
+    }
+    watcher.current()
+}
+
+/// Serve requests on `address` (e.g. "127.0.0.1:8080") until the process is killed
+pub fn serve(address: &str) -> Result<(), Error> {
+    let server = tiny_http::Server::http(address).map_err(|e| format_err!("{}", e))?;
+    let ubuntu = SnapshotWatcher::<UbuntuDistroInfo>::load_with(load_distro_info)?;
+    let debian = SnapshotWatcher::<DebianDistroInfo>::load_with(load_distro_info)?;
+    let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .map_err(|_| format_err!("invalid header"))?;
+    for request in server.incoming_requests() {
+        let (ubuntu, debian) = (refreshed(&ubuntu), refreshed(&debian));
+        let response = route(request.url(), &**ubuntu, &**debian, today());
+        let http_response = tiny_http::Response::from_string(response.body.to_string())
+            .with_status_code(response.status)
+            .with_header(content_type.clone());
+        if let Err(e) = request.respond(http_response) {
+            eprintln!("failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use distro_info::test_support;
+
+    use super::route;
+
+    fn get(url: &str) -> super::Response {
+        route(
+            url,
+            &test_support::ubuntu(),
+            &test_support::debian(),
+            test_support::date("2024-01-01"),
+        )
+    }
+
+    #[test]
+    fn supported() {
+        let response = get("/v1/ubuntu/supported?date=2024-06-01");
+        assert_eq!(200, response.status);
+        let series: Vec<_> = response
+            .body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|release| release["series"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            vec!["focal", "jammy", "mantic", "noble", "oracular"],
+            series[series.len() - 5..]
+        );
+    }
+
+    #[test]
+    fn series() {
+        let response = get("/v1/debian/series/bookworm");
+        assert_eq!(200, response.status);
+        assert_eq!("12", response.body["version"]);
+        assert_eq!("2023-06-10", response.body["release"]);
+        assert_eq!(404, get("/v1/debian/series/nonexistent").status);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(404, get("/v1/fedora/all").status);
+        assert_eq!(404, get("/v1/ubuntu/nonexistent").status);
+        assert_eq!(400, get("/v1/ubuntu/all?date=tomorrow").status);
+    }
+}
@@ -0,0 +1,184 @@
+//! The `update-data` command: installing the latest distro-info-data file in the user's data
+//! directory, where `DistroDataSource::new` prefers it to an older system copy (see
+//! `distro_info::discovery`).
+//!
+//! The file is downloaded with curl, and only replaces the user's copy if it parses and, with
+//! `--public-key`, if its minisign signature (downloaded alongside it) is valid.
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(feature = "minisign")]
+use clap::Arg;
+use clap::{App, SubCommand};
+use distro_info::discovery;
+#[cfg(feature = "minisign")]
+use distro_info::verify::signature_path;
+use distro_info::DistroDataSource;
+use failure::{bail, format_err, Error, ResultExt};
+
+use crate::l10n::gettext;
+
+/// Where the latest distro-info-data files are published
+pub const DATA_URL: &str = "https://salsa.debian.org/debian/distro-info-data/-/raw/main";
+
+/// The clap `SubCommand` for `update-data`
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    let subcommand = SubCommand::with_name("update-data").about(gettext(
+        "download the latest data into the user's data directory, where it is used in \
+         preference to older system data",
+    ));
+    #[cfg(feature = "minisign")]
+    let subcommand = subcommand.arg(
+        Arg::with_name("public-key")
+            .long("public-key")
+            .takes_value(true)
+            .value_name("key")
+            .help(gettext(
+                "only install the data if its minisign signature is valid for <key> (base64, or \
+                 the contents of a minisign.pub file)",
+            )),
+    );
+    subcommand
+}
+
+/// Download `url` to `path` with curl
+fn download(url: &str, path: &Path) -> Result<(), Error> {
+    let output = Command::new("curl")
+        .args([
+            "--fail",
+            "--silent",
+            "--show-error",
+            "--location",
+            "--output",
+        ])
+        .arg(path)
+        .arg(url)
+        .output()
+        .context("failed to run curl")?;
+    if !output.status.success() {
+        bail!(
+            "failed to download {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Download the signature of `url` alongside `path`, if the data is to be verified with a
+/// `public_key`
+#[cfg_attr(not(feature = "minisign"), allow(unused_variables))]
+fn download_signature(url: &str, path: &Path, public_key: Option<&str>) -> Result<(), Error> {
+    match public_key {
+        #[cfg(feature = "minisign")]
+        Some(_) => download(&format!("{}.minisig", url), &signature_path(path)),
+        _ => Ok(()),
+    }
+}
+
+/// Move the data file at `downloaded` to `path`, if it parses as data for `T`
+///
+/// With a `public_key`, the file's signature must also be valid, and is moved alongside it.
+pub fn install<T: DistroDataSource>(
+    downloaded: &Path,
+    path: &Path,
+    public_key: Option<&str>,
+) -> Result<(), Error> {
+    let parsed = match public_key {
+        #[cfg(feature = "minisign")]
+        Some(public_key) => T::from_path_verified(downloaded, public_key),
+        #[cfg(not(feature = "minisign"))]
+        Some(_) => bail!("verifying signatures needs the minisign feature"),
+        None => T::from_path(downloaded),
+    };
+    parsed.map_err(|e| format_err!("the downloaded data is invalid: {}", e))?;
+    #[cfg(feature = "minisign")]
+    if public_key.is_some() {
+        let signature = signature_path(path);
+        fs::rename(signature_path(downloaded), &signature)
+            .with_context(|_| format!("failed to install {}", signature.display()))?;
+    }
+    fs::rename(downloaded, path)
+        .with_context(|_| format!("failed to install {}", path.display()))?;
+    Ok(())
+}
+
+/// Download the latest data for `T` from `base_url` into the user's data directory, returning
+/// the path it was installed at
+///
+/// With a `public_key`, the data's signature is downloaded too, and must be valid.
+pub fn update_data<T: DistroDataSource>(
+    base_url: &str,
+    public_key: Option<&str>,
+) -> Result<PathBuf, Error> {
+    let file_name = Path::new(T::csv_path())
+        .file_name()
+        .unwrap_or_else(|| OsStr::new(T::csv_path()));
+    let dir = discovery::user_data_dir()
+        .ok_or_else(|| format_err!("unable to determine the user data directory"))?;
+    fs::create_dir_all(&dir).with_context(|_| format!("failed to create {}", dir.display()))?;
+    // Downloaded alongside the destination, so that installing it is an atomic rename
+    let mut partial = file_name.to_os_string();
+    partial.push(".partial");
+    let partial = dir.join(partial);
+    let path = dir.join(file_name);
+    let url = format!("{}/{}", base_url, file_name.to_string_lossy());
+    let result = download(&url, &partial)
+        .and_then(|()| download_signature(&url, &partial, public_key))
+        .and_then(|()| install::<T>(&partial, &path, public_key));
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+        #[cfg(feature = "minisign")]
+        let _ = fs::remove_file(signature_path(&partial));
+    }
+    result.map(|()| path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use distro_info::{test_support, UbuntuDistroInfo};
+
+    use super::install;
+
+    #[test]
+    fn install_validates() {
+        let dir = std::env::temp_dir().join(format!("distro-info-update-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let partial = dir.join("ubuntu.csv.partial");
+        let path = dir.join("ubuntu.csv");
+        fs::write(&partial, "not,a\nvalid,file\n").unwrap();
+        assert!(install::<UbuntuDistroInfo>(&partial, &path, None).is_err());
+        assert!(!path.exists());
+        fs::write(&partial, test_support::UBUNTU_CSV).unwrap();
+        install::<UbuntuDistroInfo>(&partial, &path, None).unwrap();
+        assert!(!partial.exists());
+        assert_eq!(test_support::UBUNTU_CSV, fs::read_to_string(&path).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "minisign")]
+    #[test]
+    fn install_verifies_signature() {
+        use distro_info::verify::signature_path;
+
+        let dir = std::env::temp_dir().join(format!("distro-info-signed-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let public_key = fs::read_to_string("../testdata/signed/minisign.pub").unwrap();
+        let partial = dir.join("ubuntu.csv.partial");
+        let path = dir.join("ubuntu.csv");
+        let signature = fs::read("../testdata/signed/ubuntu.csv.minisig").unwrap();
+        fs::write(&partial, test_support::UBUNTU_CSV).unwrap();
+        fs::write(signature_path(&partial), &signature).unwrap();
+        assert!(install::<UbuntuDistroInfo>(&partial, &path, Some(&public_key)).is_err());
+        assert!(!path.exists());
+        fs::copy("../testdata/signed/ubuntu.csv", &partial).unwrap();
+        install::<UbuntuDistroInfo>(&partial, &path, Some(&public_key)).unwrap();
+        assert!(path.exists());
+        assert_eq!(signature, fs::read(signature_path(&path)).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+//! Generates the `baked` module (with the `baked-data` feature) from the distro-info-data CSV
+//! files.
+//!
+//! The files are read from `$DISTRO_INFO_DATA_DIR` if it is set (e.g. to /usr/share/distro-info),
+//! or else from the snapshot in testdata/, so that the build doesn't depend on the host.
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DISTROS: [(&str, &str); 2] = [("UBUNTU", "ubuntu.csv"), ("DEBIAN", "debian.csv")];
+
+fn data_dir() -> PathBuf {
+    println!("cargo:rerun-if-env-changed=DISTRO_INFO_DATA_DIR");
+    match env::var_os("DISTRO_INFO_DATA_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata"),
+    }
+}
+
+/// The values of the series column of the CSV file at `path`
+fn series(path: &Path) -> Vec<String> {
+    println!("cargo:rerun-if-changed={}", path.display());
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().expect("CSV file has no header");
+    let column = header
+        .split(',')
+        .position(|column| column.trim() == "series")
+        .expect("CSV file has no series column");
+    lines
+        .filter_map(|line| line.split(',').nth(column))
+        .map(|series| series.trim().to_string())
+        .collect()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_BAKED_DATA").is_none() {
+        return;
+    }
+    let data_dir = data_dir();
+    let mut generated = String::new();
+    for (name, file) in DISTROS {
+        let path = data_dir.join(file);
+        let series = series(&path);
+        writeln!(
+            generated,
+            "/// The series in {} when this crate was built\n\
+             pub const {}_SERIES: [&str; {}] = {:?};",
+            path.display(),
+            name,
+            series.len(),
+            series
+        )
+        .unwrap();
+    }
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("baked.rs");
+    fs::write(out, generated).unwrap();
+}
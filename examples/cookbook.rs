@@ -0,0 +1,28 @@
+//! Answer the common questions about a release in one line each.
+//!
+//! Usage: cargo run --example cookbook [distro] [series]
+use std::env;
+
+use failure::Error;
+
+fn main() -> Result<(), Error> {
+    let mut args = env::args().skip(1);
+    let distro = args.next().unwrap_or_else(|| "ubuntu".to_string());
+    let series = args.next().unwrap_or_else(|| "focal".to_string());
+
+    let supported = distro_info::is_supported(&distro, &series)?;
+    println!(
+        "{} {} is {}supported",
+        distro,
+        series,
+        if supported { "" } else { "not " }
+    );
+    match distro_info::eol_date(&distro, &series)? {
+        Some(eol) => println!("its end of life is {}", eol),
+        None => println!("its end of life is not yet known"),
+    }
+    if let Some(lts) = distro_info::current_lts(&distro)? {
+        println!("the current LTS release is {}", lts.series());
+    }
+    Ok(())
+}
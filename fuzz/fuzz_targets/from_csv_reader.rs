@@ -0,0 +1,9 @@
+#![no_main]
+
+use distro_info::{DistroInfo, UbuntuDistroInfo};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let reader = csv::ReaderBuilder::new().flexible(true).from_reader(data);
+    let _ = UbuntuDistroInfo::from_csv_reader(reader);
+});
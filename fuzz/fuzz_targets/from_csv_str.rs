@@ -0,0 +1,8 @@
+#![no_main]
+
+use distro_info::{DistroInfo, UbuntuDistroInfo};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    let _ = UbuntuDistroInfo::from_csv_str(data);
+});
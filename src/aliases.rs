@@ -0,0 +1,118 @@
+//! Historical names for releases, beyond the series, codenames and versions in distro-info-data.
+//!
+//! [`DistroQueries::release_by_any_name`](../trait.DistroQueries.html#method.release_by_any_name)
+//! accepts, as well as anything `lookup` does:
+//!
+//! * the names in the [alias table](fn.historical_alias.html), such as Debian's
+//!   "etch-and-a-half";
+//! * point releases, in each of the styles Debian has used: "2.2r7" (potato to etch), "5.0.10"
+//!   (lenny and squeeze) and "12.5" (since wheezy), as well as Ubuntu's "22.04.3";
+//! * versions with a redundant ".0", such as "7.0" for wheezy (announced as Debian 7.0, but
+//!   numbered 7 in the data), or without one, such as "4" for etch.
+//!
+//! Debian skipped 1.0 (an unofficial CD had already been sold under that number), so "1.0" doesn't
+//! match buzz, which was 1.1.
+use alloc::vec::Vec;
+
+use crate::Distro;
+
+/// Names Debian releases have been known by, other than those in the data, and the series each
+/// refers to
+pub const DEBIAN_ALIASES: &[(&str, &str)] = &[
+    // 4.0r4 added a newer kernel and drivers alongside the original ones
+    ("etch-and-a-half", "etch"),
+    ("etchnhalf", "etch"),
+];
+
+/// The series a historical `name` for a release of `distro` refers to, if it is one
+pub fn historical_alias(distro: &Distro, name: &str) -> Option<&'static str> {
+    let aliases = match distro {
+        Distro::Debian => DEBIAN_ALIASES,
+        Distro::Ubuntu | Distro::Derivative(_) => &[],
+    };
+    aliases
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map(|(_, series)| *series)
+}
+
+/// Whether `needle` names `version`, or one of its point releases
+///
+/// The components of each (separated by "." or, for Debian's older point releases, "r") must agree
+/// as far as `version` goes, and any left over in `version` must be zero.
+pub fn version_matches(version: &str, needle: &str) -> bool {
+    if !needle.starts_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+    let components = |version: &str| {
+        version
+            .trim_end_matches(" LTS")
+            .split(['.', 'r'])
+            .map(|component| component.parse::<u32>().ok())
+            .collect::<Vec<_>>()
+    };
+    let (version, needle) = (components(version), components(needle));
+    version.iter().enumerate().all(|(i, component)| {
+        component.is_some()
+            && needle
+                .get(i)
+                .map_or(*component == Some(0), |needle| needle == component)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{historical_alias, version_matches};
+    use crate::{test_support, Distro, DistroInfo};
+
+    #[test]
+    fn version_matching() {
+        assert!(version_matches("3.0", "3.0"));
+        assert!(version_matches("3.0", "3.0r6"));
+        assert!(version_matches("6.0", "6.0.10"));
+        assert!(version_matches("12", "12.5"));
+        assert!(version_matches("7", "7.0"));
+        assert!(version_matches("4.0", "4"));
+        assert!(version_matches("22.04 LTS", "22.04.3"));
+        assert!(!version_matches("1.1", "1.0"));
+        assert!(!version_matches("2.1", "2.2r7"));
+        assert!(!version_matches("12", "1"));
+        assert!(!version_matches("12", "bookworm"));
+        assert_eq!(
+            Some("etch"),
+            historical_alias(&Distro::Debian, "Etch-And-A-Half")
+        );
+        assert_eq!(None, historical_alias(&Distro::Ubuntu, "etchnhalf"));
+    }
+
+    #[test]
+    fn release_by_any_name() {
+        let debian_distro_info = test_support::debian();
+        let series = |name| {
+            debian_distro_info
+                .release_by_any_name(name)
+                .map(|distro_release| distro_release.series().as_str())
+        };
+        assert_eq!(Some("woody"), series("woody"));
+        assert_eq!(Some("woody"), series("3.0"));
+        assert_eq!(Some("woody"), series("3.0r6"));
+        assert_eq!(Some("sarge"), series("Sarge"));
+        assert_eq!(Some("sarge"), series("3.1r8"));
+        assert_eq!(Some("potato"), series("2.2r7"));
+        assert_eq!(Some("etch"), series("4"));
+        assert_eq!(Some("etch"), series("etch-and-a-half"));
+        assert_eq!(Some("lenny"), series("5.0.10"));
+        assert_eq!(Some("wheezy"), series("7.0"));
+        assert_eq!(Some("bookworm"), series("12.5"));
+        assert_eq!(Some("sid"), series("sid"));
+        assert_eq!(None, series("1.0"));
+        assert_eq!(None, series("17"));
+        let ubuntu_distro_info = test_support::ubuntu();
+        assert_eq!(
+            Some("jammy"),
+            ubuntu_distro_info
+                .release_by_any_name("22.04.3")
+                .map(|distro_release| distro_release.series().as_str())
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! All of the built-in distros' data together, for tools which handle mixed fleets.
+//!
+//! [`load_all_from_dir`](fn.load_all_from_dir.html) instead loads whatever distros' data a
+//! directory holds, for tools which should pick up distros added to distro-info-data without
+//! code changes.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::thread;
+
+use failure::Error;
+
+use crate::compression::COMPRESSED_EXTENSIONS;
+use crate::matching::MatchMode;
+use crate::parser::ParseError;
+use crate::{
+    DebianDistroInfo, Distro, DistroDataSource, DistroInfo, DistroRelease, UbuntuDistroInfo,
+};
+
+/// The result of a loading thread, re-raising any panic in the caller
+fn join<T>(handle: thread::ScopedJoinHandle<'_, T>) -> T {
+    handle
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+/// The release data of every `Distro`
+pub struct AllDistros {
+    ubuntu: UbuntuDistroInfo,
+    debian: DebianDistroInfo,
+}
+
+impl AllDistros {
+    /// Combine already-loaded data
+    pub fn from_parts(ubuntu: UbuntuDistroInfo, debian: DebianDistroInfo) -> Self {
+        Self { ubuntu, debian }
+    }
+
+    /// Load the system's data for every distro (as `DistroDataSource::new` does), concurrently
+    pub fn load() -> Result<Self, Error> {
+        thread::scope(|scope| {
+            let ubuntu = scope.spawn(UbuntuDistroInfo::new);
+            let debian = scope.spawn(DebianDistroInfo::new);
+            Ok(Self {
+                ubuntu: join(ubuntu)?,
+                debian: join(debian)?,
+            })
+        })
+    }
+
+    /// The data for `distro`, or `None` for a derivative (which has no built-in data)
+    pub fn by_kind(&self, distro: Distro) -> Option<&dyn DistroInfo> {
+        match distro {
+            Distro::Ubuntu => Some(&self.ubuntu),
+            Distro::Debian => Some(&self.debian),
+            Distro::Derivative(_) => None,
+        }
+    }
+
+    /// Each distro's data, in the order of `Distro::ALL`
+    pub fn iter(&self) -> impl Iterator<Item = (Distro, &dyn DistroInfo)> {
+        Distro::ALL
+            .iter()
+            .filter_map(move |distro| Some((*distro, self.by_kind(*distro)?)))
+    }
+
+    /// The release with the given series in any distro, and the distro it belongs to
+    ///
+    /// Series are matched exactly; the distros are searched in the order of `Distro::ALL`.
+    pub fn find_series_anywhere(&self, series: &str) -> Option<(Distro, &DistroRelease)> {
+        self.iter().find_map(|(distro, distro_info)| {
+            distro_info
+                .find_series(series, MatchMode::Strict)
+                .map(|distro_release| (distro, distro_release))
+        })
+    }
+}
+
+/// The name of the distro whose data is in the file at `path`, e.g. "ubuntu" for ubuntu.csv (or
+/// ubuntu.csv.gz), if it is a CSV file
+fn csv_distro_name(path: &Path) -> Option<&str> {
+    let file_name = path.file_name()?.to_str()?;
+    let uncompressed = COMPRESSED_EXTENSIONS
+        .iter()
+        .find_map(|extension| {
+            file_name
+                .strip_suffix(extension)
+                .and_then(|name| name.strip_suffix('.'))
+        })
+        .unwrap_or(file_name);
+    uncompressed
+        .strip_suffix(".csv")
+        .filter(|name| !name.is_empty())
+}
+
+/// Load the data of every distro with a `*.csv` file (possibly compressed) in `dir`, such as
+/// /usr/share/distro-info, keyed by the file's name without its extension (e.g. "ubuntu")
+///
+/// Files named after a built-in `Distro` are loaded as its data; any others are loaded as Debian
+/// data, as their policy isn't known.  CSV files which aren't release data
+/// (lacking its required columns, as ubuntu-flavours.csv does) are skipped, and an uncompressed
+/// file is preferred to a compressed one of the same name.
+pub fn load_all_from_dir<P: AsRef<Path>>(
+    dir: P,
+) -> Result<BTreeMap<String, Box<dyn DistroInfo>>, Error> {
+    let mut paths = fs::read_dir(dir.as_ref())?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>, std::io::Error>>()?;
+    // So that ubuntu.csv comes before ubuntu.csv.gz
+    paths.sort();
+    let mut distros = BTreeMap::new();
+    for path in paths {
+        let name = match csv_distro_name(&path) {
+            Some(name) if path.is_file() && !distros.contains_key(name) => name.to_string(),
+            _ => continue,
+        };
+        let releases = match DebianDistroInfo::from_path(&path) {
+            Ok(distro_info) => distro_info.releases().to_vec(),
+            Err(e) => match e.downcast_ref::<ParseError>() {
+                Some(ParseError::MissingHeader) | Some(ParseError::MissingColumn(_)) => continue,
+                _ => return Err(format_err!("failed to read {}: {}", path.display(), e)),
+            },
+        };
+        let distro = Distro::from_name(&name).unwrap_or(Distro::Debian);
+        distros.insert(name, distro.distro_info(releases));
+    }
+    Ok(distros)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{load_all_from_dir, AllDistros};
+    use crate::{test_support, Distro, DistroInfo};
+
+    #[test]
+    fn find_series_anywhere() {
+        let all_distros = AllDistros::from_parts(test_support::ubuntu(), test_support::debian());
+        let found = |series| {
+            all_distros
+                .find_series_anywhere(series)
+                .map(|(distro, distro_release)| (distro, distro_release.series().clone()))
+        };
+        assert_eq!(
+            Some((Distro::Debian, "bookworm".to_string())),
+            found("bookworm")
+        );
+        assert_eq!(Some((Distro::Ubuntu, "jammy".to_string())), found("jammy"));
+        assert_eq!(None, found("nonexistent"));
+        assert_eq!(
+            Distro::Ubuntu,
+            *all_distros.by_kind(Distro::Ubuntu).unwrap().distro()
+        );
+    }
+
+    #[test]
+    fn load_all_from_testdata_dir() {
+        let distros = load_all_from_dir(test_support::testdata_path("")).unwrap();
+        assert_eq!(
+            vec!["debian", "ubuntu"],
+            distros.keys().map(String::as_str).collect::<Vec<_>>()
+        );
+        assert_eq!(Distro::Ubuntu, *distros["ubuntu"].distro());
+        assert_eq!(test_support::debian().len(), distros["debian"].len());
+    }
+
+    #[test]
+    fn load_all_from_dir_with_unknown_distros() {
+        let dir = std::env::temp_dir().join(format!("distro-info-all-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("ubuntu.csv"), test_support::UBUNTU_CSV).unwrap();
+        fs::write(
+            dir.join("kali.csv"),
+            "version,codename,series,created,release,eol\n\
+             2024.1,Kali 2024.1,kali-2024-1,2024-01-01,2024-02-27,2024-05-15\n",
+        )
+        .unwrap();
+        fs::write(dir.join("ubuntu-flavours.csv"), "flavour,series,eol\n").unwrap();
+        fs::write(dir.join("README"), "not data").unwrap();
+        let distros = load_all_from_dir(&dir).unwrap();
+        assert_eq!(
+            vec!["kali", "ubuntu"],
+            distros.keys().map(String::as_str).collect::<Vec<_>>()
+        );
+        assert_eq!(Distro::Debian, *distros["kali"].distro());
+        assert!(distros["kali"].lookup("kali-2024-1").is_some());
+        fs::write(
+            dir.join("broken.csv"),
+            "version,codename,series,created\n1,A,a,bad\n",
+        )
+        .unwrap();
+        let err = load_all_from_dir(&dir).err().unwrap();
+        assert!(err.to_string().contains("broken.csv"), "{}", err);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
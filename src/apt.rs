@@ -0,0 +1,229 @@
+//! Inferring release data from apt's metadata, for systems without distro-info-data.
+//!
+//! Minimal containers often lack the distro-info-data package, but do have apt's lists of the
+//! suites they are configured to use.  The `Release`/`InRelease` files in `/var/lib/apt/lists` (or
+//! failing that, the output of `apt-get indextargets`) name the codename and version of each
+//! suite, which is enough for a degraded-but-useful `DistroInfo`.  Releases built this way only
+//! know about the suites apt is configured for, have no EOL dates, and are marked as
+//! [`inferred`](../struct.DistroRelease.html#method.inferred).
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use chrono::naive::NaiveDate;
+use failure::Error;
+
+use crate::{Distro, DistroRelease};
+
+/// The directory apt stores downloaded lists in
+pub const APT_LISTS_DIR: &str = "/var/lib/apt/lists";
+
+/// Infer the releases of `distro` from apt's lists, falling back to `apt-get indextargets`
+pub fn infer_releases(distro: &Distro) -> Result<Vec<DistroRelease>, Error> {
+    let releases = releases_from_lists_dir(Path::new(APT_LISTS_DIR), distro)?;
+    if !releases.is_empty() {
+        trace_event!(
+            debug,
+            releases = releases.len(),
+            "inferred releases from apt lists"
+        );
+        return Ok(releases);
+    }
+    trace_event!(
+        debug,
+        "no releases in apt lists; trying apt-get indextargets"
+    );
+    let output = Command::new("apt-get").arg("indextargets").output()?;
+    if !output.status.success() {
+        bail!("apt-get indextargets failed: {}", output.status);
+    }
+    Ok(releases_from_stanzas(
+        &String::from_utf8_lossy(&output.stdout),
+        distro,
+    ))
+}
+
+/// Infer the releases of `distro` from the `Release` and `InRelease` files in `dir`
+pub fn releases_from_lists_dir(dir: &Path, distro: &Distro) -> Result<Vec<DistroRelease>, Error> {
+    let mut contents = String::new();
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.ends_with("_Release") || name.ends_with("_InRelease"))
+                .unwrap_or(false)
+        })
+        .collect();
+    paths.sort();
+    for path in paths {
+        contents.push_str(&fs::read_to_string(path)?);
+        contents.push_str("\n\n");
+    }
+    Ok(releases_from_stanzas(&contents, distro))
+}
+
+/// Infer the releases of `distro` from deb822 stanzas, as found in `Release` files or output by
+/// `apt-get indextargets`
+///
+/// Stanzas from other origins, and for pockets such as `bookworm-security`, are ignored.
+pub fn releases_from_stanzas(input: &str, distro: &Distro) -> Vec<DistroRelease> {
+    let mut releases: Vec<DistroRelease> = vec![];
+    for stanza in stanzas(input) {
+        let field = |name: &str| {
+            stanza
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value)
+        };
+        if field("Origin") != Some(distro.display_name()) {
+            continue;
+        }
+        let series = match field("Codename") {
+            Some(series) if !series.contains('-') => series,
+            _ => continue,
+        };
+        if releases.iter().any(|r| r.series() == series) {
+            continue;
+        }
+        let mut codename = series.to_string();
+        if let Some(first) = codename.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        let date = field("Date").and_then(parse_release_date);
+        releases.push(
+            DistroRelease::new(
+                field("Version").unwrap_or("").to_string(),
+                codename,
+                series.to_string(),
+                date,
+                date,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .with_inferred(true),
+        );
+    }
+    releases
+}
+
+/// Split deb822 input into stanzas of (field, value) pairs, skipping any PGP armour
+fn stanzas(input: &str) -> Vec<Vec<(&str, &str)>> {
+    let mut stanzas = vec![];
+    let mut current = vec![];
+    let mut in_signature = false;
+    for line in input.lines() {
+        if line.starts_with("-----BEGIN PGP SIGNATURE") {
+            in_signature = true;
+        } else if line.starts_with("-----END PGP SIGNATURE") {
+            in_signature = false;
+            continue;
+        }
+        if in_signature || line.starts_with("-----BEGIN PGP SIGNED") || line.starts_with("Hash:") {
+            continue;
+        }
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                stanzas.push(std::mem::take(&mut current));
+            }
+        } else if !line.starts_with(' ') {
+            if let Some((key, value)) = line.split_once(':') {
+                current.push((key.trim(), value.trim()));
+            }
+        }
+    }
+    if !current.is_empty() {
+        stanzas.push(current);
+    }
+    stanzas
+}
+
+/// Parse the date from a `Date: Sat, 10 Jun 2023 08:54:14 UTC` field
+fn parse_release_date(value: &str) -> Option<NaiveDate> {
+    let mut parts = value.split_whitespace().skip(1);
+    let (day, month, year) = (parts.next()?, parts.next()?, parts.next()?);
+    NaiveDate::parse_from_str(&format!("{} {} {}", day, month, year), "%d %b %Y").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::{releases_from_lists_dir, releases_from_stanzas};
+    use crate::{test_support, Distro};
+
+    const IN_RELEASE: &str = "-----BEGIN PGP SIGNED MESSAGE-----
+Hash: SHA256
+
+Origin: Debian
+Label: Debian
+Suite: stable
+Version: 12.1
+Codename: bookworm
+Date: Sat, 22 Jul 2023 09:39:44 UTC
+MD5Sum:
+ 0ed6d4c8891eb86358b94bb35d9e4da4  1484322 contrib/Contents-all
+-----BEGIN PGP SIGNATURE-----
+
+iQIzBAEBCAAdFiEE
+-----END PGP SIGNATURE-----
+";
+
+    const INDEXTARGETS: &str = "MetaKey: main/binary-amd64/Packages
+Codename: bookworm-security
+Origin: Debian
+
+MetaKey: main/binary-amd64/Packages
+Codename: trixie
+Origin: Debian
+Version: 13.0
+
+MetaKey: main/binary-amd64/Packages
+Codename: nodistro
+Origin: . nodistro
+";
+
+    #[test]
+    fn from_in_release() {
+        let releases = releases_from_stanzas(IN_RELEASE, &Distro::Debian);
+        assert_eq!(1, releases.len());
+        let bookworm = &releases[0];
+        assert_eq!("bookworm", bookworm.series());
+        assert_eq!("Bookworm", bookworm.codename());
+        assert_eq!(&Some("12.1".to_string()), bookworm.version());
+        assert_eq!(&Some(test_support::date("2023-07-22")), bookworm.release());
+        assert!(bookworm.inferred());
+        assert!(releases_from_stanzas(IN_RELEASE, &Distro::Ubuntu).is_empty());
+    }
+
+    #[test]
+    fn from_indextargets() {
+        let releases = releases_from_stanzas(INDEXTARGETS, &Distro::Debian);
+        assert_eq!(1, releases.len());
+        assert_eq!("trixie", releases[0].series());
+        assert_eq!(&None, releases[0].release());
+    }
+
+    #[test]
+    fn from_lists_dir() {
+        let dir = env::temp_dir().join(format!("distro-info-apt-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("deb.debian.org_debian_dists_bookworm_InRelease"),
+            IN_RELEASE,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("deb.debian.org_debian_dists_bookworm_Packages"),
+            "",
+        )
+        .unwrap();
+        let releases = releases_from_lists_dir(&dir, &Distro::Debian).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(1, releases.len());
+    }
+}
@@ -0,0 +1,79 @@
+//! Loading data from async code, without blocking the runtime.
+//!
+//! The file is read with `tokio::fs`, and decompressed and parsed on tokio's blocking thread
+//! pool, so services can load (or reload) their data from async handlers.  These functions must
+//! be called from within a tokio runtime.
+use std::io::Cursor;
+use std::path::Path;
+
+use failure::Error;
+
+use crate::{compression, csv_reader, DebianDistroInfo, DistroDataSource, UbuntuDistroInfo};
+
+/// Read the CSV file at `path` and parse the release data contained therein, as
+/// `DistroDataSource::from_path` does
+pub async fn from_path<T>(path: impl AsRef<Path>) -> Result<T, Error>
+where
+    T: DistroDataSource + Send + 'static,
+{
+    let path = path.as_ref().to_path_buf();
+    let data = tokio::fs::read(&path).await?;
+    tokio::task::spawn_blocking(move || parse(data, &path)).await?
+}
+
+/// Decompress and parse `data`, the contents of `path`
+fn parse<T: DistroDataSource>(data: Vec<u8>, path: &Path) -> Result<T, Error> {
+    T::from_csv_reader(csv_reader(compression::decompressing(
+        Cursor::new(data),
+        path,
+    )?))
+}
+
+/// Read and parse the system's data for `T`, as `DistroDataSource::new` does
+pub async fn new<T>() -> Result<T, Error>
+where
+    T: DistroDataSource + Send + 'static,
+{
+    from_path(T::data_path()).await
+}
+
+impl UbuntuDistroInfo {
+    /// Read and parse the system's Ubuntu data without blocking the runtime
+    pub async fn new_async() -> Result<Self, Error> {
+        new().await
+    }
+}
+
+impl DebianDistroInfo {
+    /// Read and parse the system's Debian data without blocking the runtime
+    pub async fn new_async() -> Result<Self, Error> {
+        new().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_path;
+    use crate::{DistroDataSource, DistroInfo, UbuntuDistroInfo};
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn from_path_async() {
+        let ubuntu_distro_info: UbuntuDistroInfo =
+            block_on(from_path("testdata/ubuntu.csv")).unwrap();
+        assert_eq!(
+            UbuntuDistroInfo::from_path("testdata/ubuntu.csv")
+                .unwrap()
+                .iter()
+                .count(),
+            ubuntu_distro_info.iter().count()
+        );
+        assert!(block_on(from_path::<UbuntuDistroInfo>("testdata/missing.csv")).is_err());
+    }
+}
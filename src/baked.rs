@@ -0,0 +1,64 @@
+//! Series names baked in when the crate was built (with the `baked-data` feature), for
+//! compile-time checks in downstream crates:
+//!
+//! ```
+//! use distro_info::baked;
+//!
+//! const _: () = assert!(baked::is_ubuntu_series("jammy"));
+//! ```
+//!
+//! The data is read by the build script from `$DISTRO_INFO_DATA_DIR` if it is set (e.g. to
+//! /usr/share/distro-info), or else from a snapshot shipped with the crate.  It is only as current
+//! as the build; use `DistroDataSource::new` for anything else.
+include!(concat!(env!("OUT_DIR"), "/baked.rs"));
+
+/// Whether `series` is one of `names`, usable in `const` contexts
+pub const fn contains(names: &[&str], series: &str) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        if bytes_eq(names[i].as_bytes(), series.as_bytes()) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Whether `series` is an Ubuntu series, usable in `const` contexts
+pub const fn is_ubuntu_series(series: &str) -> bool {
+    contains(&UBUNTU_SERIES, series)
+}
+
+/// Whether `series` is a Debian series, usable in `const` contexts
+pub const fn is_debian_series(series: &str) -> bool {
+    contains(&DEBIAN_SERIES, series)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_debian_series, is_ubuntu_series};
+
+    const _: () = assert!(is_ubuntu_series("jammy"));
+
+    #[test]
+    fn baked_series() {
+        assert!(is_ubuntu_series("warty"));
+        assert!(is_debian_series("bookworm"));
+        assert!(!is_ubuntu_series("bookworm"));
+        assert!(!is_debian_series("jam"));
+    }
+}
@@ -0,0 +1,71 @@
+//! Answering "which release was current?" for large numbers of dates.
+//!
+//! [`BatchResolver`](struct.BatchResolver.html) precomputes the result of
+//! [`DistroQueries::latest`](../trait.DistroQueries.html#method.latest) for every interval between
+//! milestones, so each query is a binary search rather than a scan of all releases.  With the
+//! `rayon` feature, `resolve_many` resolves its dates in parallel.
+use alloc::vec::Vec;
+
+use chrono::naive::NaiveDate;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{DistroInfo, DistroRelease};
+
+/// An index mapping dates to the then-current release of a distro
+pub struct BatchResolver<'a> {
+    /// The dates at which the current release may change, in ascending order
+    boundaries: Vec<NaiveDate>,
+    /// The current release from each boundary up to (but excluding) the next
+    current: Vec<Option<&'a DistroRelease>>,
+}
+
+impl<'a> BatchResolver<'a> {
+    /// Build the index for the releases in `distro_info`
+    pub fn new<D: DistroInfo + ?Sized>(distro_info: &'a D) -> Self {
+        let mut boundaries: Vec<NaiveDate> = distro_info
+            .iter()
+            .flat_map(|distro_release| {
+                let end_of_support = |date: &Option<NaiveDate>| date.and_then(|d| d.succ_opt());
+                [
+                    *distro_release.created(),
+                    *distro_release.release(),
+                    end_of_support(distro_release.eol()),
+                    end_of_support(distro_release.eol_server()),
+                ]
+            })
+            .flatten()
+            .collect();
+        boundaries.sort();
+        boundaries.dedup();
+        let current = boundaries
+            .iter()
+            .map(|boundary| distro_info.latest(*boundary))
+            .collect();
+        Self {
+            boundaries,
+            current,
+        }
+    }
+
+    /// Returns the release that was current at the given date (as `DistroInfo::latest` would)
+    pub fn resolve(&self, date: NaiveDate) -> Option<&'a DistroRelease> {
+        match self.boundaries.binary_search(&date) {
+            Ok(i) => self.current[i],
+            Err(0) => None,
+            Err(i) => self.current[i - 1],
+        }
+    }
+
+    /// Returns the release that was current at each of the given dates
+    #[cfg(not(feature = "rayon"))]
+    pub fn resolve_many(&self, dates: &[NaiveDate]) -> Vec<Option<&'a DistroRelease>> {
+        dates.iter().map(|date| self.resolve(*date)).collect()
+    }
+
+    /// Returns the release that was current at each of the given dates
+    #[cfg(feature = "rayon")]
+    pub fn resolve_many(&self, dates: &[NaiveDate]) -> Vec<Option<&'a DistroRelease>> {
+        dates.par_iter().map(|date| self.resolve(*date)).collect()
+    }
+}
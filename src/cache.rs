@@ -0,0 +1,116 @@
+//! A binary cache of parsed release data, to avoid re-parsing the CSV files on every start up.
+//!
+//! Cache files live in `$XDG_CACHE_HOME/distro-info` (falling back to `~/.cache/distro-info`) and
+//! are keyed on the path and modification time of the CSV file they were generated from, so they
+//! are transparently regenerated whenever distro-info-data is updated.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use failure::Error;
+
+use crate::DistroRelease;
+
+/// The version of the cache format, so caches written by other versions of this crate (whose
+/// `DistroRelease` may differ) are regenerated rather than misread
+const CACHE_FORMAT: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    format: String,
+    csv_path: PathBuf,
+    csv_mtime: SystemTime,
+    releases: Vec<DistroRelease>,
+}
+
+/// The directory cache files are stored in, if one can be determined
+pub fn cache_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")),
+    }
+    .map(|dir| dir.join("distro-info"))
+}
+
+fn cache_path(csv_path: &Path) -> Option<PathBuf> {
+    let file_name = csv_path.file_name()?;
+    let mut cache_name = file_name.to_os_string();
+    cache_name.push(".bin");
+    Some(cache_dir()?.join(cache_name))
+}
+
+/// Read the releases cached for `csv_path`, if there is an up-to-date cache for it
+pub fn load(csv_path: &Path) -> Option<Vec<DistroRelease>> {
+    load_from(&cache_path(csv_path)?, csv_path)
+}
+
+/// Write `releases` (as parsed from `csv_path`) to the cache
+pub fn store(csv_path: &Path, releases: &[DistroRelease]) -> Result<(), Error> {
+    let path = cache_path(csv_path).ok_or(format_err!("unable to determine cache directory"))?;
+    store_to(&path, csv_path, releases)
+}
+
+fn load_from(path: &Path, csv_path: &Path) -> Option<Vec<DistroRelease>> {
+    let csv_mtime = fs::metadata(csv_path).and_then(|m| m.modified()).ok()?;
+    let contents = fs::read(path).ok()?;
+    let entry: CacheEntry = bincode::deserialize(&contents).ok()?;
+    if entry.format == CACHE_FORMAT && entry.csv_path == csv_path && entry.csv_mtime == csv_mtime {
+        Some(entry.releases)
+    } else {
+        None
+    }
+}
+
+fn store_to(path: &Path, csv_path: &Path, releases: &[DistroRelease]) -> Result<(), Error> {
+    let entry = CacheEntry {
+        format: CACHE_FORMAT.to_string(),
+        csv_path: csv_path.to_path_buf(),
+        csv_mtime: fs::metadata(csv_path)?.modified()?,
+        releases: releases.to_vec(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Write to a temporary file first so concurrent readers never see a partial cache
+    let tmp_path = path.with_extension(format!("bin.{}.tmp", std::process::id()));
+    fs::write(&tmp_path, bincode::serialize(&entry)?)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::{load_from, store_to, CacheEntry};
+    use crate::{test_support, DistroInfo};
+
+    #[test]
+    fn cache_round_trip() {
+        let dir = env::temp_dir().join(format!("distro-info-cache-test-{}", std::process::id()));
+        let cache_file = dir.join("ubuntu.csv.bin");
+        let csv_path = &test_support::testdata_path("ubuntu.csv");
+        assert!(load_from(&cache_file, csv_path).is_none());
+
+        let ubuntu_distro_info = test_support::ubuntu();
+        store_to(&cache_file, csv_path, ubuntu_distro_info.releases()).unwrap();
+        let cached = load_from(&cache_file, csv_path).unwrap();
+        assert_eq!(ubuntu_distro_info.releases().len(), cached.len());
+        assert_eq!(
+            ubuntu_distro_info.releases()[0].series(),
+            cached[0].series()
+        );
+
+        // A cache generated from a different file is not used
+        assert!(load_from(&cache_file, &test_support::testdata_path("debian.csv")).is_none());
+
+        // Nor is one written by another version of the crate
+        let mut entry: CacheEntry = bincode::deserialize(&fs::read(&cache_file).unwrap()).unwrap();
+        entry.format = "0.0.0".to_string();
+        fs::write(&cache_file, bincode::serialize(&entry).unwrap()).unwrap();
+        assert!(load_from(&cache_file, csv_path).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,245 @@
+//! The cadence of a distro's releases: the intervals between them, the days of the week they fall
+//! on, and when the next is likely.
+//!
+//! Ubuntu releases every six months, in April and October, so
+//! [`predict_next_release`](fn.predict_next_release.html) steps through those months.  Debian
+//! releases when it is ready (roughly every two years), so its next release is predicted from the
+//! median of the recent intervals between releases, with a window as wide as their spread.  Either
+//! way, the result is a [`PredictedRelease`](struct.PredictedRelease.html), whose notes say how far
+//! it can be trusted.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use chrono::naive::NaiveDate;
+use chrono::{Datelike, Duration, Months, Weekday};
+
+use crate::{Distro, DistroInfo, DistroRelease};
+
+/// How many of the most recent intervals between releases Debian's cadence is taken from
+pub const RECENT_INTERVALS: usize = 5;
+
+/// The time between the releases of two consecutive releases
+#[derive(Clone, Copy, Debug)]
+pub struct ReleaseInterval<'a> {
+    pub from: &'a DistroRelease,
+    pub to: &'a DistroRelease,
+    pub days: i64,
+}
+
+/// When the next release is expected, as predicted from the cadence of earlier ones
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PredictedRelease {
+    /// The most likely release date
+    pub expected: NaiveDate,
+    /// The first and last dates the release is likely to fall between
+    pub window: (NaiveDate, NaiveDate),
+    /// Caveats about the prediction, such as what it is based on
+    pub notes: Vec<String>,
+}
+
+impl DistroRelease {
+    /// The day of the week this release was (or is to be) released on, if its date is known
+    pub fn release_weekday(&self) -> Option<Weekday> {
+        self.release.map(|release| release.weekday())
+    }
+}
+
+/// The intervals between the releases of `distro_info` with known release dates, oldest first
+pub fn release_intervals(distro_info: &dyn DistroInfo) -> Vec<ReleaseInterval<'_>> {
+    let released: Vec<_> = distro_info
+        .iter()
+        .filter_map(|distro_release| Some((distro_release, (*distro_release.release())?)))
+        .collect();
+    released
+        .windows(2)
+        .map(|pair| ReleaseInterval {
+            from: pair[0].0,
+            to: pair[1].0,
+            days: pair[1].1.signed_duration_since(pair[0].1).num_days(),
+        })
+        .collect()
+}
+
+/// The day of the week most of the releases of `distro_info` fall on (the later in the week, if
+/// there is a tie), if any release dates are known
+pub fn usual_weekday(distro_info: &dyn DistroInfo) -> Option<Weekday> {
+    let mut counts = [0usize; 7];
+    for weekday in distro_info
+        .iter()
+        .filter_map(DistroRelease::release_weekday)
+    {
+        counts[weekday.num_days_from_monday() as usize] += 1;
+    }
+    let (day, count) = counts.iter().enumerate().max_by_key(|(_, count)| **count)?;
+    if *count == 0 {
+        return None;
+    }
+    Weekday::try_from(day as u8).ok()
+}
+
+/// The day closest to `date` which falls on `weekday`
+fn nearest_weekday(date: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let offset = (7 + weekday.num_days_from_monday() as i64
+        - date.weekday().num_days_from_monday() as i64)
+        % 7;
+    date + Duration::days(if offset > 3 { offset - 7 } else { offset })
+}
+
+/// Predict when the first release of `distro_info` after `date` will be
+///
+/// If the data already has a release date after `date`, that is the prediction.  Otherwise,
+/// this steps through the cadence from the last release on or before `date`, and moves the
+/// result to the `usual_weekday`.  Returns `None` if there are too few release dates to go on.
+pub fn predict_next_release(
+    distro_info: &dyn DistroInfo,
+    date: NaiveDate,
+) -> Option<PredictedRelease> {
+    let release_dates: Vec<NaiveDate> = distro_info
+        .iter()
+        .filter_map(|distro_release| *distro_release.release())
+        .collect();
+    if let Some(announced) = release_dates
+        .iter()
+        .filter(|release| **release > date)
+        .min()
+    {
+        return Some(PredictedRelease {
+            expected: *announced,
+            window: (*announced, *announced),
+            notes: vec!["the release date is in the data".to_string()],
+        });
+    }
+    let last = *release_dates.iter().max()?;
+    let mut prediction = match distro_info.distro() {
+        Distro::Ubuntu => predict_by_months(last, date)?,
+        Distro::Debian | Distro::Derivative(_) => {
+            predict_by_intervals(&release_intervals(distro_info), last, date)?
+        }
+    };
+    if let Some(weekday) = usual_weekday(distro_info) {
+        let expected = nearest_weekday(prediction.expected, weekday);
+        if expected > date {
+            prediction.expected = expected;
+            prediction.notes.push(format!(
+                "moved to the nearest {}, the weekday most releases have been on",
+                weekday
+            ));
+        }
+    }
+    Some(prediction)
+}
+
+/// Step six months at a time from `last` until after `date`, with a window of the whole month
+fn predict_by_months(last: NaiveDate, date: NaiveDate) -> Option<PredictedRelease> {
+    let mut expected = last;
+    let mut steps = 0;
+    while expected <= date {
+        expected = expected.checked_add_months(Months::new(6))?;
+        steps += 1;
+    }
+    let month_start = expected.with_day(1)?;
+    let month_end = month_start.checked_add_months(Months::new(1))?.pred_opt()?;
+    let mut notes = vec!["releases are every six months, in April and October".to_string()];
+    if steps > 1 {
+        notes.push(format!(
+            "{} releases beyond the last in the data, so the version may have changed cadence",
+            steps - 1
+        ));
+    }
+    Some(PredictedRelease {
+        expected,
+        window: (month_start, month_end),
+        notes,
+    })
+}
+
+/// Step by the median of the most recent `intervals` from `last` until after `date`, with a
+/// window as wide as their spread
+fn predict_by_intervals(
+    intervals: &[ReleaseInterval],
+    last: NaiveDate,
+    date: NaiveDate,
+) -> Option<PredictedRelease> {
+    let mut recent: Vec<i64> = intervals
+        .iter()
+        .rev()
+        .take(RECENT_INTERVALS)
+        .map(|interval| interval.days)
+        .collect();
+    recent.sort_unstable();
+    let median = *recent.get(recent.len() / 2)?;
+    let (shortest, longest) = (recent[0], recent[recent.len() - 1]);
+    let mut expected = last;
+    while expected <= date {
+        expected += Duration::days(median);
+    }
+    let mut notes = vec![
+        format!(
+            "based on the median of the last {} intervals between releases ({} to {} days)",
+            recent.len(),
+            shortest,
+            longest
+        ),
+        "releases are made when ready, so this is only a rough guide".to_string(),
+    ];
+    if last + Duration::days(longest) < date {
+        notes.push("the next release is later than any recent interval".to_string());
+    }
+    let earliest = ::core::cmp::max(
+        expected - Duration::days(median - shortest),
+        date.succ_opt()?,
+    );
+    Some(PredictedRelease {
+        expected,
+        window: (earliest, expected + Duration::days(longest - median)),
+        notes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use super::{predict_next_release, release_intervals, usual_weekday};
+    use crate::test_support;
+
+    #[test]
+    fn intervals_and_weekdays() {
+        let debian_distro_info = test_support::debian();
+        let intervals = release_intervals(&debian_distro_info);
+        let bookworm = intervals
+            .iter()
+            .find(|interval| interval.to.series() == "bookworm")
+            .unwrap();
+        assert_eq!("bullseye", bookworm.from.series());
+        assert_eq!(665, bookworm.days);
+        assert_eq!(Some(Weekday::Sat), usual_weekday(&debian_distro_info));
+        assert_eq!(Some(Weekday::Thu), usual_weekday(&test_support::ubuntu()));
+    }
+
+    #[test]
+    fn predictions() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let announced =
+            predict_next_release(&ubuntu_distro_info, test_support::date("2026-01-01")).unwrap();
+        assert_eq!(test_support::date("2026-04-23"), announced.expected);
+        let predicted =
+            predict_next_release(&ubuntu_distro_info, test_support::date("2026-06-01")).unwrap();
+        assert_eq!(
+            (
+                test_support::date("2026-10-01"),
+                test_support::date("2026-10-31")
+            ),
+            predicted.window
+        );
+        assert_eq!(Weekday::Thu, chrono::Datelike::weekday(&predicted.expected));
+        assert_eq!(2, predicted.notes.len());
+        let debian_distro_info = test_support::debian();
+        let date = test_support::date("2025-09-01");
+        let predicted = predict_next_release(&debian_distro_info, date).unwrap();
+        assert!(predicted.window.0 > date);
+        assert!(predicted.window.0 <= predicted.expected);
+        assert!(predicted.expected <= predicted.window.1);
+        assert!(predicted.notes[0].starts_with("based on the median of the last 5 intervals"));
+    }
+}
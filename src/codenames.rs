@@ -0,0 +1,88 @@
+//! Ubuntu's "codename alphabet": since breezy, each release's series starts with the letter after
+//! its predecessor's, wrapping from "z" back to "a" (zesty was followed by artful).
+//!
+//! The first two releases, warty and hoary, predate the scheme, so each forms a cycle of its own.
+use alloc::vec::Vec;
+
+use crate::{DistroInfo, DistroRelease, UbuntuDistroInfo};
+
+/// The letter a release's series starts with, lowercased
+pub fn codename_letter(distro_release: &DistroRelease) -> Option<char> {
+    distro_release
+        .series()
+        .chars()
+        .next()
+        .map(|letter| letter.to_ascii_lowercase())
+}
+
+impl UbuntuDistroInfo {
+    /// The releases whose series start with `letter` (in either case), oldest first
+    ///
+    /// There may be several, one per trip through the alphabet.
+    pub fn codename_series_for_letter(&self, letter: char) -> Vec<&DistroRelease> {
+        let letter = letter.to_ascii_lowercase();
+        self.iter()
+            .filter(|distro_release| codename_letter(distro_release) == Some(letter))
+            .collect()
+    }
+
+    /// The releases which start a new trip through the alphabet, i.e. whose letter does not come
+    /// after their predecessor's, oldest first
+    ///
+    /// The first release is not included.
+    pub fn codename_alphabet_wraps(&self) -> Vec<&DistroRelease> {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .filter(|(previous, distro_release)| {
+                codename_letter(distro_release) <= codename_letter(previous)
+            })
+            .map(|(_, distro_release)| distro_release)
+            .collect()
+    }
+
+    /// The releases split into trips through the alphabet, oldest first
+    pub fn codename_alphabet_cycles(&self) -> Vec<Vec<&DistroRelease>> {
+        let mut cycles: Vec<Vec<&DistroRelease>> = Vec::new();
+        for distro_release in self.iter() {
+            match cycles.last_mut() {
+                Some(cycle)
+                    if codename_letter(distro_release)
+                        > cycle.last().and_then(|previous| codename_letter(previous)) =>
+                {
+                    cycle.push(distro_release)
+                }
+                _ => cycles.push(vec![distro_release]),
+            }
+        }
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support;
+
+    #[test]
+    fn codename_alphabet() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let series = |releases: Vec<&crate::DistroRelease>| -> Vec<String> {
+            releases
+                .into_iter()
+                .map(|distro_release| distro_release.series().clone())
+                .collect()
+        };
+        assert_eq!(
+            vec!["natty", "noble"],
+            series(ubuntu_distro_info.codename_series_for_letter('N'))
+        );
+        assert_eq!(
+            vec!["hoary", "breezy", "artful"],
+            series(ubuntu_distro_info.codename_alphabet_wraps())
+        );
+        let cycles = ubuntu_distro_info.codename_alphabet_cycles();
+        assert_eq!(4, cycles.len());
+        assert_eq!("breezy", cycles[2].first().unwrap().series());
+        assert_eq!("zesty", cycles[2].last().unwrap().series());
+        assert_eq!("artful", cycles[3].first().unwrap().series());
+    }
+}
@@ -0,0 +1,152 @@
+//! Transparent decompression of distro-info-data CSV files.
+//!
+//! Some space-constrained systems ship `ubuntu.csv.gz` (or `.zst`) rather than `ubuntu.csv`.
+//! Compressed files are detected by their magic bytes, so the extension does not matter once a
+//! file has been found; gzip support requires the `flate2` feature and zstd support the `zstd`
+//! feature.
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use failure::Error;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// The extensions of compressed variants of a file that `find` looks for
+pub const COMPRESSED_EXTENSIONS: [&str; 2] = ["gz", "zst"];
+
+/// Returns `path` if it exists, or else the first of its compressed variants which does
+///
+/// If none of them exist, `path` is returned (so that opening it reports a sensible error).
+pub fn find(path: &Path) -> PathBuf {
+    if path.exists() {
+        return path.to_path_buf();
+    }
+    COMPRESSED_EXTENSIONS
+        .iter()
+        .map(|extension| {
+            let mut compressed = path.as_os_str().to_os_string();
+            compressed.push(".");
+            compressed.push(extension);
+            PathBuf::from(compressed)
+        })
+        .find(|compressed| compressed.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Open `path`, decompressing its contents if it is compressed
+pub fn open(path: &Path) -> Result<Box<dyn Read>, Error> {
+    decompressing(BufReader::new(File::open(path)?), path)
+}
+
+/// Read `reader` (the contents of `path`), decompressing it if it is compressed
+pub fn decompressing<R: BufRead + 'static>(
+    mut reader: R,
+    path: &Path,
+) -> Result<Box<dyn Read>, Error> {
+    let magic = reader.fill_buf()?;
+    if magic.starts_with(GZIP_MAGIC) {
+        trace_event!(debug, path = %path.display(), "decompressing gzip data");
+        gzip(reader, path)
+    } else if magic.starts_with(ZSTD_MAGIC) {
+        trace_event!(debug, path = %path.display(), "decompressing zstd data");
+        zstd(reader, path)
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+#[cfg(feature = "flate2")]
+fn gzip<R: BufRead + 'static>(reader: R, _path: &Path) -> Result<Box<dyn Read>, Error> {
+    Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader)))
+}
+
+#[cfg(not(feature = "flate2"))]
+fn gzip<R: BufRead>(_reader: R, path: &Path) -> Result<Box<dyn Read>, Error> {
+    bail!(
+        "{} is gzip-compressed; rebuild with the flate2 feature to read it",
+        path.display()
+    )
+}
+
+#[cfg(feature = "zstd")]
+fn zstd<R: BufRead + 'static>(reader: R, _path: &Path) -> Result<Box<dyn Read>, Error> {
+    Ok(Box::new(zstd::stream::read::Decoder::with_buffer(reader)?))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd<R: BufRead>(_reader: R, path: &Path) -> Result<Box<dyn Read>, Error> {
+    bail!(
+        "{} is zstd-compressed; rebuild with the zstd feature to read it",
+        path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::io::Read;
+
+    use super::{find, open};
+    use crate::test_support;
+
+    #[test]
+    fn find_prefers_uncompressed() {
+        let csv_path = test_support::testdata_path("ubuntu.csv");
+        assert_eq!(csv_path, find(&csv_path));
+        let missing = test_support::testdata_path("missing.csv");
+        assert_eq!(missing, find(&missing));
+    }
+
+    #[test]
+    fn open_uncompressed() {
+        let mut contents = String::new();
+        open(&test_support::testdata_path("ubuntu.csv"))
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(test_support::UBUNTU_CSV, contents);
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn open_gzip() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let path = env::temp_dir().join(format!("distro-info-test-{}.csv.gz", std::process::id()));
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(test_support::UBUNTU_CSV.as_bytes())
+            .unwrap();
+        fs::write(&path, encoder.finish().unwrap()).unwrap();
+        let mut contents = String::new();
+        open(&path).unwrap().read_to_string(&mut contents).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(test_support::UBUNTU_CSV, contents);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn open_zstd() {
+        let path = env::temp_dir().join(format!("distro-info-test-{}.csv.zst", std::process::id()));
+        let compressed = zstd::encode_all(test_support::UBUNTU_CSV.as_bytes(), 0).unwrap();
+        fs::write(&path, compressed).unwrap();
+        let mut contents = String::new();
+        open(&path).unwrap().read_to_string(&mut contents).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(test_support::UBUNTU_CSV, contents);
+    }
+
+    #[cfg(not(feature = "flate2"))]
+    #[test]
+    fn open_gzip_without_feature() {
+        let path = env::temp_dir().join(format!("distro-info-test-{}.gz", std::process::id()));
+        fs::write(&path, [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+        let err = open(&path).err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(err.to_string().contains("flate2 feature"));
+    }
+}
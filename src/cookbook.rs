@@ -0,0 +1,95 @@
+//! One-line answers to the most common questions, for users who don't need the `DistroInfo`
+//! trait.
+//!
+//! Each function loads the system's data for the named distro (through the binary cache, with the
+//! `cache` feature) and answers for today (in UTC):
+//!
+//! ```no_run
+//! if !distro_info::is_supported("ubuntu", "focal")? {
+//!     println!("focal reached its end of life on {:?}", distro_info::eol_date("ubuntu", "focal")?);
+//! }
+//! # Ok::<(), failure::Error>(())
+//! ```
+//!
+//! See `examples/cookbook.rs` for more.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::naive::NaiveDate;
+use failure::Error;
+
+use crate::{
+    DebianDistroInfo, Distro, DistroDataSource, DistroInfo, DistroRelease, SupportTier,
+    UbuntuDistroInfo,
+};
+
+/// Today's date, in UTC
+pub fn today() -> NaiveDate {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Duration::days((seconds / 86400) as i64)
+}
+
+/// Load the data for the distro called `distro` (e.g. "ubuntu", in any case)
+fn load(distro: &str) -> Result<Box<dyn DistroInfo>, Error> {
+    let distro =
+        Distro::from_name(distro).ok_or_else(|| format_err!("unknown distro `{}'", distro))?;
+    #[cfg(feature = "cache")]
+    return Ok(match distro {
+        Distro::Ubuntu => Box::new(UbuntuDistroInfo::new_cached()?),
+        Distro::Debian => Box::new(DebianDistroInfo::new_cached()?),
+        Distro::Derivative(_) => distro.load()?,
+    });
+    #[cfg(not(feature = "cache"))]
+    return Ok(match distro {
+        Distro::Ubuntu => Box::new(UbuntuDistroInfo::new()?),
+        Distro::Debian => Box::new(DebianDistroInfo::new()?),
+        Distro::Derivative(_) => distro.load()?,
+    });
+}
+
+/// The release `series` (or codename, or version) of `distro_info`
+fn release<'a>(distro_info: &'a dyn DistroInfo, series: &str) -> Result<&'a DistroRelease, Error> {
+    distro_info
+        .lookup(series)
+        .ok_or_else(|| format_err!("unknown distribution series `{}'", series))
+}
+
+/// Whether `series` of `distro` is supported today, e.g. `is_supported("ubuntu", "focal")`
+pub fn is_supported(distro: &str, series: &str) -> Result<bool, Error> {
+    let distro_info = load(distro)?;
+    Ok(release(distro_info.as_ref(), series)?.supported_at(today()))
+}
+
+/// The end of standard support of `series` of `distro`, if it is known
+pub fn eol_date(distro: &str, series: &str) -> Result<Option<NaiveDate>, Error> {
+    let distro_info = load(distro)?;
+    Ok(release(distro_info.as_ref(), series)?.support_end(SupportTier::Standard))
+}
+
+/// The newest LTS release of `distro` which has been released, e.g. `current_lts("ubuntu")`
+pub fn current_lts(distro: &str) -> Result<Option<DistroRelease>, Error> {
+    let distro_info = load(distro)?;
+    let date = today();
+    Ok(distro_info
+        .lts(date)
+        .into_iter()
+        .rev()
+        .find(|distro_release| distro_release.released_at(date))
+        .cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_supported, today};
+
+    #[test]
+    fn unknown_distro() {
+        assert_eq!(
+            "unknown distro `gentoo'",
+            is_supported("gentoo", "focal").unwrap_err().to_string()
+        );
+        assert!(today() > crate::test_support::date("2024-01-01"));
+    }
+}
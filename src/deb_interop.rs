@@ -0,0 +1,123 @@
+//! Resolving the `Distribution:` of a `debian/changelog` entry to a release, so that crates
+//! which parse changelogs (or other deb822 data) can share one implementation.
+//!
+//! A distribution may be a series ("bookworm"), an alias the distro's policy knows ("stable",
+//! "unstable"), either of those with a pocket suffix ("bookworm-security", "jammy-updates"), or
+//! `UNRELEASED`.
+use chrono::naive::NaiveDate;
+
+use crate::matching::MatchMode;
+use crate::{DistroInfo, DistroRelease};
+
+/// The pockets (or suites) a distribution may name, longest first so that e.g.
+/// "-backports-sloppy" is matched before "-backports"
+pub const POCKETS: [&str; 6] = [
+    "backports-sloppy",
+    "proposed-updates",
+    "backports",
+    "security",
+    "updates",
+    "proposed",
+];
+
+/// The distribution of a `UNRELEASED` changelog entry
+pub const UNRELEASED: &str = "UNRELEASED";
+
+/// What a changelog `Distribution:` refers to
+#[derive(Clone, Copy, Debug)]
+pub enum ChangelogTarget<'a> {
+    /// `UNRELEASED`: the entry hasn't been uploaded yet
+    Unreleased,
+    /// A release, or one of its pockets (e.g. "security"; `None` for the release itself)
+    Release {
+        release: &'a DistroRelease,
+        pocket: Option<&'static str>,
+    },
+}
+
+/// Split a distribution into its base and pocket, e.g. "bookworm-security" into "bookworm" and
+/// "security"
+pub fn split_pocket(distribution: &str) -> (&str, Option<&'static str>) {
+    for pocket in POCKETS {
+        if let Some(base) = distribution
+            .strip_suffix(pocket)
+            .and_then(|rest| rest.strip_suffix('-'))
+        {
+            return (base, Some(pocket));
+        }
+    }
+    (distribution, None)
+}
+
+/// Resolve a changelog distribution to the release it targets at `date`
+///
+/// Aliases are resolved by the distro's `DistroPolicy`, so e.g. "stable" depends on `date`.
+/// Series are matched exactly, as they are by dpkg.  Returns `None` if the distribution is
+/// unknown.
+pub fn resolve_distribution<'a>(
+    distro_info: &'a dyn DistroInfo,
+    distribution: &str,
+    date: NaiveDate,
+) -> Option<ChangelogTarget<'a>> {
+    if distribution == UNRELEASED {
+        return Some(ChangelogTarget::Unreleased);
+    }
+    let (base, pocket) = split_pocket(distribution);
+    let release = distro_info
+        .find_series(base, MatchMode::Strict)
+        .or_else(|| {
+            distro_info
+                .distro()
+                .policy()
+                .resolve_alias(distro_info, base, date)
+        })?;
+    Some(ChangelogTarget::Release { release, pocket })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_distribution, split_pocket, ChangelogTarget};
+    use crate::test_support;
+
+    #[test]
+    fn pockets() {
+        assert_eq!(("bookworm", None), split_pocket("bookworm"));
+        assert_eq!(
+            ("bullseye", Some("backports-sloppy")),
+            split_pocket("bullseye-backports-sloppy")
+        );
+        assert_eq!(
+            ("stable", Some("proposed-updates")),
+            split_pocket("stable-proposed-updates")
+        );
+        assert_eq!(("jammy", Some("updates")), split_pocket("jammy-updates"));
+        assert_eq!(("updates", None), split_pocket("updates"));
+    }
+
+    #[test]
+    fn resolve() {
+        let debian_distro_info = test_support::debian();
+        let date = test_support::date("2024-01-01");
+        let resolve =
+            |distribution| match resolve_distribution(&debian_distro_info, distribution, date) {
+                Some(ChangelogTarget::Release { release, pocket }) => {
+                    Some((release.series().as_str(), pocket))
+                }
+                Some(ChangelogTarget::Unreleased) => Some(("UNRELEASED", None)),
+                None => None,
+            };
+        assert_eq!(
+            Some(("bookworm", Some("security"))),
+            resolve("bookworm-security")
+        );
+        assert_eq!(Some(("bookworm", None)), resolve("stable"));
+        assert_eq!(Some(("sid", None)), resolve("unstable"));
+        assert_eq!(
+            Some(("trixie", Some("proposed-updates"))),
+            resolve("testing-proposed-updates")
+        );
+        assert_eq!(Some(("UNRELEASED", None)), resolve("UNRELEASED"));
+        assert_eq!(None, resolve("Bookworm"));
+        assert_eq!(None, resolve("jammy"));
+    }
+}
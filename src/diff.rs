@@ -0,0 +1,173 @@
+//! Comparing two versions of a distro's release data, e.g. to review a distro-info-data update.
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::milestones::Milestone;
+use crate::{DistroInfo, DistroRelease};
+
+/// A field whose value differs between two versions of a release
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldChange {
+    /// The name of the field, as a CSV column name (e.g. "eol-server")
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// The changes to a release present in both versions of the data
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseChange {
+    pub series: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// The differences between two versions of a distro's release data, matching releases by series
+#[derive(Clone, Debug, Default)]
+pub struct DistroInfoDiff {
+    /// Releases only in the new data
+    pub added: Vec<DistroRelease>,
+    /// Releases only in the old data
+    pub removed: Vec<DistroRelease>,
+    /// Releases in both, whose fields differ
+    pub changed: Vec<ReleaseChange>,
+}
+
+/// The fields of `distro_release`, keyed by CSV column name
+fn fields(distro_release: &DistroRelease) -> BTreeMap<String, String> {
+    let mut fields = distro_release.extra_fields().clone();
+    if let Some(version) = distro_release.version() {
+        fields.insert("version".to_string(), version.clone());
+    }
+    fields.insert(
+        "codename".to_string(),
+        distro_release.codename().to_string(),
+    );
+    for milestone in Milestone::ALL.iter() {
+        if let Some(date) = milestone.date(distro_release) {
+            fields.insert(milestone.name().to_string(), date.to_string());
+        }
+    }
+    fields
+}
+
+impl DistroInfoDiff {
+    /// Compare `old` with `new`
+    ///
+    /// Added and changed releases are in the order of `new`, and removed ones in the order of
+    /// `old`.
+    pub fn between<A, B>(old: &A, new: &B) -> Self
+    where
+        A: DistroInfo + ?Sized,
+        B: DistroInfo + ?Sized,
+    {
+        let mut diff = Self::default();
+        for new_release in new.iter() {
+            let old_release = match old
+                .iter()
+                .find(|old_release| old_release.series() == new_release.series())
+            {
+                Some(old_release) => old_release,
+                None => {
+                    diff.added.push(new_release.clone());
+                    continue;
+                }
+            };
+            let (old_fields, new_fields) = (fields(old_release), fields(new_release));
+            let mut names: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+            names.sort();
+            names.dedup();
+            let changes: Vec<FieldChange> = names
+                .into_iter()
+                .filter(|name| old_fields.get(*name) != new_fields.get(*name))
+                .map(|name| FieldChange {
+                    field: name.clone(),
+                    old: old_fields.get(name).cloned(),
+                    new: new_fields.get(name).cloned(),
+                })
+                .collect();
+            if !changes.is_empty() {
+                diff.changed.push(ReleaseChange {
+                    series: new_release.series().clone(),
+                    changes,
+                });
+            }
+        }
+        diff.removed = old
+            .iter()
+            .filter(|old_release| {
+                !new.iter()
+                    .any(|new_release| new_release.series() == old_release.series())
+            })
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Whether the two versions of the data are the same
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for DistroInfoDiff {
+    /// One line per added (`+`) or removed (`-`) release, and per changed (`~`) field
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for distro_release in &self.added {
+            writeln!(f, "+ {}", distro_release.series())?;
+        }
+        for distro_release in &self.removed {
+            writeln!(f, "- {}", distro_release.series())?;
+        }
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "(none)".to_string());
+        for release_change in &self.changed {
+            for change in &release_change.changes {
+                writeln!(
+                    f,
+                    "~ {} {}: {} -> {}",
+                    release_change.series,
+                    change.field,
+                    value(&change.old),
+                    value(&change.new)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DistroInfoDiff;
+    use crate::{test_support, DistroDataSource, DistroInfo, UbuntuDistroInfo};
+
+    #[test]
+    fn between() {
+        let old = UbuntuDistroInfo::from_csv_str(
+            "version,codename,series,created,release,eol\n\
+             22.04 LTS,Jammy Jellyfish,jammy,2021-10-14,2022-04-21,2027-04-21\n\
+             22.10,Kinetic Kudu,kinetic,2022-04-21,2022-10-20,2023-07-20\n",
+        )
+        .unwrap();
+        let new = UbuntuDistroInfo::from_csv_str(
+            "version,codename,series,created,release,eol,eol-server\n\
+             22.04 LTS,Jammy Jellyfish,jammy,2021-10-14,2022-04-21,2027-06-01,2027-06-01\n\
+             23.04,Lunar Lobster,lunar,2022-10-20,2023-04-20,2024-01-25,\n",
+        )
+        .unwrap();
+        let diff = DistroInfoDiff::between(&old, &new);
+        assert_eq!(
+            "+ lunar\n\
+             - kinetic\n\
+             ~ jammy eol: 2027-04-21 -> 2027-06-01\n\
+             ~ jammy eol-server: (none) -> 2027-06-01\n",
+            diff.to_string()
+        );
+        let ubuntu_distro_info = test_support::ubuntu();
+        assert!(DistroInfoDiff::between(&ubuntu_distro_info, &ubuntu_distro_info).is_empty());
+        // Trait objects can be compared too
+        let (old, new): (&dyn DistroInfo, &dyn DistroInfo) = (&old, &new);
+        assert_eq!(1, DistroInfoDiff::between(old, new).added.len());
+    }
+}
@@ -0,0 +1,164 @@
+//! Finding distro-info-data's files on hosts other than Debian and Ubuntu, for cross-platform
+//! tooling which targets Debian and Ubuntu containers.
+//!
+//! `DistroDataSource::new` reads a distro's CSV file from its usual path (e.g.
+//! /usr/share/distro-info/ubuntu.csv) if it exists, and otherwise from the first of the
+//! [`data_search_paths`](fn.data_search_paths.html) which has a file of the same name.  A
+//! directory named by `$DISTRO_INFO_DATA_DIR` is searched before anything else.
+//!
+//! Newer data can be installed in the [`user_data_dir`](fn.user_data_dir.html) (as the tools'
+//! `update-data` command does), so that users of stable releases aren't stuck with their release's
+//! distro-info-data: whichever of the usual file and the user's copy was modified most recently
+//! is read.
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::compression;
+
+/// The environment variable naming a directory to search before any other
+pub const DATA_DIR_ENV: &str = "DISTRO_INFO_DATA_DIR";
+
+/// The directories searched for data files, in order, for this OS
+///
+/// These are `$DISTRO_INFO_DATA_DIR` (if set), then:
+///
+/// * on Windows, `%PROGRAMDATA%\distro-info` and `%LOCALAPPDATA%\distro-info`;
+/// * elsewhere, /usr/share/distro-info and /usr/local/share/distro-info, and on macOS, the
+///   Homebrew prefix's share/distro-info (`$HOMEBREW_PREFIX`, or /opt/homebrew).
+pub fn data_search_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let mut push = |path: PathBuf| {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    };
+    if let Some(dir) = env::var_os(DATA_DIR_ENV) {
+        push(PathBuf::from(dir));
+    }
+    if cfg!(windows) {
+        for variable in ["PROGRAMDATA", "LOCALAPPDATA"] {
+            if let Some(dir) = env::var_os(variable) {
+                push(PathBuf::from(dir).join("distro-info"));
+            }
+        }
+    } else {
+        push(PathBuf::from("/usr/share/distro-info"));
+        push(PathBuf::from("/usr/local/share/distro-info"));
+        if cfg!(target_os = "macos") {
+            let prefix = env::var_os("HOMEBREW_PREFIX")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("/opt/homebrew"));
+            push(prefix.join("share").join("distro-info"));
+        }
+    }
+    paths
+}
+
+/// The directory for the user's own copies of data files: `$XDG_DATA_HOME/distro-info` (by
+/// default, ~/.local/share/distro-info), if one can be determined
+pub fn user_data_dir() -> Option<PathBuf> {
+    match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")),
+    }
+    .map(|dir| dir.join("distro-info"))
+}
+
+/// The file to read for the data usually at `csv_path`, as `DistroDataSource::new` does
+///
+/// If no file is found, this is `csv_path` itself, so that errors name the usual path.
+pub fn locate(csv_path: &Path) -> PathBuf {
+    let overridden = env::var_os(DATA_DIR_ENV).map(PathBuf::from);
+    locate_in(
+        csv_path,
+        overridden.as_deref(),
+        user_data_dir().as_deref(),
+        &data_search_paths(),
+    )
+}
+
+/// When `path` was last modified, if it exists
+fn modified(path: &Path) -> Option<SystemTime> {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The (possibly compressed) file named like `csv_path` in `first`; or else whichever of
+/// `csv_path` itself and the file in `user_dir` was modified last; or else the file in the
+/// first of `dirs` which has one
+fn locate_in(
+    csv_path: &Path,
+    first: Option<&Path>,
+    user_dir: Option<&Path>,
+    dirs: &[PathBuf],
+) -> PathBuf {
+    let existing = |path: PathBuf| Some(compression::find(&path)).filter(|path| path.exists());
+    let file_name = match csv_path.file_name() {
+        Some(file_name) => file_name,
+        None => return csv_path.to_path_buf(),
+    };
+    let freshest = || {
+        let system = existing(csv_path.to_path_buf());
+        let user = user_dir.and_then(|dir| existing(dir.join(file_name)));
+        match (system, user) {
+            (Some(system), Some(user)) if modified(&user) > modified(&system) => Some(user),
+            (Some(system), _) => Some(system),
+            (None, user) => user,
+        }
+    };
+    first
+        .and_then(|dir| existing(dir.join(file_name)))
+        .or_else(freshest)
+        .or_else(|| dirs.iter().find_map(|dir| existing(dir.join(file_name))))
+        .unwrap_or_else(|| csv_path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use super::{data_search_paths, locate_in, modified};
+    use crate::test_support;
+
+    #[test]
+    fn locate_searches_dirs() {
+        let testdata = test_support::testdata_path("");
+        let missing = Path::new("/nonexistent/ubuntu.csv");
+        let dirs = [PathBuf::from("/nonexistent"), testdata.clone()];
+        assert_eq!(
+            testdata.join("ubuntu.csv"),
+            locate_in(missing, None, None, &dirs)
+        );
+        assert_eq!(missing, locate_in(missing, None, None, &dirs[..1]));
+        let csv_path = testdata.join("debian.csv");
+        assert_eq!(
+            csv_path,
+            locate_in(&csv_path, Some(Path::new("/nonexistent")), None, &[])
+        );
+        assert_eq!(
+            testdata.join("ubuntu.csv"),
+            locate_in(missing, Some(&testdata), None, &[])
+        );
+        #[cfg(unix)]
+        assert!(data_search_paths().contains(&PathBuf::from("/usr/share/distro-info")));
+    }
+
+    #[test]
+    fn locate_prefers_freshest_copy() {
+        let user_dir =
+            std::env::temp_dir().join(format!("distro-info-user-{}", std::process::id()));
+        fs::create_dir_all(&user_dir).unwrap();
+        let system = test_support::testdata_path("ubuntu.csv");
+        let user = user_dir.join("ubuntu.csv");
+        fs::write(&user, test_support::UBUNTU_CSV).unwrap();
+        assert!(modified(&user) > modified(&system));
+        assert_eq!(user, locate_in(&system, None, Some(&user_dir), &[]));
+        let missing = Path::new("/nonexistent/ubuntu.csv");
+        assert_eq!(user, locate_in(missing, None, Some(&user_dir), &[]));
+        fs::remove_dir_all(&user_dir).unwrap();
+        assert_eq!(system, locate_in(&system, None, Some(&user_dir), &[]));
+    }
+}
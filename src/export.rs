@@ -0,0 +1,297 @@
+//! Gantt-style datasets of releases' support windows, for rendering lifecycle charts.
+//!
+//! [`gantt`](fn.gantt.html) splits each release's lifetime into one bar per support tier, which
+//! [`GanttFormat`](enum.GanttFormat.html) renders as JSON, or as Mermaid or PlantUML source for
+//! documentation sites.
+//!
+//! [`to_ics`](fn.to_ics.html) produces an iCalendar feed of releases' release and end-of-life
+//! dates, for subscribing calendars to upcoming deadlines.
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use chrono::naive::NaiveDate;
+
+use crate::milestones::Milestone;
+use crate::{DistroRelease, SupportTier};
+
+/// The period a release spent (or will spend) in one tier of support
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GanttBar {
+    pub series: String,
+    pub tier: SupportTier,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// The latest of `dates`
+fn latest(dates: &[Option<NaiveDate>]) -> Option<NaiveDate> {
+    dates.iter().flatten().max().copied()
+}
+
+/// One bar per support tier of each release, in order
+///
+/// The standard bar runs from a release's release date to the end of its standard support (or
+/// server support, if later); each later tier starts where the previous one ended.  Releases
+/// without a release date or end of support are skipped, as are tiers which would not extend
+/// support.  `SupportTier::Core` overlaps the others, so has no bar.
+pub fn gantt<'a>(releases: impl IntoIterator<Item = &'a DistroRelease>) -> Vec<GanttBar> {
+    let mut bars = Vec::new();
+    for release in releases {
+        let (start, end) = match (release.release, latest(&[release.eol, release.eol_server])) {
+            (Some(start), Some(end)) => (start, end),
+            _ => continue,
+        };
+        let tiers = [
+            (SupportTier::Standard, Some(end)),
+            (SupportTier::Esm, release.eol_esm.or(release.eol_lts)),
+            (
+                SupportTier::Pro,
+                latest(&[release.eol_elts, release.eol_legacy]),
+            ),
+        ];
+        let mut start = start;
+        for (tier, end) in tiers {
+            match end {
+                Some(end) if end > start || tier == SupportTier::Standard => {
+                    bars.push(GanttBar {
+                        series: String::clone(&release.series),
+                        tier,
+                        start,
+                        end,
+                    });
+                    start = end;
+                }
+                _ => (),
+            }
+        }
+    }
+    bars
+}
+
+/// A text format for a Gantt dataset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GanttFormat {
+    /// A JSON array of `{"series", "tier", "start", "end"}` objects
+    Json,
+    /// A Mermaid `gantt` diagram, with a section per release
+    Mermaid,
+    /// A PlantUML `@startgantt` diagram
+    PlantUml,
+}
+
+impl GanttFormat {
+    pub const NAMES: [&'static str; 3] = ["json", "mermaid", "plantuml"];
+
+    /// The format called `name` (one of `NAMES`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(GanttFormat::Json),
+            "mermaid" => Some(GanttFormat::Mermaid),
+            "plantuml" => Some(GanttFormat::PlantUml),
+            _ => None,
+        }
+    }
+
+    /// Render `bars` in this format, titled `title` (where the format has titles)
+    pub fn render(&self, title: &str, bars: &[GanttBar]) -> String {
+        let mut text = String::new();
+        match self {
+            GanttFormat::Json => {
+                let objects: Vec<String> = bars
+                    .iter()
+                    .map(|bar| {
+                        format!(
+                            "{{\"series\":\"{}\",\"tier\":\"{}\",\"start\":\"{}\",\"end\":\"{}\"}}",
+                            json_escape(&bar.series),
+                            bar.tier.name(),
+                            bar.start,
+                            bar.end
+                        )
+                    })
+                    .collect();
+                let _ = writeln!(text, "[{}]", objects.join(","));
+            }
+            GanttFormat::Mermaid => {
+                let _ = writeln!(
+                    text,
+                    "gantt\n    title {}\n    dateFormat YYYY-MM-DD",
+                    title
+                );
+                let mut section = None;
+                for bar in bars {
+                    if section != Some(&bar.series) {
+                        let _ = writeln!(text, "    section {}", bar.series);
+                        section = Some(&bar.series);
+                    }
+                    let _ = writeln!(
+                        text,
+                        "    {} :{}-{}, {}, {}",
+                        bar.tier.name(),
+                        bar.series,
+                        bar.tier.name(),
+                        bar.start,
+                        bar.end
+                    );
+                }
+            }
+            GanttFormat::PlantUml => {
+                let _ = writeln!(text, "@startgantt\ntitle {}", title);
+                for bar in bars {
+                    let _ = writeln!(
+                        text,
+                        "[{} {}] starts {} and ends {}",
+                        bar.series,
+                        bar.tier.name(),
+                        bar.start,
+                        bar.end
+                    );
+                }
+                let _ = writeln!(text, "@endgantt");
+            }
+        }
+        text
+    }
+}
+
+/// What a milestone's calendar event is called, after the release's name
+fn event_summary(milestone: Milestone) -> &'static str {
+    match milestone {
+        Milestone::Created => "opens for development",
+        Milestone::Release => "release",
+        Milestone::Eol => "end of life",
+        Milestone::EolServer => "end of server support",
+        Milestone::EolLts => "end of LTS",
+        Milestone::EolElts => "end of ELTS",
+        Milestone::EolEsm => "end of ESM",
+        Milestone::EolLegacy => "end of Legacy support",
+    }
+}
+
+/// An iCalendar feed of the release and end-of-life dates of `releases` (of the distro called
+/// `distro`, e.g. "Ubuntu"), as all-day events stamped with `date`
+///
+/// Each event's UID is derived from the distro, series and milestone, so calendars update
+/// (rather than duplicate) events whose dates change in later data.
+pub fn to_ics<'a>(
+    distro: &str,
+    releases: impl IntoIterator<Item = &'a DistroRelease>,
+    date: NaiveDate,
+) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//distro-info//distro-info-rs//EN"),
+        format!("X-WR-CALNAME:{} releases", ics_escape(distro)),
+    ];
+    for release in releases {
+        for (milestone, milestone_date) in release.milestones() {
+            if milestone == Milestone::Created {
+                continue;
+            }
+            lines.extend([
+                String::from("BEGIN:VEVENT"),
+                format!(
+                    "UID:{}-{}@{}.distro-info",
+                    release.series,
+                    milestone.name(),
+                    distro.to_lowercase()
+                ),
+                format!("DTSTAMP:{}T000000Z", date.format("%Y%m%d")),
+                format!("DTSTART;VALUE=DATE:{}", milestone_date.format("%Y%m%d")),
+                format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (milestone_date + chrono::Duration::days(1)).format("%Y%m%d")
+                ),
+                format!(
+                    "SUMMARY:{} {} {}",
+                    ics_escape(distro),
+                    ics_escape(&release.series),
+                    event_summary(milestone)
+                ),
+                String::from("END:VEVENT"),
+            ]);
+        }
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    let mut ics = lines.join("\r\n");
+    ics.push_str("\r\n");
+    ics
+}
+
+/// Escape `text` for use as an iCalendar TEXT value
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Escape `text` for use in a JSON string
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gantt, to_ics, GanttFormat};
+    use crate::{test_support, DistroInfo, SupportTier};
+
+    #[test]
+    fn gantt_bars() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info.lookup("jammy").unwrap();
+        let bars = gantt(vec![jammy]);
+        assert_eq!(
+            vec![SupportTier::Standard, SupportTier::Esm, SupportTier::Pro],
+            bars.iter().map(|bar| bar.tier).collect::<Vec<_>>()
+        );
+        assert_eq!(&Some(bars[0].start), jammy.release());
+        assert_eq!(bars[0].end, bars[1].start);
+        assert_eq!(bars[1].end, bars[2].start);
+
+        let json = GanttFormat::Json.render("Ubuntu", &bars[..1]);
+        assert_eq!(
+            "[{\"series\":\"jammy\",\"tier\":\"standard\",\"start\":\"2022-04-21\",\"end\":\"2027-06-01\"}]\n",
+            json
+        );
+        let mermaid = GanttFormat::Mermaid.render("Ubuntu", &bars);
+        assert!(mermaid.starts_with("gantt\n    title Ubuntu\n"));
+        assert!(mermaid
+            .contains("    section jammy\n    standard :jammy-standard, 2022-04-21, 2027-06-01\n"));
+        let plantuml = GanttFormat::PlantUml.render("Ubuntu", &bars);
+        assert!(plantuml.contains("[jammy standard] starts 2022-04-21 and ends 2027-06-01\n"));
+        assert!(plantuml.ends_with("@endgantt\n"));
+        assert_eq!(None, GanttFormat::from_name("svg"));
+    }
+
+    #[test]
+    fn ics() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let ics = to_ics(
+            "Ubuntu",
+            ubuntu_distro_info.lookup("jammy"),
+            test_support::date("2024-01-01"),
+        );
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains(
+            "BEGIN:VEVENT\r\nUID:jammy-eol@ubuntu.distro-info\r\nDTSTAMP:20240101T000000Z\r\n\
+             DTSTART;VALUE=DATE:20270601\r\nDTEND;VALUE=DATE:20270602\r\n\
+             SUMMARY:Ubuntu jammy end of life\r\nEND:VEVENT\r\n"
+        ));
+        assert!(!ics.contains("opens for development"));
+        assert_eq!(5, ics.matches("BEGIN:VEVENT").count());
+    }
+}
@@ -0,0 +1,171 @@
+//! End-of-life dates for Ubuntu flavours (Kubuntu, Xubuntu, etc.).
+//!
+//! Flavours are supported for less time than Ubuntu itself: for example, flavour LTS releases
+//! have typically had three years of support rather than five.  distro-info-data doesn't record
+//! this, so flavour EOLs are read from an optional supplementary table, with one row per flavour
+//! release:
+//!
+//! ```text
+//! flavour,series,eol
+//! xubuntu,jammy,2025-04-30
+//! ```
+//!
+//! A flavour's EOL is stored in its release's `extra_fields` as `eol-<flavour>`, so a data file
+//! with (say) an `eol-xubuntu` column provides the same information.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use chrono::naive::NaiveDate;
+
+use crate::parser::{parse_date, ParseError};
+use crate::DistroRelease;
+
+/// The default location of the supplementary flavour table
+pub const FLAVOURS_CSV_PATH: &str = "/usr/share/distro-info/ubuntu-flavours.csv";
+
+/// The columns of the supplementary flavour table
+const FLAVOUR_COLUMNS: [&str; 3] = ["flavour", "series", "eol"];
+
+/// The end of life of one flavour's release of a series
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlavourEol {
+    pub flavour: String,
+    pub series: String,
+    pub eol: NaiveDate,
+}
+
+/// Parse the contents of a supplementary flavour table
+pub fn parse_flavour_eols(input: &str) -> Result<Vec<FlavourEol>, ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.is_empty());
+    let (_, header) = lines.next().ok_or(ParseError::MissingHeader)?;
+    let header: Vec<&str> = header.split(',').collect();
+    let position = |name: &'static str| {
+        header
+            .iter()
+            .position(|column| *column == name)
+            .ok_or(ParseError::MissingColumn(name))
+    };
+    let mut columns = [0; 3];
+    for (column, name) in columns.iter_mut().zip(FLAVOUR_COLUMNS.iter()) {
+        *column = position(name)?;
+    }
+    lines
+        .map(|(line, row)| {
+            let fields: Vec<&str> = row.split(',').collect();
+            let field = |i: usize| match fields.get(columns[i]) {
+                None | Some(&"") => Err(ParseError::MissingField {
+                    line,
+                    column: FLAVOUR_COLUMNS[i],
+                }),
+                Some(value) => Ok(*value),
+            };
+            let eol = field(2)?;
+            Ok(FlavourEol {
+                flavour: field(0)?.to_string(),
+                series: field(1)?.to_string(),
+                eol: parse_date(eol).ok_or_else(|| ParseError::InvalidDate {
+                    line,
+                    value: eol.to_string(),
+                })?,
+            })
+        })
+        .collect()
+}
+
+/// Read the supplementary flavour table at `path`
+///
+/// The table is optional, so a missing file is treated as an empty one.
+#[cfg(feature = "std")]
+pub fn load_flavour_eols<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<Vec<FlavourEol>, failure::Error> {
+    match std::fs::read_to_string(path) {
+        Ok(input) => Ok(parse_flavour_eols(&input)?),
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The `extra_fields` key holding the EOL of `flavour`
+fn eol_key(flavour: &str) -> String {
+    format!("eol-{}", flavour.to_lowercase())
+}
+
+impl DistroRelease {
+    /// Set the end of life of `flavour`'s release of this series
+    pub fn with_flavour_eol(mut self, flavour: &str, eol: NaiveDate) -> Self {
+        self.extra_fields
+            .insert(eol_key(flavour), eol.format("%Y-%m-%d").to_string());
+        self
+    }
+
+    /// The end of life of `flavour`'s release of this series, if it is known to differ from
+    /// Ubuntu's
+    pub fn explicit_flavour_eol(&self, flavour: &str) -> Option<NaiveDate> {
+        self.extra_fields
+            .get(&eol_key(flavour))
+            .and_then(|eol| parse_date(eol))
+    }
+
+    /// The end of life of `flavour`'s release of this series
+    ///
+    /// This is Ubuntu's `eol` unless the flavour's EOL is known to differ.
+    pub fn flavour_eol(&self, flavour: &str) -> Option<NaiveDate> {
+        self.explicit_flavour_eol(flavour).or(self.eol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_flavour_eols;
+    use crate::parser::ParseError;
+    use crate::{test_support, DistroDataSource, DistroInfo};
+
+    const FLAVOURS: &str = "flavour,series,eol\nxubuntu,jammy,2025-04-30\n";
+
+    #[test]
+    fn parse() {
+        let eols = parse_flavour_eols(FLAVOURS).unwrap();
+        assert_eq!(1, eols.len());
+        assert_eq!("xubuntu", eols[0].flavour);
+        assert_eq!(test_support::date("2025-04-30"), eols[0].eol);
+        assert_eq!(
+            Err(ParseError::MissingColumn("eol")),
+            parse_flavour_eols("flavour,series\n")
+        );
+        assert_eq!(
+            Err(ParseError::MissingField {
+                line: 2,
+                column: "eol"
+            }),
+            parse_flavour_eols("flavour,series,eol\nxubuntu,jammy,\n")
+        );
+    }
+
+    #[test]
+    fn for_flavour() {
+        let ubuntu_distro_info =
+            test_support::ubuntu().with_flavour_eols(&parse_flavour_eols(FLAVOURS).unwrap());
+        let jammy = ubuntu_distro_info.lookup("jammy").unwrap();
+        assert_eq!(
+            Some(test_support::date("2025-04-30")),
+            jammy.flavour_eol("Xubuntu")
+        );
+        assert_eq!(*jammy.eol(), jammy.flavour_eol("kubuntu"));
+
+        let date = test_support::date("2026-01-01");
+        let supported = |distro_info: &dyn DistroInfo| {
+            distro_info
+                .supported(date)
+                .iter()
+                .any(|distro_release| distro_release.series() == "jammy")
+        };
+        assert!(supported(&ubuntu_distro_info));
+        assert!(supported(&ubuntu_distro_info.for_flavour("kubuntu")));
+        assert!(!supported(&ubuntu_distro_info.for_flavour("xubuntu")));
+    }
+}
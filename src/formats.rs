@@ -0,0 +1,181 @@
+//! Reading release data from YAML and TOML, for tools which keep it in those formats.
+//!
+//! Both hold a list of releases, keyed by the column names of the CSV files:
+//!
+//! ```yaml
+//! - version: 22.04 LTS
+//!   codename: Jammy Jellyfish
+//!   series: jammy
+//!   created: 2021-10-14
+//!   release: 2022-04-21
+//!   eol: 2027-06-01
+//! ```
+//!
+//! ```toml
+//! [[release]]
+//! version = "22.04 LTS"
+//! codename = "Jammy Jellyfish"
+//! series = "jammy"
+//! created = 2021-10-14
+//! ```
+//!
+//! Values are validated as they are in CSV, and unknown keys are kept in `extra_fields`.  Line
+//! numbers in `ParseError`s for invalid values are the (1-indexed) position of the release in the
+//! list.
+use std::collections::BTreeSet;
+
+use crate::parser::{check_unique, Columns, ParseError};
+use crate::DistroRelease;
+
+/// Build releases from records of (key, value) pairs, one record per release
+pub(crate) fn releases_from_records<I>(records: I) -> Result<Vec<DistroRelease>, ParseError>
+where
+    I: IntoIterator<Item = Vec<(String, String)>>,
+{
+    let mut releases = vec![];
+    let mut seen = BTreeSet::new();
+    for (i, record) in records.into_iter().enumerate() {
+        let line = i + 1;
+        let columns = Columns::new(record.iter().map(|(key, _)| key.as_str()))?;
+        let distro_release =
+            columns.release(|i| record.get(i).map(|(_, value)| value.as_str()), line)?;
+        check_unique(&mut seen, &distro_release, line)?;
+        releases.push(distro_release);
+    }
+    Ok(releases)
+}
+
+/// Parse a YAML sequence of releases
+///
+/// Null values are treated as absent.
+#[cfg(feature = "yaml")]
+pub(crate) fn parse_yaml<R: std::io::Read>(reader: R) -> Result<Vec<DistroRelease>, ParseError> {
+    use std::collections::BTreeMap;
+
+    let records: Vec<BTreeMap<String, Option<String>>> =
+        serde_yaml::from_reader(reader).map_err(|e| ParseError::Malformed {
+            line: e.location().map_or(0, |location| location.line()),
+            message: e.to_string(),
+        })?;
+    releases_from_records(records.into_iter().map(|record| {
+        record
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value?)))
+            .collect()
+    }))
+}
+
+/// Parse a TOML array of tables named `release`
+///
+/// Values may be TOML dates, as well as strings.
+#[cfg(feature = "toml")]
+pub(crate) fn parse_toml(input: &str) -> Result<Vec<DistroRelease>, ParseError> {
+    let document: toml::Table =
+        input
+            .parse()
+            .map_err(|e: toml::de::Error| ParseError::Malformed {
+                line: e
+                    .span()
+                    .map_or(0, |span| input[..span.start].matches('\n').count() + 1),
+                message: e.message().to_string(),
+            })?;
+    let malformed = |message: &str| ParseError::Malformed {
+        line: 0,
+        message: message.to_string(),
+    };
+    let records = match document.get("release") {
+        Some(toml::Value::Array(records)) => records,
+        _ => return Err(malformed("expected an array of tables named 'release'")),
+    };
+    let mut tables = vec![];
+    for record in records {
+        match record {
+            toml::Value::Table(table) => tables.push(table),
+            _ => return Err(malformed("each release must be a table")),
+        }
+    }
+    releases_from_records(tables.into_iter().map(|table| {
+        table
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    toml::Value::String(value) => value.clone(),
+                    toml::Value::Datetime(datetime) => datetime.to_string(),
+                    value => value.to_string(),
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::ParseError;
+    use crate::{test_support, DistroDataSource, DistroInfo, UbuntuDistroInfo};
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn from_yaml_reader() {
+        let yaml = "- version: 22.10\n  codename: Kinetic Kudu\n  series: kinetic\n  \
+                    created: 2022-04-21\n  release: 2022-10-20\n  eol: 2023-07-20\n  \
+                    eol-server: ~\n  mirror: http://m/\n";
+        let ubuntu_distro_info = UbuntuDistroInfo::from_yaml_reader(yaml.as_bytes()).unwrap();
+        let kinetic = ubuntu_distro_info.iter().next().unwrap();
+        assert_eq!(&Some("22.10".to_string()), kinetic.version());
+        assert_eq!(&Some(test_support::date("2023-07-20")), kinetic.eol());
+        assert_eq!(&None, kinetic.eol_server());
+        assert_eq!(
+            Some("http://m/"),
+            kinetic.extra_fields().get("mirror").map(String::as_str)
+        );
+
+        let err = UbuntuDistroInfo::from_yaml_reader("- series: kinetic\n".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!(
+            Some(&ParseError::MissingColumn("version")),
+            err.downcast_ref::<ParseError>()
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn from_toml_str() {
+        let toml = "[[release]]\nversion = \"22.04 LTS\"\ncodename = \"Jammy Jellyfish\"\n\
+                    series = \"jammy\"\ncreated = 2021-10-14\nrelease = \"2022-04-21\"\n";
+        let ubuntu_distro_info = UbuntuDistroInfo::from_toml_str(toml).unwrap();
+        let jammy = ubuntu_distro_info.iter().next().unwrap();
+        assert_eq!(&Some(test_support::date("2021-10-14")), jammy.created());
+        assert_eq!(&Some(test_support::date("2022-04-21")), jammy.release());
+        assert_eq!(
+            Some(ParseError::InvalidDate {
+                line: 1,
+                value: "2021-10-14T00:00:00".to_string()
+            }),
+            UbuntuDistroInfo::from_toml_str(
+                "[[release]]\nversion = \"1\"\ncodename = \"A\"\nseries = \"a\"\n\
+                 created = 2021-10-14T00:00:00\n"
+            )
+            .err()
+        );
+    }
+
+    #[test]
+    fn duplicate_series_in_records() {
+        let record = || {
+            vec![
+                ("version".to_string(), "1".to_string()),
+                ("codename".to_string(), "A".to_string()),
+                ("series".to_string(), "a".to_string()),
+            ]
+        };
+        assert_eq!(
+            Some(ParseError::DuplicateSeries {
+                line: 2,
+                series: "a".to_string()
+            }),
+            super::releases_from_records(vec![record(), record()]).err()
+        );
+    }
+}
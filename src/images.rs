@@ -0,0 +1,93 @@
+//! References to the official container and cloud images of a release, for CI matrix generators
+//! which derive image names from selectors such as `supported`.
+//!
+//! The container tags are those of the Docker Official Images (`ubuntu:22.04`, `ubuntu:jammy`,
+//! `debian:12`, `debian:bookworm`), and the cloud image identifiers are the product name prefixes
+//! of Ubuntu's simplestreams metadata at cloud-images.ubuntu.com.  They are derived from the
+//! data, so tags for releases which have no image (such as long-unsupported ones) are generated
+//! too.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Distro, DistroRelease};
+
+/// The simplestreams product namespace of Ubuntu's released server cloud images
+const UBUNTU_CLOUD_PRODUCT: &str = "com.ubuntu.cloud:server";
+
+impl DistroRelease {
+    /// The version as used in image references: e.g. "22.04" for Ubuntu 22.04 LTS, and the major
+    /// version (e.g. "12") for Debian
+    fn image_version(&self, distro: &Distro) -> Option<String> {
+        match distro {
+            Distro::Ubuntu => self
+                .version()
+                .as_deref()
+                .map(|version| String::from(version.trim_end_matches(" LTS"))),
+            Distro::Debian => self
+                .parsed_version()
+                .map(|version| format!("{}", version.major)),
+            Distro::Derivative(_) => None,
+        }
+    }
+
+    /// The official container image tags of this release (a release of `distro`), by version
+    /// and then by series, e.g. `["ubuntu:22.04", "ubuntu:jammy"]`
+    ///
+    /// Releases without a version (e.g. Debian's sid) are tagged by series only, and derivatives'
+    /// releases have no official images.
+    pub fn docker_tags(&self, distro: &Distro) -> Vec<String> {
+        let image = match distro {
+            Distro::Ubuntu => "ubuntu",
+            Distro::Debian => "debian",
+            Distro::Derivative(_) => return Vec::new(),
+        };
+        self.image_version(distro)
+            .into_iter()
+            .chain(core::iter::once(String::clone(&self.series)))
+            .map(|tag| format!("{}:{}", image, tag))
+            .collect()
+    }
+
+    /// The simplestreams product name prefix of this release's cloud images, e.g.
+    /// "com.ubuntu.cloud:server:22.04", to which an architecture (e.g. ":amd64") is appended to
+    /// name a product
+    ///
+    /// This is `None` for distros whose images aren't published with simplestreams (Debian), and
+    /// for releases without a version.
+    pub fn cloud_image_stream(&self, distro: &Distro) -> Option<String> {
+        match distro {
+            Distro::Ubuntu => self
+                .image_version(distro)
+                .map(|version| format!("{}:{}", UBUNTU_CLOUD_PRODUCT, version)),
+            Distro::Debian | Distro::Derivative(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_support, Distro, DistroInfo};
+
+    #[test]
+    fn image_references() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info.lookup("jammy").unwrap();
+        assert_eq!(
+            vec!["ubuntu:22.04", "ubuntu:jammy"],
+            jammy.docker_tags(&Distro::Ubuntu)
+        );
+        assert_eq!(
+            Some("com.ubuntu.cloud:server:22.04".to_string()),
+            jammy.cloud_image_stream(&Distro::Ubuntu)
+        );
+        let debian_distro_info = test_support::debian();
+        let bookworm = debian_distro_info.lookup("bookworm").unwrap();
+        assert_eq!(
+            vec!["debian:12", "debian:bookworm"],
+            bookworm.docker_tags(&Distro::Debian)
+        );
+        assert_eq!(None, bookworm.cloud_image_stream(&Distro::Debian));
+        let sid = debian_distro_info.lookup("sid").unwrap();
+        assert_eq!(vec!["debian:sid"], sid.docker_tags(&Distro::Debian));
+    }
+}
@@ -0,0 +1,143 @@
+//! Indexes for looking releases up by series, codename or version without scanning.
+//!
+//! `UbuntuDistroInfo` and `DebianDistroInfo` build a [`ReleaseIndex`](struct.ReleaseIndex.html)
+//! the first time it is needed and keep it for their lifetime, so construction costs nothing extra
+//! and repeated lookups (e.g. in a web handler) are cheap.  The index is behind a `OnceLock`, so
+//! it is safe to share a `DistroInfo` between threads.
+//!
+//! Types defined with [`define_distro!`](../macro.define_distro.html) get the same index.
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+use crate::matching::{fold, MatchMode};
+use crate::DistroRelease;
+
+/// The position in the release list of the first release with each key, exactly and `fold`ed
+#[derive(Debug, Default)]
+struct Keys {
+    exact: BTreeMap<String, usize>,
+    folded: BTreeMap<String, usize>,
+}
+
+impl Keys {
+    fn insert(&mut self, key: &str, position: usize) {
+        self.exact.entry(key.to_string()).or_insert(position);
+        self.folded.entry(fold(key)).or_insert(position);
+    }
+
+    fn get(&self, needle: &str, mode: MatchMode) -> Option<usize> {
+        self.exact.get(needle).copied().or_else(|| match mode {
+            MatchMode::Strict => None,
+            MatchMode::Relaxed => self.folded.get(&fold(needle)).copied(),
+        })
+    }
+}
+
+/// Positions of releases in a release list, keyed by series, codename and version
+#[derive(Debug, Default)]
+pub struct ReleaseIndex {
+    series: Keys,
+    codename: Keys,
+    version: Keys,
+}
+
+impl ReleaseIndex {
+    /// Index `releases`
+    ///
+    /// Versions are indexed both as given and without any " LTS" suffix.
+    pub fn new(releases: &[DistroRelease]) -> Self {
+        let mut index = Self::default();
+        for (position, distro_release) in releases.iter().enumerate() {
+            index.series.insert(distro_release.series(), position);
+            index.codename.insert(distro_release.codename(), position);
+            if let Some(version) = distro_release.version() {
+                index.version.insert(version, position);
+                index
+                    .version
+                    .insert(version.trim_end_matches(" LTS"), position);
+            }
+        }
+        index
+    }
+
+    /// The position of the first release whose series matches `series`
+    pub fn series(&self, series: &str, mode: MatchMode) -> Option<usize> {
+        self.series.get(series, mode)
+    }
+
+    /// The position of the first release whose codename matches `codename`
+    pub fn codename(&self, codename: &str, mode: MatchMode) -> Option<usize> {
+        self.codename.get(codename, mode)
+    }
+
+    /// The position of the first release whose version (with or without " LTS") matches `version`
+    pub fn version(&self, version: &str, mode: MatchMode) -> Option<usize> {
+        self.version.get(version, mode)
+    }
+}
+
+/// A `ReleaseIndex` built on first use
+///
+/// Without the `std` feature there is no thread-safe lazy cell, so no index is kept and lookups
+/// scan the release list.
+#[derive(Debug, Default)]
+pub struct LazyIndex {
+    #[cfg(feature = "std")]
+    index: std::sync::OnceLock<ReleaseIndex>,
+}
+
+impl LazyIndex {
+    /// The index of `releases`, building it if this is the first call
+    ///
+    /// `releases` must be the same on every call.
+    #[cfg(feature = "std")]
+    pub fn get(&self, releases: &[DistroRelease]) -> Option<&ReleaseIndex> {
+        Some(self.index.get_or_init(|| ReleaseIndex::new(releases)))
+    }
+
+    /// The index of `releases`, building it if this is the first call
+    ///
+    /// `releases` must be the same on every call.
+    #[cfg(not(feature = "std"))]
+    pub fn get(&self, _releases: &[DistroRelease]) -> Option<&ReleaseIndex> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matching::MatchMode;
+    use crate::{test_support, DistroInfo};
+
+    #[test]
+    fn indexed_lookups_match_scans() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let index = ubuntu_distro_info.index().unwrap();
+        let releases = ubuntu_distro_info.releases();
+        assert_eq!(
+            "focal",
+            releases[index.series("Focal", MatchMode::Relaxed).unwrap()].series()
+        );
+        assert_eq!(None, index.series("Focal", MatchMode::Strict));
+        assert_eq!(
+            "jammy",
+            releases[index
+                .codename("Jammy Jellyfish", MatchMode::Strict)
+                .unwrap()]
+            .series()
+        );
+        assert_eq!(
+            "noble",
+            releases[index.version("24.04", MatchMode::Strict).unwrap()].series()
+        );
+        // Every series resolves to itself
+        for distro_release in releases {
+            assert_eq!(
+                Some(distro_release.series()),
+                ubuntu_distro_info
+                    .find_series(distro_release.series(), MatchMode::Strict)
+                    .map(|found| found.series())
+            );
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Sharing the strings of releases between copies of the data.
+//!
+//! A service which keeps many versions of the data (e.g. to diff historical snapshots) would
+//! otherwise hold a separate copy of every series and codename for each.  Passing each snapshot
+//! through `DistroDataSource::interned` with the same [`Interner`](struct.Interner.html) makes them
+//! share one allocation per distinct string; the getters are unchanged.
+//!
+//! Versions are not interned, as `DistroRelease::version` returns an `&Option<String>`.
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::sync::Arc;
+
+use crate::DistroRelease;
+
+/// A set of shared strings, from which equal strings are reused
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: BTreeSet<Arc<String>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The shared copy of `value`, which becomes the shared copy if there isn't one yet
+    pub fn intern(&mut self, value: &Arc<String>) -> Arc<String> {
+        match self.strings.get(value.as_ref()) {
+            Some(interned) => interned.clone(),
+            None => {
+                self.strings.insert(value.clone());
+                value.clone()
+            }
+        }
+    }
+
+    /// The number of distinct strings interned
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl DistroRelease {
+    /// This release, with its series and codename shared through `interner`
+    pub fn interned(mut self, interner: &mut Interner) -> Self {
+        self.series = interner.intern(&self.series);
+        self.codename = interner.intern(&self.codename);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use super::Interner;
+    use crate::{test_support, DistroDataSource, DistroInfo};
+
+    #[test]
+    fn snapshots_share_strings() {
+        let mut interner = Interner::new();
+        let old = test_support::ubuntu().interned(&mut interner);
+        let distinct = interner.len();
+        let new = test_support::ubuntu().interned(&mut interner);
+        assert_eq!(distinct, interner.len());
+        let (old_jammy, new_jammy) = (old.lookup("jammy").unwrap(), new.lookup("jammy").unwrap());
+        assert!(Arc::ptr_eq(&old_jammy.series, &new_jammy.series));
+        assert!(Arc::ptr_eq(&old_jammy.codename, &new_jammy.codename));
+        assert_eq!("Jammy Jellyfish", new_jammy.codename());
+    }
+}
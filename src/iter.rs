@@ -0,0 +1,55 @@
+//! Lazy iterators over the releases matching a date-based query.
+//!
+//! These back the `iter_*` methods of [`DistroQueries`](../trait.DistroQueries.html), which let
+//! callers chain further adapters without the intermediate `Vec` the non-`iter_` methods allocate.
+use core::slice;
+
+use chrono::naive::NaiveDate;
+
+use crate::DistroRelease;
+
+/// An iterator over the releases for which a predicate holds at a given date
+#[derive(Clone)]
+pub struct FilterByDate<'a> {
+    iter: slice::Iter<'a, DistroRelease>,
+    date: NaiveDate,
+    predicate: fn(&DistroRelease, NaiveDate) -> bool,
+}
+
+impl<'a> FilterByDate<'a> {
+    pub(crate) fn new(
+        iter: slice::Iter<'a, DistroRelease>,
+        date: NaiveDate,
+        predicate: fn(&DistroRelease, NaiveDate) -> bool,
+    ) -> Self {
+        Self {
+            iter,
+            date,
+            predicate,
+        }
+    }
+}
+
+impl<'a> Iterator for FilterByDate<'a> {
+    type Item = &'a DistroRelease;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (date, predicate) = (self.date, self.predicate);
+        self.iter
+            .by_ref()
+            .find(|distro_release| predicate(distro_release, date))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a> DoubleEndedIterator for FilterByDate<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (date, predicate) = (self.date, self.predicate);
+        self.iter
+            .by_ref()
+            .rfind(|distro_release| predicate(distro_release, date))
+    }
+}
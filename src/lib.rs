@@ -3,41 +3,348 @@
 //!
 //! Use [``UbuntuDistroInfo``](struct.UbuntuDistroInfo.html) to access the Ubuntu data.  (The
 //! Debian implementation has yet to happen.)
+//!
+//! Without the default `std` feature, the crate is `no_std` (but requires `alloc`): the data model,
+//! the query methods and the [`parser`](parser/index.html) module are available, but reading
+//! files from disk is not.
+//!
+//! With the `tracing` feature, parsing, fallbacks (to cached, compressed or inferred data),
+//! staleness checks and support decisions are reported as `tracing` spans and events.
+//!
+//! The `yaml` and `toml` features add constructors reading release data in those formats; see
+//! the [`formats`](formats/index.html) module.
+//!
+//! The `baked-data` feature adds the [`baked`](baked/index.html) module, with the series known
+//! at build time as `const`s.
+//!
+//! The `tokio` feature adds async constructors, which read the data without blocking the runtime;
+//! see the [`asynchronous`](asynchronous/index.html) module.
+//!
+//! The `minisign` feature adds `DistroDataSource::from_path_verified`, which checks a signature on
+//! the data before using it; see the [`verify`](verify/index.html) module.
+#![cfg_attr(not(feature = "std"), no_std)]
+#[macro_use]
+extern crate alloc;
 extern crate chrono;
+#[cfg(feature = "std")]
 extern crate csv;
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate failure;
+#[cfg(feature = "cache")]
+extern crate bincode;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "cache")]
+#[macro_use]
+extern crate serde;
 
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use chrono::naive::NaiveDate;
+use core::fmt;
+use core::ops::RangeInclusive;
+#[cfg(feature = "std")]
 use csv::ReaderBuilder;
+#[cfg(feature = "std")]
 use failure::Error;
 
+/// A CSV reader over `reader`, configured for distro-info-data's files
+#[cfg(feature = "std")]
+pub(crate) fn csv_reader<R: std::io::Read>(reader: R) -> csv::Reader<R> {
+    ReaderBuilder::new()
+        .flexible(true)
+        .has_headers(true)
+        .from_reader(reader)
+}
+
+/// Emit a `tracing` event, if the `tracing` feature is enabled
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
+/// Read the releases from `rdr` which `filter` keeps, adding any recoverable issues to `warnings`
+///
+/// With `options`, the rows are also validated as they configure; without, only duplicate series
+/// are rejected.
+#[cfg(feature = "std")]
+fn read_csv_records<T: std::io::Read>(
+    mut rdr: csv::Reader<T>,
+    filter: &parser::RowFilter,
+    mut warnings: Option<&mut Vec<parser::ParseWarning>>,
+    options: Option<&parser::LoadOptions>,
+) -> Result<Vec<DistroRelease>, Error> {
+    let malformed = |e: csv::Error| ParseError::Malformed {
+        line: e.position().map_or(0, |position| position.line() as usize),
+        message: match e.kind() {
+            csv::ErrorKind::Utf8 { .. } => "invalid UTF-8".to_string(),
+            csv::ErrorKind::UnequalLengths { .. } => "wrong number of fields".to_string(),
+            _ => e.to_string(),
+        },
+    };
+    let columns = parser::Columns::new(rdr.headers().map_err(malformed)?.iter())?;
+    if let Some(warnings) = warnings.as_deref_mut() {
+        warnings.extend(columns.header_warnings());
+    }
+    let mut record = csv::StringRecord::new();
+    let mut releases = Vec::with_capacity(64);
+    let mut seen = alloc::collections::BTreeSet::new();
+    while rdr.read_record(&mut record).map_err(malformed)? {
+        let line = record
+            .position()
+            .map_or(0, |position| position.line() as usize);
+        let distro_release = columns.release(|i| record.get(i), line)?;
+        if let Some(warnings) = warnings.as_deref_mut() {
+            warnings.extend(columns.row_warnings(|i| record.get(i), line));
+        }
+        if !filter.keeps(&distro_release) {
+            continue;
+        }
+        let options = match options {
+            Some(options) => options,
+            None => {
+                parser::check_unique(&mut seen, &distro_release, line)?;
+                releases.push(distro_release);
+                continue;
+            }
+        };
+        for (earlier, later) in parser::out_of_order(&distro_release) {
+            match (options.validate, warnings.as_deref_mut()) {
+                (parser::Strictness::Lenient, Some(warnings)) => {
+                    warnings.push(parser::ParseWarning::OutOfOrder {
+                        line,
+                        earlier,
+                        later,
+                    })
+                }
+                (parser::Strictness::Lenient, None) => {}
+                (parser::Strictness::Strict, _) => {
+                    return Err(ParseError::OutOfOrder {
+                        line,
+                        earlier,
+                        later,
+                    }
+                    .into())
+                }
+            }
+        }
+        match parser::check_unique(&mut seen, &distro_release, line) {
+            Ok(()) => releases.push(distro_release),
+            Err(ParseError::DuplicateSeries { line, series })
+                if options.validate == parser::Strictness::Lenient =>
+            {
+                if let Some(warnings) = warnings.as_deref_mut() {
+                    warnings.push(parser::ParseWarning::DuplicateSeries { line, series });
+                }
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    trace_event!(debug, releases = releases.len(), "parsed CSV");
+    Ok(releases)
+}
+
+pub mod aliases;
+#[cfg(feature = "std")]
+pub mod all;
+#[cfg(feature = "std")]
+pub mod apt;
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
+#[cfg(feature = "baked-data")]
+pub mod baked;
+pub mod batch;
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod cadence;
+pub mod codenames;
+#[cfg(feature = "std")]
+pub mod compression;
+#[cfg(feature = "std")]
+pub mod cookbook;
+pub mod deb_interop;
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod discovery;
+pub mod export;
+pub mod flavours;
+#[cfg(any(feature = "yaml", feature = "toml"))]
+pub mod formats;
+pub mod images;
+pub mod index;
+pub mod interning;
+pub mod iter;
+pub mod links;
+pub mod macros;
+pub mod matching;
+pub mod milestones;
+pub mod parser;
+pub mod phase;
+pub mod policy;
+pub mod prediction;
+pub mod query;
+pub mod record;
+#[cfg(feature = "std")]
+pub mod snapshot;
+#[cfg(feature = "std")]
+pub mod source;
+pub mod status;
+pub mod suites;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+#[cfg(feature = "chrono-tz")]
+pub mod tz;
+pub mod upload_targets;
+#[cfg(feature = "minisign")]
+pub mod verify;
+pub mod version;
+
+#[cfg(feature = "std")]
+pub use cookbook::{current_lts, eol_date, is_supported};
+use parser::ParseError;
+
 const UBUNTU_CSV_PATH: &str = "/usr/share/distro-info/ubuntu.csv";
 const DEBIAN_CSV_PATH: &str = "/usr/share/distro-info/debian.csv";
 
+const UBUNTU_ARCHIVE_URL: &str = "http://archive.ubuntu.com/ubuntu/";
+const UBUNTU_OLD_RELEASES_URL: &str = "http://old-releases.ubuntu.com/ubuntu/";
+const DEBIAN_ARCHIVE_URL: &str = "http://deb.debian.org/debian/";
+const DEBIAN_OLD_RELEASES_URL: &str = "http://archive.debian.org/debian/";
+
+#[derive(Clone, Copy)]
 pub enum Distro {
     Debian,
     Ubuntu,
+    /// A distro without built-in data, such as one defined with
+    /// [`define_distro!`](macro.define_distro.html), which follows `policy`
+    Derivative(&'static dyn policy::DistroPolicy),
 }
 
+impl fmt::Debug for Distro {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Distro::Debian => f.write_str("Debian"),
+            Distro::Ubuntu => f.write_str("Ubuntu"),
+            Distro::Derivative(policy) => f
+                .debug_tuple("Derivative")
+                .field(&policy.display_name())
+                .finish(),
+        }
+    }
+}
+
+/// Derivatives are equal if their policies have the same `display_name`
+impl PartialEq for Distro {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Distro::Derivative(policy), Distro::Derivative(other_policy)) => {
+                policy.display_name() == other_policy.display_name()
+            }
+            (Distro::Derivative(_), _) | (_, Distro::Derivative(_)) => false,
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Distro {}
+
 impl Distro {
+    /// All of the distros with built-in data
+    pub const ALL: [Distro; 2] = [Distro::Debian, Distro::Ubuntu];
+
+    /// The distro called `name` (e.g. "ubuntu"), compared case-insensitively
+    pub fn from_name(name: &str) -> Option<Distro> {
+        Distro::ALL
+            .iter()
+            .find(|distro| distro.display_name().eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// The human-readable name of the distro (the same as `display_name`)
     pub fn to_string(&self) -> &'static str {
+        self.display_name()
+    }
+
+    /// The human-readable name of the distro, e.g. "Ubuntu"
+    pub fn display_name(&self) -> &'static str {
         match self {
             Distro::Ubuntu => "Ubuntu",
             Distro::Debian => "Debian",
+            Distro::Derivative(policy) => policy.display_name(),
+        }
+    }
+
+    /// The `DistroPolicy` for the distro: the built-in one, or a derivative's own
+    pub fn policy(&self) -> &'static dyn policy::DistroPolicy {
+        match self {
+            Distro::Ubuntu => &policy::UbuntuPolicy,
+            Distro::Debian => &policy::DebianPolicy,
+            Distro::Derivative(policy) => *policy,
+        }
+    }
+
+    /// Wrap `releases` of this distro in the matching `DistroInfo`, as a trait object
+    ///
+    /// Together with `load`, this allows distros to be handled uniformly, e.g. in a
+    /// `Vec<Box<dyn DistroInfo>>`.
+    pub fn distro_info(&self, releases: Vec<DistroRelease>) -> alloc::boxed::Box<dyn DistroInfo> {
+        match self {
+            Distro::Ubuntu => alloc::boxed::Box::new(UbuntuDistroInfo::from_vec(releases)),
+            Distro::Debian => alloc::boxed::Box::new(DebianDistroInfo::from_vec(releases)),
+            Distro::Derivative(_) => alloc::boxed::Box::new(DerivativeDistroInfo {
+                distro: *self,
+                releases,
+                index: Default::default(),
+            }),
         }
     }
-}
 
-fn parse_date(field: String) -> Result<NaiveDate, Error> {
-    Ok(NaiveDate::parse_from_str(field.as_str(), "%Y-%m-%d")?)
+    /// Load the system's release data for this distro (as `DistroDataSource::new` does), as a trait
+    /// object
+    ///
+    /// A derivative's data is read from the file named after it in /usr/share/distro-info, e.g.
+    /// kali.csv for "Kali".
+    #[cfg(feature = "std")]
+    pub fn load(&self) -> Result<alloc::boxed::Box<dyn DistroInfo>, Error> {
+        Ok(match self {
+            Distro::Ubuntu => alloc::boxed::Box::new(UbuntuDistroInfo::new()?),
+            Distro::Debian => alloc::boxed::Box::new(DebianDistroInfo::new()?),
+            Distro::Derivative(policy) => {
+                let csv_path = format!(
+                    "/usr/share/distro-info/{}.csv",
+                    policy.display_name().to_lowercase()
+                );
+                let path = discovery::locate(std::path::Path::new(&csv_path));
+                let releases = read_csv_records(
+                    csv_reader(compression::open(&path)?),
+                    &parser::RowFilter::new(),
+                    None,
+                    None,
+                )?;
+                self.distro_info(releases)
+            }
+        })
+    }
 }
 
+/// A release of a distro, as described by a row of distro-info-data
+///
+/// Its fields are only available through accessors, and it may gain more as distro-info-data
+/// adds columns (as it did `eol-lts` and `eol-esm`), so it can't be built with a struct
+/// expression.  `new` takes the columns it always has; code which should keep compiling as
+/// columns are added should instead start from `named` and set dates with `with_field`.
 #[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "cache", derive(Serialize, Deserialize))]
 pub struct DistroRelease {
     version: Option<String>,
-    codename: String,
-    series: String,
+    // Shared with equal strings by an `interning::Interner`
+    codename: Arc<String>,
+    series: Arc<String>,
     created: Option<NaiveDate>,
     release: Option<NaiveDate>,
     eol: Option<NaiveDate>,
@@ -45,9 +352,111 @@ pub struct DistroRelease {
     eol_elts: Option<NaiveDate>,
     eol_esm: Option<NaiveDate>,
     eol_server: Option<NaiveDate>,
+    eol_legacy: Option<NaiveDate>,
+    release_class: Option<ReleaseClass>,
+    extra_fields: BTreeMap<String, String>,
+    inferred: bool,
+}
+
+/// The (signed) time from `date` until `milestone`
+fn time_until(milestone: Option<NaiveDate>, date: NaiveDate) -> Option<chrono::Duration> {
+    milestone.map(|milestone| milestone.signed_duration_since(date))
+}
+
+/// Whether a release is a long-term support release
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cache", derive(Serialize, Deserialize))]
+pub enum ReleaseClass {
+    Lts,
+    Interim,
+    Unknown,
+}
+
+/// Whether a distro supports upgrading directly from one release to another
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpgradeSupport {
+    /// The upgrade is supported, e.g. to the next release
+    Supported,
+    /// The upgrade skips a release which must be upgraded through
+    SkipsReleases,
+    /// The target release is not newer than the one being upgraded from
+    NotAnUpgrade,
+    /// Either release is not a regular release in the data (e.g. it is a rolling suite)
+    Unknown,
+}
+
+/// How long a release is supported for, by the most extended support it has
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SupportClass {
+    /// Only standard support, of an interim release (e.g. nine months, for Ubuntu)
+    Interim,
+    /// Standard support of an LTS release (e.g. five years, for Ubuntu)
+    Lts,
+    /// Ubuntu ESM or Debian LTS (about ten years from release, for Ubuntu)
+    Esm,
+    /// Debian ELTS or Ubuntu's Legacy add-on (over ten years from release)
+    Elts,
+}
+
+impl SupportClass {
+    /// The name of the class, e.g. "esm"
+    pub fn name(&self) -> &'static str {
+        match self {
+            SupportClass::Interim => "interim",
+            SupportClass::Lts => "lts",
+            SupportClass::Esm => "esm",
+            SupportClass::Elts => "elts",
+        }
+    }
+}
+
+/// A level of support a release may be covered by
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SupportTier {
+    /// Standard (free) security support of any flavour, until `eol` (or `eol_server`, if later)
+    Standard,
+    /// Standard support of the desktop flavour, until `eol`
+    Desktop,
+    /// Standard support of the server flavour, until `eol_server` (or `eol`, for releases with
+    /// no separate server support)
+    Server,
+    /// Expanded Security Maintenance (Ubuntu) or LTS (Debian), until `eol_esm` or `eol_lts`
+    Esm,
+    /// Ubuntu Pro, including the Legacy add-on (or Debian ELTS), until the last of any of the
+    /// release's support dates
+    Pro,
+    /// Ubuntu Core, which is based on LTS releases and supported from their release until
+    /// `eol_esm`
+    Core,
+}
+
+impl SupportTier {
+    /// The name of the tier, e.g. "esm"
+    pub fn name(&self) -> &'static str {
+        match self {
+            SupportTier::Standard => "standard",
+            SupportTier::Desktop => "desktop",
+            SupportTier::Server => "server",
+            SupportTier::Esm => "esm",
+            SupportTier::Pro => "pro",
+            SupportTier::Core => "core",
+        }
+    }
 }
 
 impl DistroRelease {
+    /// A release with the given names and no known dates, to which dates can be added with
+    /// `with_field`
+    ///
+    /// An empty `version` means the release has none (as with Debian's sid).
+    pub fn named(version: String, codename: String, series: String) -> Self {
+        Self::new(
+            version, codename, series, None, None, None, None, None, None, None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         version: String,
         codename: String,
@@ -66,8 +475,8 @@ impl DistroRelease {
             } else {
                 Some(version)
             },
-            codename,
-            series,
+            codename: Arc::new(codename),
+            series: Arc::new(series),
             created,
             release,
             eol,
@@ -75,6 +484,64 @@ impl DistroRelease {
             eol_elts,
             eol_esm,
             eol_server,
+            eol_legacy: None,
+            release_class: None,
+            extra_fields: BTreeMap::new(),
+            inferred: false,
+        }
+    }
+
+    /// Set the end of the Ubuntu Pro Legacy add-on for this release
+    pub fn with_eol_legacy(mut self, eol_legacy: Option<NaiveDate>) -> Self {
+        self.eol_legacy = eol_legacy;
+        self
+    }
+
+    /// Set the values of any columns not otherwise parsed into this release's fields
+    pub fn with_extra_fields(mut self, extra_fields: BTreeMap<String, String>) -> Self {
+        self.extra_fields = extra_fields;
+        self
+    }
+
+    /// Mark this release as having been inferred (e.g. from apt's lists) rather than read from
+    /// distro-info-data
+    pub fn with_inferred(mut self, inferred: bool) -> Self {
+        self.inferred = inferred;
+        self
+    }
+
+    /// Override the `ReleaseClass` of this release, rather than detecting it from the version
+    pub fn with_release_class(mut self, release_class: ReleaseClass) -> Self {
+        self.release_class = Some(release_class);
+        self
+    }
+
+    /// Overlay the fields of `overlay` on this release
+    ///
+    /// Each field that `overlay` has a value for replaces this release's value; fields it leaves
+    /// empty are kept.
+    pub fn merged_with(self, overlay: DistroRelease) -> Self {
+        let mut extra_fields = self.extra_fields;
+        extra_fields.extend(overlay.extra_fields);
+        Self {
+            version: overlay.version.or(self.version),
+            codename: if overlay.codename.is_empty() {
+                self.codename
+            } else {
+                overlay.codename
+            },
+            series: self.series,
+            created: overlay.created.or(self.created),
+            release: overlay.release.or(self.release),
+            eol: overlay.eol.or(self.eol),
+            eol_lts: overlay.eol_lts.or(self.eol_lts),
+            eol_elts: overlay.eol_elts.or(self.eol_elts),
+            eol_esm: overlay.eol_esm.or(self.eol_esm),
+            eol_server: overlay.eol_server.or(self.eol_server),
+            eol_legacy: overlay.eol_legacy.or(self.eol_legacy),
+            release_class: overlay.release_class.or(self.release_class),
+            extra_fields,
+            inferred: self.inferred || overlay.inferred,
         }
     }
 
@@ -82,9 +549,42 @@ impl DistroRelease {
     pub fn version(&self) -> &Option<String> {
         &self.version
     }
+    /// The human-readable name of the release, e.g. "Bookworm" or "Jammy Jellyfish"
+    ///
+    /// This is the `codename` column, or the series capitalised if that is empty.  Use `series`
+    /// for the lower-case name used in APT sources and changelogs.
+    pub fn display_codename(&self) -> String {
+        if !self.codename.is_empty() {
+            return String::clone(&self.codename);
+        }
+        let mut chars = self.series.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
     pub fn codename(&self) -> &String {
         &self.codename
     }
+    /// The adjective of an "Adjective Animal" codename, e.g. "Jammy" for "Jammy Jellyfish"
+    ///
+    /// `None` for single-word codenames, such as Debian's.
+    pub fn codename_adjective(&self) -> Option<&str> {
+        self.codename_parts().map(|(adjective, _)| adjective)
+    }
+    /// The animal of an "Adjective Animal" codename, e.g. "Jellyfish" for "Jammy Jellyfish"
+    ///
+    /// `None` for single-word codenames, such as Debian's.
+    pub fn codename_animal(&self) -> Option<&str> {
+        self.codename_parts().map(|(_, animal)| animal)
+    }
+    fn codename_parts(&self) -> Option<(&str, &str)> {
+        let mut words = self.codename.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (Some(adjective), Some(animal), None) => Some((adjective, animal)),
+            _ => None,
+        }
+    }
     pub fn series(&self) -> &String {
         &self.series
     }
@@ -94,6 +594,12 @@ impl DistroRelease {
     pub fn release(&self) -> &Option<NaiveDate> {
         &self.release
     }
+    /// The date the series' archive was opened for development (e.g. in Launchpad, for Ubuntu)
+    ///
+    /// This is the same as `created`; it is *not* the release date, which is `release`.
+    pub fn archive_opened(&self) -> &Option<NaiveDate> {
+        &self.created
+    }
     pub fn eol(&self) -> &Option<NaiveDate> {
         &self.eol
     }
@@ -106,6 +612,19 @@ impl DistroRelease {
     pub fn eol_elts(&self) -> &Option<NaiveDate> {
         &self.eol_elts
     }
+    pub fn eol_legacy(&self) -> &Option<NaiveDate> {
+        &self.eol_legacy
+    }
+    /// The values of columns this crate does not know about (e.g. those added by a derivative),
+    /// keyed on the column name
+    pub fn extra_fields(&self) -> &BTreeMap<String, String> {
+        &self.extra_fields
+    }
+    /// Whether this release's data was inferred (see the [`apt`](apt/index.html) module), and so
+    /// may be incomplete
+    pub fn inferred(&self) -> bool {
+        self.inferred
+    }
     pub fn eol_lts(&self) -> &Option<NaiveDate> {
         &self.eol_lts
     }
@@ -119,6 +638,52 @@ impl DistroRelease {
             .unwrap_or(false)
     }
 
+    /// Whether this is an LTS or interim release
+    ///
+    /// Unless overridden with `with_release_class`, this is detected from the version: versions
+    /// marked "LTS" are LTS releases, as are unmarked Ubuntu-style YY.04 versions with an even year
+    /// (as derivative data files sometimes omit the marker); other YY.MM versions are interim
+    /// releases.  Anything else (including all Debian versions) is `Unknown`.
+    pub fn release_class(&self) -> ReleaseClass {
+        if let Some(release_class) = self.release_class {
+            return release_class;
+        }
+        let version = match &self.version {
+            Some(version) => version,
+            None => return ReleaseClass::Unknown,
+        };
+        if version.contains("LTS") {
+            return ReleaseClass::Lts;
+        }
+        let mut parts = version.splitn(2, '.');
+        let year = parts.next().and_then(|year| year.parse::<u32>().ok());
+        let month = parts
+            .next()
+            .filter(|month| month.len() == 2)
+            .and_then(|month| month.parse::<u32>().ok())
+            .filter(|month| (1..=12).contains(month));
+        match (year, month) {
+            (Some(year), Some(4)) if year % 2 == 0 => ReleaseClass::Lts,
+            (Some(_), Some(_)) => ReleaseClass::Interim,
+            _ => ReleaseClass::Unknown,
+        }
+    }
+
+    /// Whether this is a rolling suite, which is created but never released (e.g. Debian's sid
+    /// and experimental)
+    pub fn is_rolling(&self) -> bool {
+        self.version.is_none() && self.release.is_none()
+    }
+
+    /// Whether this is a rolling suite which never reaches its end of life, and so is supported
+    /// from its creation onwards
+    pub fn is_permanent(&self) -> bool {
+        self.is_rolling() && self.support_end(SupportTier::Standard).is_none()
+    }
+
+    /// Whether the release had been created at `date`
+    ///
+    /// This is `false` if the creation date is unknown; see `created_state` to tell the two apart.
     pub fn created_at(&self, date: NaiveDate) -> bool {
         match self.created {
             Some(created) => date >= created,
@@ -126,6 +691,10 @@ impl DistroRelease {
         }
     }
 
+    /// Whether the release had been released at `date`
+    ///
+    /// This is `false` if the release date is unknown, or the release is a rolling suite; see
+    /// `released_state` to tell those apart.
     pub fn released_at(&self, date: NaiveDate) -> bool {
         match self.release {
             Some(release) => date >= release,
@@ -133,110 +702,352 @@ impl DistroRelease {
         }
     }
 
+    /// Whether this release is planned at `date`: it is in the data, but has not been released
+    /// (including if its release date is not yet known)
+    ///
+    /// Rolling suites are never released, so are never planned.
+    pub fn is_planned(&self, date: NaiveDate) -> bool {
+        !self.is_rolling() && !self.released_at(date)
+    }
+
+    /// Whether either flavour of this release had standard support at `date`, as the Python
+    /// tools' `--supported` reports
+    ///
+    /// Ubuntu's LTS releases until 10.04 (lucid) supported their server flavour for longer than
+    /// their desktop one, recorded as `eol_server`; later releases leave it unset.  This is
+    /// `true` until the later of `eol` and `eol_server`, so e.g. dapper counts as supported until
+    /// 2011-06-01 even though its desktop support ended in 2009.  Use `desktop_supported_at`,
+    /// `server_supported_at` or `supported_at_tier` for a particular flavour.
     pub fn supported_at(&self, date: NaiveDate) -> bool {
-        self.created_at(date)
+        let supported = self.created_at(date)
             && match self.eol {
                 Some(eol) => match self.eol_server {
-                    Some(eol_server) => date <= ::std::cmp::max(eol, eol_server),
+                    Some(eol_server) => date <= ::core::cmp::max(eol, eol_server),
                     None => date <= eol,
                 },
                 None => true,
-            }
+            };
+        trace_event!(
+            trace,
+            series = %self.series,
+            %date,
+            created = ?self.created,
+            eol = ?self.eol,
+            eol_server = ?self.eol_server,
+            supported,
+            "evaluated support"
+        );
+        supported
     }
-}
 
-pub trait DistroInfo: Sized {
-    fn distro(&self) -> &Distro;
-    fn releases(&self) -> &Vec<DistroRelease>;
-    fn from_vec(releases: Vec<DistroRelease>) -> Self;
-    /// The full path to the CSV file to read from for this distro
-    fn csv_path() -> &'static str;
-    /// Read records from the given CSV reader to create a Debian/UbuntuDistroInfo object
+    /// Whether the desktop flavour of this release had standard support at `date`, i.e. until
+    /// `eol`
+    pub fn desktop_supported_at(&self, date: NaiveDate) -> bool {
+        self.created_at(date) && self.eol.map(|eol| date <= eol).unwrap_or(true)
+    }
+
+    /// Whether the server flavour of this release had standard support at `date`
     ///
-    /// (These records must be in the format used in debian.csv/ubuntu.csv as provided by the
-    /// distro-info-data package in Debian/Ubuntu.)
-    fn from_csv_reader<T: std::io::Read>(mut rdr: csv::Reader<T>) -> Result<Self, Error> {
-        let columns = rdr.headers()?.clone();
-        let parse_required_str = |field: Option<String>| -> Result<String, Error> {
-            field.ok_or(format_err!("failed to read required option"))
-        };
-        let getfield = |r: &csv::StringRecord, n: &str| -> Option<String> {
-            columns
+    /// This is until `eol_server` where the release supported its server flavour separately
+    /// (Ubuntu's LTS releases until lucid), and otherwise until `eol`, as both flavours share
+    /// the same support period.
+    pub fn server_supported_at(&self, date: NaiveDate) -> bool {
+        self.created_at(date)
+            && self
+                .support_end(SupportTier::Server)
+                .map(|eol| date <= eol)
+                .unwrap_or(true)
+    }
+
+    /// The last day on which this release is supported, as used by `supported_at`
+    ///
+    /// This is the later of the `eol` and `eol_server` dates, or `None` if no EOL date is known.
+    #[deprecated(note = "use `support_end(SupportTier::Standard)`, or another tier")]
+    pub fn end_of_support(&self) -> Option<NaiveDate> {
+        self.support_end(SupportTier::Standard)
+    }
+
+    /// The last day of the given tier of support of this release, if known
+    ///
+    /// For `Standard`, this is the later of `eol` and `eol_server`, as used by `supported_at`.
+    /// `Core` only ends for LTS releases.
+    pub fn support_end(&self, tier: SupportTier) -> Option<NaiveDate> {
+        match tier {
+            SupportTier::Standard => match (self.eol, self.eol_server) {
+                (Some(eol), Some(eol_server)) => Some(::core::cmp::max(eol, eol_server)),
+                (eol, _) => eol,
+            },
+            SupportTier::Desktop => self.eol,
+            SupportTier::Server => self.eol_server.or(self.eol),
+            SupportTier::Esm => self.eol_esm.or(self.eol_lts),
+            SupportTier::Pro => [self.eol_esm, self.eol_lts, self.eol_elts, self.eol_legacy]
                 .iter()
-                .position(|header| header == n)
-                .and_then(|i| r.get(i))
-                .map(|s| s.to_string())
-        };
-        let mut releases = vec![];
-        for record in rdr.records() {
-            let record = record?;
-            releases.push(DistroRelease::new(
-                parse_required_str(getfield(&record, "version"))?,
-                parse_required_str(getfield(&record, "codename"))?,
-                parse_required_str(getfield(&record, "series"))?,
-                getfield(&record, "created").map(parse_date).transpose()?,
-                getfield(&record, "release").map(parse_date).transpose()?,
-                getfield(&record, "eol").map(parse_date).transpose()?,
-                getfield(&record, "eol-lts").map(parse_date).transpose()?,
-                getfield(&record, "eol-elts").map(parse_date).transpose()?,
-                getfield(&record, "eol-esm").map(parse_date).transpose()?,
-                getfield(&record, "eol-server")
-                    .map(parse_date)
-                    .transpose()?,
-            ))
+                .flatten()
+                .max()
+                .copied(),
+            SupportTier::Core if self.release_class() == ReleaseClass::Lts => self.eol_esm,
+            SupportTier::Core => None,
         }
-        Ok(Self::from_vec(releases))
     }
 
-    /// Open this distro's CSV file and parse the release data contained therein
-    fn new() -> Result<Self, Error> {
-        Self::from_csv_reader(
-            ReaderBuilder::new()
-                .flexible(true)
-                .has_headers(true)
-                .from_path(Self::csv_path())?,
-        )
+    /// How long the series' archive had been open for development at `date`
+    ///
+    /// This keeps counting after the release (so that, e.g., the age of a series can be compared
+    /// with others), and is `None` if the archive wasn't open at `date` or its opening date is
+    /// unknown.
+    pub fn since_devel_open(&self, date: NaiveDate) -> Option<chrono::Duration> {
+        let opened = self.created?;
+        if date < opened {
+            return None;
+        }
+        Some(date.signed_duration_since(opened))
+    }
+
+    /// The (inclusive) dates during which this release was in development
+    ///
+    /// This runs from its creation until the day before its release, and is `None` unless both of
+    /// those dates are known.
+    pub fn development_window(&self) -> Option<(NaiveDate, NaiveDate)> {
+        Some((self.created?, self.release?.pred_opt()?))
+    }
+
+    /// The (inclusive) dates during which this release was a supported stable release
+    ///
+    /// This runs from its release until the end of its `Standard` support, and is `None` unless
+    /// both of those dates are known.
+    pub fn supported_window(&self) -> Option<(NaiveDate, NaiveDate)> {
+        Some((self.release?, self.support_end(SupportTier::Standard)?))
+    }
+
+    /// The length of the release's standard support, from its release until its
+    /// `support_end(SupportTier::Standard)`
+    ///
+    /// This is `None` unless both of those dates are known.
+    pub fn support_duration(&self) -> Option<chrono::Duration> {
+        let (start, end) = self.supported_window()?;
+        Some(end.signed_duration_since(start))
+    }
+
+    /// The time from `date` until the release date
+    ///
+    /// Like the other `time_to_*` methods, this is negative if the date has passed, and `None` if
+    /// it is unknown.
+    pub fn time_to_release(&self, date: NaiveDate) -> Option<chrono::Duration> {
+        time_until(self.release, date)
+    }
+
+    /// The time from `date` until the (desktop) end of life
+    pub fn time_to_eol(&self, date: NaiveDate) -> Option<chrono::Duration> {
+        time_until(self.eol, date)
+    }
+
+    /// The time from `date` until the end of life for servers
+    pub fn time_to_eol_server(&self, date: NaiveDate) -> Option<chrono::Duration> {
+        time_until(self.eol_server, date)
+    }
+
+    /// The time from `date` until the end of Expanded Security Maintenance
+    pub fn time_to_eol_esm(&self, date: NaiveDate) -> Option<chrono::Duration> {
+        time_until(self.eol_esm, date)
+    }
+
+    /// The first day on which the release was no longer supported, i.e. the day after its
+    /// `support_end(SupportTier::Standard)`, if that is known
+    pub fn unsupported_since(&self) -> Option<NaiveDate> {
+        self.support_end(SupportTier::Standard)?.succ_opt()
+    }
+
+    /// How long the release had been unsupported at `date`, from `unsupported_since`
+    ///
+    /// This is `None` if the release was still supported at `date` (or its end of support is
+    /// unknown), and zero on its first unsupported day.
+    pub fn eol_age(&self, date: NaiveDate) -> Option<chrono::Duration> {
+        let since = self.unsupported_since()?;
+        if date < since {
+            return None;
+        }
+        Some(date.signed_duration_since(since))
+    }
+
+    /// The most extended support the release has, for reporting
+    ///
+    /// Releases with ELTS (or Legacy) or ESM (or Debian LTS) dates are `Elts` and `Esm`
+    /// respectively; otherwise, this is `Lts` for LTS releases and `Interim` for others.  It is
+    /// `None` for releases which aren't released (or never will be, like sid).
+    pub fn support_class(&self) -> Option<SupportClass> {
+        self.release?;
+        Some(if self.eol_elts.is_some() || self.eol_legacy.is_some() {
+            SupportClass::Elts
+        } else if self.eol_esm.is_some() || self.eol_lts.is_some() {
+            SupportClass::Esm
+        } else if self.release_class() == ReleaseClass::Lts {
+            SupportClass::Lts
+        } else {
+            SupportClass::Interim
+        })
+    }
+
+    /// The (inclusive) dates during which both this release and `other` were supported stable
+    /// releases, i.e. the intersection of their `supported_window`s
+    ///
+    /// This is `None` if the windows don't intersect, or either is unknown.
+    pub fn support_overlap(&self, other: &DistroRelease) -> Option<(NaiveDate, NaiveDate)> {
+        let (start, end) = self.supported_window()?;
+        let (other_start, other_end) = other.supported_window()?;
+        let start = ::core::cmp::max(start, other_start);
+        let end = ::core::cmp::min(end, other_end);
+        if start <= end {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+
+    /// Whether this release is covered by the given tier of support at the given date
+    ///
+    /// Every tier covers at least the standard support period, except `Desktop` and `Server`,
+    /// which only cover their own flavour, and `Core`, which only exists for LTS releases (and
+    /// only once they are released).
+    pub fn supported_at_tier(&self, date: NaiveDate, tier: SupportTier) -> bool {
+        let end = self.support_end(tier);
+        match tier {
+            SupportTier::Standard => return self.supported_at(date),
+            SupportTier::Desktop => return self.desktop_supported_at(date),
+            SupportTier::Server => return self.server_supported_at(date),
+            SupportTier::Core => {
+                return self.released_at(date) && end.map(|end| date <= end).unwrap_or(false)
+            }
+            SupportTier::Esm | SupportTier::Pro => {}
+        }
+        self.supported_at(date)
+            || (self.created_at(date) && end.map(|end| date <= end).unwrap_or(false))
+    }
+
+    /// Returns the base URL of the package archive serving this release at the given date
+    ///
+    /// Once a release has reached the end of all of its support (including ESM for Ubuntu and LTS
+    /// for Debian), it is moved off the main mirrors to old-releases.ubuntu.com or
+    /// archive.debian.org respectively.  `None` for derivatives, whose archives aren't known.
+    pub fn archive_url(&self, distro: &Distro, date: NaiveDate) -> Option<&'static str> {
+        Some(match distro {
+            Distro::Ubuntu => {
+                let end_of_support = [self.eol, self.eol_server, self.eol_esm]
+                    .iter()
+                    .flatten()
+                    .max()
+                    .copied();
+                match end_of_support {
+                    Some(end_of_support) if date > end_of_support => UBUNTU_OLD_RELEASES_URL,
+                    _ => UBUNTU_ARCHIVE_URL,
+                }
+            }
+            Distro::Debian => match self.eol_lts.or(self.eol) {
+                Some(end_of_support) if date > end_of_support => DEBIAN_OLD_RELEASES_URL,
+                _ => DEBIAN_ARCHIVE_URL,
+            },
+            Distro::Derivative(_) => return None,
+        })
+    }
+
+    /// Returns links to the announcement, release notes and upgrade notes for this release
+    pub fn links(&self, distro: &Distro) -> links::ReleaseLinks {
+        links::links(distro, self)
+    }
+}
+
+/// Access to the release data for a distro
+///
+/// This is object-safe, so that it can be used through a `&dyn DistroQueries` or
+/// `Box<dyn DistroQueries>` (see `Distro::distro_info` and `Distro::load`); the constructors are
+/// in [`DistroDataSource`](trait.DistroDataSource.html).
+pub trait DistroQueries {
+    fn distro(&self) -> &Distro;
+    /// All of the releases, in the order of the data (oldest first)
+    fn releases(&self) -> &[DistroRelease];
+    /// An index of `releases`, if the implementation keeps one, used to speed up lookups
+    ///
+    /// The built-in implementations build theirs on first use (with the `std` feature).
+    fn index(&self) -> Option<&index::ReleaseIndex> {
+        None
     }
 
     /// Returns a vector of `DistroRelease`s for releases that had been created at the given date
     fn all_at(&self, date: NaiveDate) -> Vec<&DistroRelease> {
-        self.releases()
-            .iter()
-            .filter(|distro_release| match distro_release.created {
-                Some(created) => date >= created,
-                None => false,
-            })
-            .collect()
+        self.iter_all_at(date).collect()
     }
 
     /// Returns a vector of `DistroRelease`s for releases that were released at the given date
     fn released(&self, date: NaiveDate) -> Vec<&DistroRelease> {
-        self.releases()
-            .iter()
-            .filter(|distro_release| distro_release.released_at(date))
-            .collect()
+        self.iter_released(date).collect()
     }
 
-    /// Returns a vector of `DistroRelease`s for releases that were released and supported at the
-    /// given date
+    /// Returns a vector of `DistroRelease`s for releases that had been created and were supported
+    /// at the given date
+    ///
+    /// As in the C distro-info, this includes releases still in development, and permanent
+    /// suites such as Debian's sid and experimental once they have been created.
     fn supported(&self, date: NaiveDate) -> Vec<&DistroRelease> {
-        self.releases()
-            .iter()
-            .filter(|distro_release| distro_release.supported_at(date))
-            .collect()
+        self.iter_supported(date).collect()
     }
 
     /// Returns a vector of `DistroRelease`s for releases that were released but no longer
     /// supported at the given date
+    ///
+    /// Rolling suites are never released, so are never unsupported.
     fn unsupported(&self, date: NaiveDate) -> Vec<&DistroRelease> {
-        self.released(date)
-            .into_iter()
-            .filter(|distro_release| !distro_release.supported_at(date))
+        self.iter_unsupported(date).collect()
+    }
+
+    /// Like `unsupported`, but with those which have been unsupported the longest (by `eol_age`)
+    /// first, e.g. to put the most dangerously old systems at the top of an audit report
+    fn unsupported_sorted_by_age(&self, date: NaiveDate) -> Vec<&DistroRelease> {
+        let mut unsupported = self.unsupported(date);
+        unsupported
+            .sort_by_key(|distro_release| ::core::cmp::Reverse(distro_release.eol_age(date)));
+        unsupported
+    }
+
+    /// Returns a vector of `DistroRelease`s for releases which were planned at the given date,
+    /// i.e. not yet released; see `DistroRelease::is_planned`
+    fn planned(&self, date: NaiveDate) -> Vec<&DistroRelease> {
+        iter::FilterByDate::new(self.iter(), date, DistroRelease::is_planned).collect()
+    }
+
+    /// Returns a vector of `DistroRelease`s for releases which had been created at `date` and
+    /// will still be supported at the later date `until`, e.g. to find the series that will
+    /// still be alive at a planning horizon
+    fn supported_at_future(&self, date: NaiveDate, until: NaiveDate) -> Vec<&DistroRelease> {
+        self.iter_all_at(date)
+            .filter(|distro_release| distro_release.supported_at(until))
             .collect()
     }
 
+    /// Like `all_at`, but returns a lazy iterator rather than a vector
+    fn iter_all_at(&self, date: NaiveDate) -> iter::FilterByDate<'_> {
+        iter::FilterByDate::new(self.iter(), date, DistroRelease::created_at)
+    }
+
+    /// Like `released`, but returns a lazy iterator rather than a vector
+    fn iter_released(&self, date: NaiveDate) -> iter::FilterByDate<'_> {
+        iter::FilterByDate::new(self.iter(), date, DistroRelease::released_at)
+    }
+
+    /// Like `supported`, but returns a lazy iterator rather than a vector
+    fn iter_supported(&self, date: NaiveDate) -> iter::FilterByDate<'_> {
+        iter::FilterByDate::new(self.iter(), date, DistroRelease::supported_at)
+    }
+
+    /// Like `unsupported`, but returns a lazy iterator rather than a vector
+    fn iter_unsupported(&self, date: NaiveDate) -> iter::FilterByDate<'_> {
+        iter::FilterByDate::new(self.iter(), date, |distro_release, date| {
+            distro_release.released_at(date) && !distro_release.supported_at(date)
+        })
+    }
+
     /// Returns a vector of `DistroRelease`s for releases that were in development at the given
     /// date
+    ///
+    /// Only releases with a release date are considered, so rolling suites are excluded.
     fn ubuntu_devel(&self, date: NaiveDate) -> Vec<&DistroRelease> {
         self.all_at(date)
             .into_iter()
@@ -247,8 +1058,10 @@ pub trait DistroInfo: Sized {
             .collect()
     }
 
-    /// Returns a vector of `DistroRelease`s for releases that were in development at the given
-    /// date
+    /// Returns the rolling suite into which development is uploaded (i.e. Debian's sid), if it
+    /// had been created at the given date
+    ///
+    /// This is the first rolling suite, as the C distro-info selects.
     fn debian_devel(&self, date: NaiveDate) -> Vec<&DistroRelease> {
         self.all_at(date)
             .into_iter()
@@ -256,84 +1069,807 @@ pub trait DistroInfo: Sized {
                 Some(release) => date < release,
                 None => true,
             })
-            .filter(|distro_release| distro_release.version.is_none())
+            .filter(|distro_release| distro_release.is_rolling())
             .collect::<Vec<_>>()
             .first()
             .copied()
             .map(|dr| vec![dr])
-            .unwrap_or_else(std::vec::Vec::new)
+            .unwrap_or_else(Vec::new)
     }
 
-    /// Returns a `DistroRelease` for the latest supported, non-EOL release at the given date
-    fn latest(&self, date: NaiveDate) -> Option<&DistroRelease> {
-        self.supported(date)
-            .into_iter()
+    /// Returns the current stable release at the given date: of the supported releases which had
+    /// been released by then, the one released most recently
+    ///
+    /// Releases still in development at the date (even on the day before their release) are never
+    /// stable, though they may be within their supported window; a release becomes stable on its
+    /// release day.
+    fn stable(&self, date: NaiveDate) -> Option<&DistroRelease> {
+        self.iter_supported(date)
             .filter(|distro_release| distro_release.released_at(date))
-            .collect::<Vec<_>>()
-            .last()
-            .copied()
+            .max_by_key(|distro_release| distro_release.release)
     }
 
-    fn iter(&self) -> ::std::slice::Iter<DistroRelease> {
-        self.releases().iter()
+    /// Returns a `DistroRelease` for the latest supported, non-EOL release at the given date; the
+    /// same as `stable`
+    fn latest(&self, date: NaiveDate) -> Option<&DistroRelease> {
+        self.stable(date)
     }
-}
 
-pub struct UbuntuDistroInfo {
-    releases: Vec<DistroRelease>,
-}
-
-impl DistroInfo for UbuntuDistroInfo {
-    fn distro(&self) -> &Distro {
-        &Distro::Ubuntu
+    /// Returns a vector of `DistroRelease`s for LTS releases that existed at the given date
+    fn lts(&self, date: NaiveDate) -> Vec<&DistroRelease> {
+        let policy = self.distro().policy();
+        self.all_at(date)
+            .into_iter()
+            .filter(|distro_release| policy.is_lts(distro_release))
+            .collect()
     }
-    fn releases(&self) -> &Vec<DistroRelease> {
-        &self.releases
+
+    /// Returns the newest LTS release that existed at the given date (which may still be in
+    /// development)
+    fn latest_lts(&self, date: NaiveDate) -> Option<&DistroRelease> {
+        self.lts(date).last().copied()
+    }
+
+    /// Returns the LTS release before `latest_lts` at the given date, e.g. for workflows which
+    /// target both the current and the previous LTS (the equivalent of Debian's oldstable)
+    fn previous_lts(&self, date: NaiveDate) -> Option<&DistroRelease> {
+        self.lts(date).iter().rev().nth(1).copied()
+    }
+
+    /// The milestones recorded in this distro's data, as given by its policy
+    fn supported_milestones(&self) -> &'static [milestones::Milestone] {
+        self.distro().policy().supported_milestones()
+    }
+
+    /// Whether the data is probably out of date at the given date
+    ///
+    /// This is the case if no series has been created within the distro policy's
+    /// `max_series_gap` (e.g. about seven months, for Ubuntu) before the date.
+    fn is_probably_stale(&self, date: NaiveDate) -> bool {
+        let max_series_gap = match self.distro().policy().max_series_gap() {
+            Some(max_series_gap) => max_series_gap,
+            None => return false,
+        };
+        match self
+            .iter()
+            .filter_map(|distro_release| distro_release.created)
+            .max()
+        {
+            Some(newest) => {
+                let stale =
+                    date.signed_duration_since(newest).num_days() > i64::from(max_series_gap);
+                if stale {
+                    trace_event!(
+                        warn,
+                        %newest,
+                        %date,
+                        max_series_gap,
+                        "release data is probably stale"
+                    );
+                }
+                stale
+            }
+            None => {
+                trace_event!(
+                    warn,
+                    "no release has a creation date; data is probably stale"
+                );
+                true
+            }
+        }
+    }
+
+    fn iter(&self) -> ::core::slice::Iter<'_, DistroRelease> {
+        self.releases().iter()
+    }
+
+    /// The number of releases
+    fn len(&self) -> usize {
+        self.releases().len()
+    }
+
+    /// Whether there are no releases at all
+    fn is_empty(&self) -> bool {
+        self.releases().is_empty()
+    }
+
+    /// The release at `index` in `releases`, if there is one
+    fn get(&self, index: usize) -> Option<&DistroRelease> {
+        self.releases().get(index)
+    }
+
+    /// Start a [`Query`](query/struct.Query.html) over all releases, to chain filters onto
+    fn query(&self) -> query::Query<'_> {
+        query::Query::new(*self.distro(), self.iter())
+    }
+
+    /// Returns the `DistroRelease` whose release date is nearest to the given date
+    ///
+    /// This may be a release after the given date; if two releases are equally near, the earlier
+    /// one is returned.
+    fn nearest_release(&self, date: NaiveDate) -> Option<&DistroRelease> {
+        self.iter()
+            .filter_map(|distro_release| {
+                distro_release.release.map(|release| {
+                    let distance = release.signed_duration_since(date).num_days().abs();
+                    (distance, release, distro_release)
+                })
+            })
+            .min_by_key(|(distance, release, _)| (*distance, *release))
+            .map(|(_, _, distro_release)| distro_release)
+    }
+
+    /// Returns a vector of `DistroRelease`s for releases that were supported at any point in the
+    /// given (inclusive) date range
+    fn release_active_during(&self, range: RangeInclusive<NaiveDate>) -> Vec<&DistroRelease> {
+        self.iter()
+            .filter(|distro_release| {
+                distro_release.created_at(*range.end())
+                    && match distro_release.support_end(SupportTier::Standard) {
+                        Some(end_of_support) => end_of_support >= *range.start(),
+                        None => true,
+                    }
+            })
+            .collect()
+    }
+
+    /// Returns the pairs of releases (older first) which were both supported at some point in the
+    /// given (inclusive) date range, according to their `support_overlap`
+    fn concurrently_supported_pairs(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+    ) -> Vec<(&DistroRelease, &DistroRelease)> {
+        let releases = self.releases();
+        let mut pairs = vec![];
+        for (position, older) in releases.iter().enumerate() {
+            for newer in &releases[position + 1..] {
+                if let Some((start, end)) = older.support_overlap(newer) {
+                    if start <= *range.end() && end >= *range.start() {
+                        pairs.push((older, newer));
+                    }
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Returns a vector of `DistroRelease`s for releases whose development window contains the
+    /// given date
+    ///
+    /// Unlike `ubuntu_devel` and `debian_devel`, this only considers releases with known creation
+    /// and release dates.
+    fn in_development_at(&self, date: NaiveDate) -> Vec<&DistroRelease> {
+        self.in_development_during(date..=date)
+    }
+
+    /// Returns a vector of `DistroRelease`s for releases whose development window overlaps the
+    /// given (inclusive) date range
+    fn in_development_during(&self, range: RangeInclusive<NaiveDate>) -> Vec<&DistroRelease> {
+        self.iter()
+            .filter(|distro_release| match distro_release.development_window() {
+                Some((start, end)) => start <= *range.end() && end >= *range.start(),
+                None => false,
+            })
+            .collect()
+    }
+
+    /// Returns the milestones of all releases falling between `date` and `horizon` days after it
+    /// (inclusive), in chronological order
+    ///
+    /// Milestones on the same day are ordered by release, then in lifecycle order.
+    fn upcoming_milestones(
+        &self,
+        date: NaiveDate,
+        horizon: u32,
+    ) -> Vec<(milestones::Milestone, &DistroRelease, NaiveDate)> {
+        let end = date + chrono::Duration::days(i64::from(horizon));
+        let mut upcoming: Vec<_> =
+            self.iter()
+                .flat_map(|distro_release| {
+                    distro_release.milestones().into_iter().map(
+                        move |(milestone, milestone_date)| {
+                            (milestone, distro_release, milestone_date)
+                        },
+                    )
+                })
+                .filter(|(_, _, milestone_date)| date <= *milestone_date && *milestone_date <= end)
+                .collect();
+        upcoming.sort_by_key(|(_, _, milestone_date)| *milestone_date);
+        upcoming
+    }
+
+    /// Returns the release following `distro_release`, if any
+    ///
+    /// Rolling suites are not considered releases, so have neither a successor nor a predecessor.
+    fn successor(&self, distro_release: &DistroRelease) -> Option<&DistroRelease> {
+        let releases: Vec<&DistroRelease> = self
+            .iter()
+            .filter(|candidate| !candidate.is_rolling())
+            .collect();
+        releases
+            .iter()
+            .position(|candidate| candidate.series == distro_release.series)
+            .and_then(|index| releases.get(index + 1).copied())
+    }
+
+    /// The phases of `distro_release`'s support, from full support to end of life
+    fn support_timeline(&self, distro_release: &DistroRelease) -> phase::SupportTimeline {
+        phase::SupportTimeline::new(
+            self.distro(),
+            distro_release,
+            self.successor(distro_release),
+        )
+    }
+
+    /// Returns the release preceding `distro_release`, if any
+    fn predecessor(&self, distro_release: &DistroRelease) -> Option<&DistroRelease> {
+        let releases: Vec<&DistroRelease> = self
+            .iter()
+            .filter(|candidate| !candidate.is_rolling())
+            .collect();
+        releases
+            .iter()
+            .position(|candidate| candidate.series == distro_release.series)
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| releases.get(index).copied())
+    }
+
+    /// Returns the sequence of releases to upgrade through to get from `from_series` to
+    /// `to_series` (excluding `from_series` itself)
+    ///
+    /// For Ubuntu, upgrades from an LTS release go directly to the next LTS release where that
+    /// doesn't overshoot the target; otherwise each release is upgraded to its successor.
+    /// Returns `None` if either series is unknown or a rolling suite, or `to_series` precedes
+    /// `from_series`.
+    fn upgrade_path(&self, from_series: &str, to_series: &str) -> Option<Vec<&DistroRelease>> {
+        let releases: Vec<&DistroRelease> = self
+            .iter()
+            .filter(|distro_release| !distro_release.is_rolling())
+            .collect();
+        let position = |series: &str| releases.iter().position(|r| *r.series == series);
+        let (mut current, target) = (position(from_series)?, position(to_series)?);
+        if target < current {
+            return None;
+        }
+        let policy = self.distro().policy();
+        let mut path = vec![];
+        while current < target {
+            let next_lts = match self.distro() {
+                Distro::Ubuntu if policy.is_lts(releases[current]) => {
+                    (current + 1..=target).find(|&index| policy.is_lts(releases[index]))
+                }
+                _ => None,
+            };
+            current = next_lts.unwrap_or(current + 1);
+            path.push(releases[current]);
+        }
+        Some(path)
+    }
+
+    /// Whether upgrading directly from `from` to `to` is supported
+    ///
+    /// Every distro supports upgrading to the next release; Ubuntu also supports upgrading from
+    /// an LTS release to the next LTS release.  Rolling suites are not considered releases.
+    fn upgrade_supported(&self, from: &DistroRelease, to: &DistroRelease) -> UpgradeSupport {
+        let releases: Vec<&DistroRelease> = self
+            .iter()
+            .filter(|distro_release| !distro_release.is_rolling())
+            .collect();
+        let position = |needle: &DistroRelease| {
+            releases
+                .iter()
+                .position(|distro_release| distro_release.series == needle.series)
+        };
+        let (from_position, to_position) = match (position(from), position(to)) {
+            (Some(from_position), Some(to_position)) => (from_position, to_position),
+            _ => return UpgradeSupport::Unknown,
+        };
+        if to_position <= from_position {
+            return UpgradeSupport::NotAnUpgrade;
+        }
+        let policy = self.distro().policy();
+        let supported = to_position == from_position + 1
+            || match self.distro() {
+                Distro::Ubuntu if policy.is_lts(from) => {
+                    policy.is_lts(to)
+                        && !releases[from_position + 1..to_position]
+                            .iter()
+                            .any(|distro_release| policy.is_lts(distro_release))
+                }
+                _ => false,
+            };
+        if supported {
+            UpgradeSupport::Supported
+        } else {
+            UpgradeSupport::SkipsReleases
+        }
+    }
+
+    /// Returns the series of all releases, oldest first, without duplicates
+    ///
+    /// This is intended for validating user input and tab-completion.
+    fn all_series(&self) -> Vec<&str> {
+        unique(
+            self.iter()
+                .map(|distro_release| distro_release.series.as_str()),
+        )
+    }
+
+    /// Returns the full codenames of all releases, oldest first, without duplicates
+    fn all_codenames(&self) -> Vec<&str> {
+        unique(
+            self.iter()
+                .map(|distro_release| distro_release.codename.as_str()),
+        )
+    }
+
+    /// Returns the versions of all releases which have one, oldest first, without duplicates
+    fn all_versions(&self) -> Vec<&str> {
+        unique(
+            self.iter()
+                .filter_map(|distro_release| distro_release.version.as_deref()),
+        )
+    }
+
+    /// Returns the release identified by `needle`, which may be a series (e.g. "jammy"), a full
+    /// codename (e.g. "Jammy Jellyfish") or a version (e.g. "22.04" or "22.04 LTS")
+    ///
+    /// Case and diacritics are ignored; see `lookup_with` for exact matching.
+    fn lookup(&self, needle: &str) -> Option<&DistroRelease> {
+        self.lookup_with(needle, matching::MatchMode::Relaxed)
+    }
+
+    /// Like `lookup`, but comparing `needle` with the data according to `mode`
+    fn lookup_with(&self, needle: &str, mode: matching::MatchMode) -> Option<&DistroRelease> {
+        let found = match self.index() {
+            Some(index) => index
+                .series(needle, mode)
+                .or_else(|| index.codename(needle, mode))
+                .or_else(|| index.version(needle, mode))
+                .map(|position| &self.releases()[position]),
+            None => self
+                .find_series(needle, mode)
+                .or_else(|| {
+                    self.iter()
+                        .find(|distro_release| mode.matches(&distro_release.codename, needle))
+                })
+                .or_else(|| {
+                    self.iter()
+                        .find(|distro_release| match &distro_release.version {
+                            Some(version) => {
+                                mode.matches(version, needle)
+                                    || mode.matches(version.trim_end_matches(" LTS"), needle)
+                            }
+                            None => false,
+                        })
+                }),
+        };
+        // Relaxed matching also accepts partial codenames, like "Jammy"
+        found.or_else(|| match mode {
+            matching::MatchMode::Relaxed => self.release_by_codename(needle),
+            matching::MatchMode::Strict => None,
+        })
+    }
+
+    /// Returns the release named by `codename`, which may be its series ("bookworm") or its
+    /// codename ("Bookworm", "Jammy Jellyfish", just "Jammy" or just "Jellyfish"), in any case
+    fn release_by_codename(&self, codename: &str) -> Option<&DistroRelease> {
+        self.find_series(
+            &matching::normalize_codename(codename),
+            matching::MatchMode::Relaxed,
+        )
+        .or_else(|| {
+            self.iter().find(|distro_release| {
+                matching::MatchMode::Relaxed.matches(&distro_release.codename, codename)
+            })
+        })
+        .or_else(|| {
+            self.iter().find(|distro_release| {
+                distro_release.codename_animal().is_some_and(|animal| {
+                    matching::MatchMode::Relaxed.matches(animal, codename.trim())
+                })
+            })
+        })
+    }
+
+    /// Find a release by any name it has been known by: anything `lookup` accepts, historical
+    /// aliases (such as Debian's "etch-and-a-half") and point releases (such as "3.0r6" or
+    /// "12.5"); see the [`aliases`](aliases/index.html) module
+    fn release_by_any_name(&self, name: &str) -> Option<&DistroRelease> {
+        let name = name.trim();
+        self.lookup(name)
+            .or_else(|| {
+                let series = aliases::historical_alias(self.distro(), name)?;
+                self.find_series(series, matching::MatchMode::Strict)
+            })
+            .or_else(|| {
+                self.iter().find(|distro_release| {
+                    distro_release
+                        .version
+                        .as_ref()
+                        .is_some_and(|version| aliases::version_matches(version, name))
+                })
+            })
+    }
+
+    /// Returns the release with the given series, compared according to `mode`
+    fn find_series(&self, series: &str, mode: matching::MatchMode) -> Option<&DistroRelease> {
+        if let Some(index) = self.index() {
+            return index
+                .series(series, mode)
+                .map(|position| &self.releases()[position]);
+        }
+        self.iter()
+            .find(|distro_release| mode.matches(&distro_release.series, series))
+    }
+
+    /// Returns the series most similar to `needle`, most similar first
+    ///
+    /// Series which `needle` is a prefix or substring of are suggested first, followed by those
+    /// within a small edit distance of it.  This is intended for "did you mean" messages when a
+    /// user gives an unknown series.
+    fn suggest_series(&self, needle: &str) -> Vec<&str> {
+        let needle = needle.to_lowercase();
+        let max_distance = core::cmp::max(1, needle.chars().count() / 3);
+        let mut candidates: Vec<(usize, &str)> = self
+            .iter()
+            .filter_map(|distro_release| {
+                let series = distro_release.series().as_str();
+                if series == needle {
+                    None
+                } else if series.starts_with(needle.as_str()) {
+                    Some((0, series))
+                } else if series.contains(needle.as_str()) {
+                    Some((1, series))
+                } else {
+                    let distance = edit_distance(&needle, series);
+                    if distance <= max_distance {
+                        Some((1 + distance, series))
+                    } else {
+                        None
+                    }
+                }
+            })
+            .collect();
+        candidates.sort_by_key(|(rank, _)| *rank);
+        candidates.into_iter().map(|(_, series)| series).collect()
+    }
+}
+
+/// Constructing a `DistroInfo` type from release data, e.g. from the system's CSV file
+///
+/// These are kept apart from the query methods of [`DistroQueries`](trait.DistroQueries.html),
+/// which are object-safe, so that distros can be handled uniformly as `&dyn DistroQueries` (see
+/// `Distro::distro_info` and `Distro::load`).
+pub trait DistroDataSource: DistroQueries + Sized {
+    fn from_vec(releases: Vec<DistroRelease>) -> Self;
+
+    /// The full path to the CSV file to read from for this distro
+    fn csv_path() -> &'static str;
+
+    /// Parse the contents of a CSV file to create a Debian/UbuntuDistroInfo object
+    ///
+    /// This is available without the `std` feature; see the [`parser`](parser/index.html) module.
+    fn from_csv_str(input: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_vec(parser::parse_csv(input)?))
+    }
+
+    /// Like `from_csv_str`, but only loading the rows `filter` keeps
+    fn from_csv_str_filtered(input: &str, filter: &parser::RowFilter) -> Result<Self, ParseError> {
+        Ok(Self::from_vec(parser::parse_csv_filtered(input, filter)?))
+    }
+
+    /// Read a YAML list of releases to create a Debian/UbuntuDistroInfo object
+    ///
+    /// See the [`formats`](formats/index.html) module for the format.  Errors in the input are
+    /// reported as a [`ParseError`](parser/enum.ParseError.html), which the returned error can be
+    /// downcast to.
+    #[cfg(feature = "yaml")]
+    fn from_yaml_reader<T: std::io::Read>(reader: T) -> Result<Self, Error> {
+        Ok(Self::from_vec(formats::parse_yaml(reader)?))
+    }
+
+    /// Parse a TOML list of releases to create a Debian/UbuntuDistroInfo object
+    ///
+    /// See the [`formats`](formats/index.html) module for the format.
+    #[cfg(feature = "toml")]
+    fn from_toml_str(input: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_vec(formats::parse_toml(input)?))
+    }
+
+    /// Read records from the given CSV reader to create a Debian/UbuntuDistroInfo object
+    ///
+    /// (These records must be in the format used in debian.csv/ubuntu.csv as provided by the
+    /// distro-info-data package in Debian/Ubuntu.)  Errors in the input are reported as a
+    /// [`ParseError`](parser/enum.ParseError.html), which the returned error can be downcast to.
+    #[cfg(feature = "std")]
+    fn from_csv_reader<T: std::io::Read>(rdr: csv::Reader<T>) -> Result<Self, Error> {
+        Self::from_csv_reader_filtered(rdr, &parser::RowFilter::new())
+    }
+
+    /// Like `from_csv_reader`, but only loading the rows `filter` keeps
+    #[cfg(feature = "std")]
+    fn from_csv_reader_filtered<T: std::io::Read>(
+        rdr: csv::Reader<T>,
+        filter: &parser::RowFilter,
+    ) -> Result<Self, Error> {
+        Ok(Self::from_vec(read_csv_records(rdr, filter, None, None)?))
+    }
+
+    /// Like `from_csv_reader`, but also returning the recoverable issues found in the input
+    /// (such as unknown columns), for review tooling
+    #[cfg(feature = "std")]
+    fn from_csv_reader_with_report<T: std::io::Read>(
+        rdr: csv::Reader<T>,
+    ) -> Result<(Self, Vec<parser::ParseWarning>), Error> {
+        let mut warnings = Vec::new();
+        let releases = read_csv_records(rdr, &parser::RowFilter::new(), Some(&mut warnings), None)?;
+        Ok((Self::from_vec(releases), warnings))
+    }
+
+    /// Like `from_csv_reader_with_report`, but also validating the rows as `options` configures;
+    /// see [`LoadOptions`](parser/struct.LoadOptions.html)
+    #[cfg(feature = "std")]
+    fn from_csv_reader_with_options<T: std::io::Read>(
+        rdr: csv::Reader<T>,
+        options: &parser::LoadOptions,
+    ) -> Result<(Self, Vec<parser::ParseWarning>), Error> {
+        let mut warnings = Vec::new();
+        let releases = read_csv_records(
+            rdr,
+            &parser::RowFilter::new(),
+            Some(&mut warnings),
+            Some(options),
+        )?;
+        Ok((Self::from_vec(releases), warnings))
+    }
+
+    /// Open the CSV file at `path` and parse the release data contained therein
+    ///
+    /// gzip- and zstd-compressed files are decompressed transparently (with the `flate2` and
+    /// `zstd` features respectively).
+    #[cfg(feature = "std")]
+    fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        Self::from_csv_reader(csv_reader(compression::open(path.as_ref())?))
+    }
+
+    /// Like `from_path`, but only loading the rows `filter` keeps
+    #[cfg(feature = "std")]
+    fn from_path_filtered<P: AsRef<std::path::Path>>(
+        path: P,
+        filter: &parser::RowFilter,
+    ) -> Result<Self, Error> {
+        Self::from_csv_reader_filtered(csv_reader(compression::open(path.as_ref())?), filter)
+    }
+
+    /// Like `from_path`, but validating the rows as `options` configures, and also returning the
+    /// recoverable issues found; see `from_csv_reader_with_options`
+    #[cfg(feature = "std")]
+    fn from_path_with_options<P: AsRef<std::path::Path>>(
+        path: P,
+        options: &parser::LoadOptions,
+    ) -> Result<(Self, Vec<parser::ParseWarning>), Error> {
+        Self::from_csv_reader_with_options(csv_reader(compression::open(path.as_ref())?), options)
+    }
+
+    /// Open the CSV file at `path` and parse the release data contained therein, if its minisign
+    /// signature (at `path` with `.minisig` appended) is valid for `public_key`
+    ///
+    /// See the [`verify`](verify/index.html) module.
+    #[cfg(feature = "minisign")]
+    fn from_path_verified<P: AsRef<std::path::Path>>(
+        path: P,
+        public_key: &str,
+    ) -> Result<Self, Error> {
+        let data = verify::read_verified(path.as_ref(), public_key)?;
+        Self::from_csv_reader(csv_reader(data.as_slice()))
+    }
+
+    /// Open this distro's CSV file and parse the release data contained therein
+    ///
+    /// If the CSV file does not exist, a compressed variant of it (e.g. ubuntu.csv.gz) is used, or
+    /// failing that, a file of the same name in one of the
+    /// [`discovery::data_search_paths`](discovery/fn.data_search_paths.html).
+    #[cfg(feature = "std")]
+    fn new() -> Result<Self, Error> {
+        Self::from_path(Self::data_path())
+    }
+
+    /// Like `new`, but only loading the rows `filter` keeps
+    #[cfg(feature = "std")]
+    fn new_filtered(filter: &parser::RowFilter) -> Result<Self, Error> {
+        Self::from_path_filtered(Self::data_path(), filter)
+    }
+
+    /// The file `new` reads this distro's data from (which may not exist)
+    #[cfg(feature = "std")]
+    fn data_path() -> std::path::PathBuf {
+        discovery::locate(::std::path::Path::new(Self::csv_path()))
+    }
+
+    /// Describe where `new` reads this distro's data from: the path, its modification time and
+    /// the version of the distro-info-data package providing it
+    #[cfg(feature = "std")]
+    fn data_source() -> source::DataSource {
+        source::DataSource::for_csv_path(&Self::data_path())
+    }
+
+    /// Like `new`, but if this distro's CSV file does not exist, infer what release data it can
+    /// from apt's metadata (see the [`apt`](apt/index.html) module)
+    #[cfg(feature = "std")]
+    fn new_or_inferred() -> Result<Self, Error> {
+        if Self::data_path().exists() {
+            return Self::new();
+        }
+        let distro = *Self::from_vec(Vec::new()).distro();
+        trace_event!(
+            info,
+            csv_path = Self::csv_path(),
+            "CSV file missing; inferring release data from apt"
+        );
+        Ok(Self::from_vec(apt::infer_releases(&distro)?))
     }
-    fn csv_path() -> &'static str {
-        UBUNTU_CSV_PATH
+
+    /// Like `new`, but use the binary cache of parsed data if it is up-to-date
+    ///
+    /// The cache is regenerated (best-effort) whenever it is missing or older than the CSV file.
+    #[cfg(feature = "cache")]
+    fn new_cached() -> Result<Self, Error> {
+        let csv_path = &Self::data_path();
+        if let Some(releases) = cache::load(csv_path) {
+            trace_event!(debug, csv_path = %csv_path.display(), "using cached release data");
+            return Ok(Self::from_vec(releases));
+        }
+        trace_event!(debug, csv_path = %csv_path.display(), "release data cache missing or stale");
+        let distro_info = Self::new()?;
+        // Failing to write the cache shouldn't prevent the data being used
+        if let Err(_e) = cache::store(csv_path, distro_info.releases()) {
+            trace_event!(warn, error = %_e, "failed to write release data cache");
+        }
+        Ok(distro_info)
+    }
+
+    /// This data, with its releases' strings shared through `interner`; see the
+    /// [`interning`](interning/index.html) module
+    fn interned(&self, interner: &mut interning::Interner) -> Self {
+        Self::from_vec(
+            self.iter()
+                .map(|distro_release| distro_release.clone().interned(interner))
+                .collect(),
+        )
+    }
+
+    /// Overlay the releases of `overlay` on these, matching them by series
+    ///
+    /// Releases in both are merged field-by-field (see `DistroRelease::merged_with`), with the
+    /// overlay's values taking precedence; releases only in the overlay are appended.  This allows
+    /// a small local CSV file to extend or correct the system data.
+    fn merge(&self, overlay: Self) -> Self {
+        let mut releases = self.releases().to_vec();
+        for overlay_release in overlay.releases().iter().cloned() {
+            match releases
+                .iter()
+                .position(|distro_release| distro_release.series == overlay_release.series)
+            {
+                Some(index) => {
+                    let base = ::core::mem::take(&mut releases[index]);
+                    releases[index] = base.merged_with(overlay_release);
+                }
+                None => releases.push(overlay_release),
+            }
+        }
+        Self::from_vec(releases)
+    }
+
+    /// Record the flavour EOLs in `flavour_eols` on the matching releases
+    ///
+    /// Rows for unknown series are ignored.
+    fn with_flavour_eols(&self, flavour_eols: &[flavours::FlavourEol]) -> Self {
+        let mut releases = self.releases().to_vec();
+        for flavour_eol in flavour_eols {
+            if let Some(distro_release) = releases
+                .iter_mut()
+                .find(|distro_release| *distro_release.series == flavour_eol.series)
+            {
+                *distro_release = ::core::mem::take(distro_release)
+                    .with_flavour_eol(&flavour_eol.flavour, flavour_eol.eol);
+            }
+        }
+        Self::from_vec(releases)
     }
-    /// Initialise an UbuntuDistroInfo struct from a vector of DistroReleases
-    fn from_vec(releases: Vec<DistroRelease>) -> Self {
-        Self { releases }
+
+    /// Returns the releases as seen by users of `flavour` (e.g. "xubuntu")
+    ///
+    /// Releases whose flavour EOL is known have it as their `eol`, with no separate server EOL,
+    /// so that `supported` and friends give answers for the flavour.
+    fn for_flavour(&self, flavour: &str) -> Self {
+        Self::from_vec(
+            self.iter()
+                .map(|distro_release| {
+                    let mut distro_release = distro_release.clone();
+                    if let Some(eol) = distro_release.explicit_flavour_eol(flavour) {
+                        distro_release.eol = Some(eol);
+                        distro_release.eol_server = None;
+                    }
+                    distro_release
+                })
+                .collect(),
+        )
     }
 }
 
-impl IntoIterator for UbuntuDistroInfo {
-    type Item = DistroRelease;
-    type IntoIter = ::std::vec::IntoIter<DistroRelease>;
+/// `DistroQueries`, under the name the trait had before the constructors were split out
+pub use DistroQueries as DistroInfo;
+
+/// Collect `values`, dropping any seen before
+fn unique<'a>(values: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut unique: Vec<&str> = Vec::new();
+    for value in values {
+        if !unique.contains(&value) {
+            unique.push(value);
+        }
+    }
+    unique
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.releases.into_iter()
+/// The edit distance between two strings, counting insertions, deletions, substitutions and
+/// transpositions of adjacent characters (the "optimal string alignment" distance)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = core::cmp::min(
+                distances[i - 1][j - 1] + cost,
+                core::cmp::min(distances[i - 1][j], distances[i][j - 1]) + 1,
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = core::cmp::min(distance, distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
     }
+    distances[a.len()][b.len()]
 }
 
-pub struct DebianDistroInfo {
+/// The releases of a derivative, as `Distro::distro_info` wraps them
+struct DerivativeDistroInfo {
+    distro: Distro,
     releases: Vec<DistroRelease>,
+    index: index::LazyIndex,
 }
 
-impl DistroInfo for DebianDistroInfo {
+impl DistroQueries for DerivativeDistroInfo {
     fn distro(&self) -> &Distro {
-        &Distro::Debian
+        &self.distro
     }
-    fn releases(&self) -> &Vec<DistroRelease> {
+    fn releases(&self) -> &[DistroRelease] {
         &self.releases
     }
-    fn csv_path() -> &'static str {
-        DEBIAN_CSV_PATH
-    }
-    /// Initialise an DebianDistroInfo struct from a vector of DistroReleases
-    fn from_vec(releases: Vec<DistroRelease>) -> Self {
-        Self { releases }
+    fn index(&self) -> Option<&index::ReleaseIndex> {
+        self.index.get(&self.releases)
     }
 }
 
-impl IntoIterator for DebianDistroInfo {
-    type Item = DistroRelease;
-    type IntoIter = ::std::vec::IntoIter<DistroRelease>;
+crate::define_distro! {
+    pub struct UbuntuDistroInfo {
+        distro: Distro::Ubuntu,
+        csv_path: UBUNTU_CSV_PATH,
+    }
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.releases.into_iter()
+crate::define_distro! {
+    pub struct DebianDistroInfo {
+        distro: Distro::Debian,
+        csv_path: DEBIAN_CSV_PATH,
     }
 }
 
@@ -341,15 +1877,19 @@ impl IntoIterator for DebianDistroInfo {
 mod tests {
     use chrono::naive::NaiveDate;
     use {
-        super::DebianDistroInfo, super::DistroInfo, super::DistroRelease, super::UbuntuDistroInfo,
+        super::batch::BatchResolver, super::parser::ParseError, super::parser::ParseWarning,
+        super::parser::RowFilter, super::status, super::status::SupportStatus, super::test_support,
+        super::DebianDistroInfo, super::Distro, super::DistroDataSource, super::DistroInfo,
+        super::DistroRelease, super::ReleaseClass, super::SupportClass, super::SupportTier,
+        super::UbuntuDistroInfo, super::UpgradeSupport,
     };
 
     #[test]
     fn create_struct() {
-        DistroRelease {
+        let _ = DistroRelease {
             version: Some("version".to_string()),
-            codename: "codename".to_string(),
-            series: "series".to_string(),
+            codename: "codename".to_string().into(),
+            series: "series".to_string().into(),
             created: Some(NaiveDate::from_ymd_opt(2018, 6, 14).unwrap()),
             release: Some(NaiveDate::from_ymd_opt(2018, 6, 14).unwrap()),
             eol: Some(NaiveDate::from_ymd_opt(2018, 6, 14).unwrap()),
@@ -381,8 +1921,8 @@ mod tests {
             Some(get_date(6)),
         );
         assert_eq!(Some("version".to_string()), distro_release.version);
-        assert_eq!("codename", distro_release.codename);
-        assert_eq!("series", distro_release.series);
+        assert_eq!("codename", *distro_release.codename);
+        assert_eq!("series", *distro_release.series);
         assert_eq!(Some(get_date(0)), distro_release.created);
         assert_eq!(Some(get_date(1)), distro_release.release);
         assert_eq!(Some(get_date(2)), distro_release.eol);
@@ -434,6 +1974,24 @@ mod tests {
         assert!(!distro_release.is_lts());
     }
 
+    #[test]
+    fn distro_release_release_class() {
+        let release_class = |version: &str| {
+            test_support::fake_release(version, "series", "2018-06-14", None, None).release_class()
+        };
+        assert_eq!(ReleaseClass::Lts, release_class("6.06 LTS"));
+        assert_eq!(ReleaseClass::Lts, release_class("22.04"));
+        assert_eq!(ReleaseClass::Interim, release_class("23.04"));
+        assert_eq!(ReleaseClass::Interim, release_class("22.10"));
+        assert_eq!(ReleaseClass::Unknown, release_class("12"));
+        assert_eq!(ReleaseClass::Unknown, release_class("6.0"));
+        assert_eq!(ReleaseClass::Unknown, release_class(""));
+
+        let overridden = test_support::fake_release("22.04", "series", "2018-06-14", None, None)
+            .with_release_class(ReleaseClass::Interim);
+        assert_eq!(ReleaseClass::Interim, overridden.release_class());
+    }
+
     #[test]
     fn distro_release_released_at() {
         let distro_release = DistroRelease::new(
@@ -479,64 +2037,457 @@ mod tests {
     }
 
     #[test]
-    fn debian_distro_info_new() {
-        DebianDistroInfo::new().unwrap();
+    fn distro_release_supported_at_tier() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let release = |series: &str| {
+            ubuntu_distro_info
+                .iter()
+                .find(|distro_release| distro_release.series() == series)
+                .unwrap()
+        };
+        let date = test_support::date("2027-01-01");
+        let xenial = release("xenial");
+        assert!(!xenial.supported_at_tier(date, SupportTier::Standard));
+        assert!(!xenial.supported_at_tier(date, SupportTier::Esm));
+        assert!(xenial.supported_at_tier(date, SupportTier::Pro));
+        assert!(!xenial.supported_at_tier(date, SupportTier::Core));
+        let bionic = release("bionic");
+        assert!(bionic.supported_at_tier(date, SupportTier::Esm));
+        assert!(bionic.supported_at_tier(date, SupportTier::Core));
+        // Interim releases have no extended support, and no Ubuntu Core release
+        let cosmic = release("cosmic");
+        let during_cosmic = test_support::date("2019-01-01");
+        assert!(cosmic.supported_at_tier(during_cosmic, SupportTier::Pro));
+        assert!(!cosmic.supported_at_tier(during_cosmic, SupportTier::Core));
+        assert!(!cosmic.supported_at_tier(date, SupportTier::Pro));
     }
 
     #[test]
-    fn ubuntu_distro_info_new() {
-        UbuntuDistroInfo::new().unwrap();
+    fn distro_release_server_supported_at() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let release = |series: &str| {
+            ubuntu_distro_info
+                .iter()
+                .find(|distro_release| distro_release.series() == series)
+                .unwrap()
+        };
+        // dapper and hardy supported their server flavour for longer than their desktop one
+        let dapper = release("dapper");
+        let after_desktop = test_support::date("2010-01-01");
+        assert!(!dapper.desktop_supported_at(after_desktop));
+        assert!(dapper.server_supported_at(after_desktop));
+        assert!(dapper.supported_at(after_desktop));
+        assert!(!dapper.supported_at_tier(after_desktop, SupportTier::Desktop));
+        assert!(dapper.supported_at_tier(after_desktop, SupportTier::Server));
+        assert!(!dapper.server_supported_at(test_support::date("2011-06-02")));
+        let hardy = release("hardy");
+        assert_eq!(
+            Some(test_support::date("2013-05-09")),
+            hardy.support_end(SupportTier::Server)
+        );
+        assert_eq!(
+            Some(test_support::date("2011-05-12")),
+            hardy.support_end(SupportTier::Desktop)
+        );
+        assert!(hardy.server_supported_at(test_support::date("2013-05-09")));
+        assert!(!hardy.desktop_supported_at(test_support::date("2011-05-13")));
+        // Since oneiric, there is no separate server support, so both flavours end at `eol`
+        let oneiric = release("oneiric");
+        assert_eq!(None, *oneiric.eol_server());
+        assert_eq!(oneiric.eol, oneiric.support_end(SupportTier::Server));
+        let eol = test_support::date("2013-05-09");
+        assert!(oneiric.server_supported_at(eol));
+        assert!(oneiric.desktop_supported_at(eol));
+        assert!(!oneiric.server_supported_at(eol.succ_opt().unwrap()));
+        assert_eq!("server", SupportTier::Server.name());
     }
 
     #[test]
-    fn debian_distro_info_item() {
-        let distro_release = DebianDistroInfo::new().unwrap().into_iter().next().unwrap();
-        assert_eq!(Some("1.1".to_string()), distro_release.version);
-        assert_eq!("Buzz", distro_release.codename);
-        assert_eq!("buzz", distro_release.series);
-        assert_eq!(
-            Some(NaiveDate::from_ymd_opt(1993, 8, 16).unwrap()),
-            distro_release.created
+    fn distro_release_archive_url_ubuntu() {
+        let distro_release = DistroRelease::new(
+            "16.04 LTS".to_string(),
+            "Xenial Xerus".to_string(),
+            "xenial".to_string(),
+            Some(NaiveDate::from_ymd_opt(2015, 10, 22).unwrap()),
+            Some(NaiveDate::from_ymd_opt(2016, 4, 21).unwrap()),
+            Some(NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()),
+            None,
+            None,
+            Some(NaiveDate::from_ymd_opt(2026, 4, 23).unwrap()),
+            Some(NaiveDate::from_ymd_opt(2021, 4, 30).unwrap()),
         );
+        // still on the main archive during ESM
         assert_eq!(
-            Some(NaiveDate::from_ymd_opt(1996, 6, 17).unwrap()),
-            distro_release.release
+            Some("http://archive.ubuntu.com/ubuntu/"),
+            distro_release.archive_url(
+                &Distro::Ubuntu,
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+            )
         );
+        // moved to old-releases once ESM ends
         assert_eq!(
-            Some(NaiveDate::from_ymd_opt(1997, 6, 5).unwrap()),
-            distro_release.eol
+            Some("http://old-releases.ubuntu.com/ubuntu/"),
+            distro_release.archive_url(
+                &Distro::Ubuntu,
+                NaiveDate::from_ymd_opt(2026, 4, 24).unwrap()
+            )
         );
-        assert_eq!(None, distro_release.eol_server);
     }
 
     #[test]
-    fn ubuntu_distro_info_item() {
-        let distro_release = UbuntuDistroInfo::new().unwrap().into_iter().next().unwrap();
-        assert_eq!(Some("4.10".to_string()), distro_release.version);
-        assert_eq!("Warty Warthog", distro_release.codename);
-        assert_eq!("warty", distro_release.series);
-        assert_eq!(
-            Some(NaiveDate::from_ymd_opt(2004, 3, 5).unwrap()),
-            distro_release.created
+    fn distro_release_archive_url_debian() {
+        let distro_release = DistroRelease::new(
+            "10".to_string(),
+            "Buster".to_string(),
+            "buster".to_string(),
+            Some(NaiveDate::from_ymd_opt(2017, 6, 17).unwrap()),
+            Some(NaiveDate::from_ymd_opt(2019, 7, 6).unwrap()),
+            Some(NaiveDate::from_ymd_opt(2022, 9, 10).unwrap()),
+            Some(NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()),
+            Some(NaiveDate::from_ymd_opt(2029, 6, 30).unwrap()),
+            None,
+            None,
         );
+        // still on the main mirrors during LTS
         assert_eq!(
-            Some(NaiveDate::from_ymd_opt(2004, 10, 20).unwrap()),
-            distro_release.release
+            Some("http://deb.debian.org/debian/"),
+            distro_release.archive_url(
+                &Distro::Debian,
+                NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()
+            )
         );
+        // moved to archive.debian.org once LTS ends
         assert_eq!(
-            Some(NaiveDate::from_ymd_opt(2006, 4, 30).unwrap()),
-            distro_release.eol
+            Some("http://archive.debian.org/debian/"),
+            distro_release.archive_url(
+                &Distro::Debian,
+                NaiveDate::from_ymd_opt(2024, 7, 1).unwrap()
+            )
         );
-        assert_eq!(None, distro_release.eol_server);
     }
 
     #[test]
-    fn ubuntu_distro_info_eol_server() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
-        for distro_release in ubuntu_distro_info {
-            match distro_release.series.as_ref() {
-                "breezy" => assert_eq!(None, distro_release.eol_server),
-                "dapper" => {
+    fn debian_distro_info_new() {
+        DebianDistroInfo::new().unwrap();
+    }
+
+    #[test]
+    fn ubuntu_distro_info_new() {
+        UbuntuDistroInfo::new().unwrap();
+    }
+
+    #[test]
+    fn from_csv_str_matches_from_csv_reader() {
+        let from_str = UbuntuDistroInfo::from_csv_str(test_support::UBUNTU_CSV).unwrap();
+        let from_reader = UbuntuDistroInfo::from_csv_reader(
+            csv::ReaderBuilder::new()
+                .flexible(true)
+                .from_reader(test_support::UBUNTU_CSV.as_bytes()),
+        )
+        .unwrap();
+        assert_eq!(from_reader.releases().len(), from_str.releases().len());
+        for (a, b) in from_reader.iter().zip(from_str.iter()) {
+            assert_eq!(a.series(), b.series());
+            assert_eq!(a.version(), b.version());
+            assert_eq!(a.release(), b.release());
+            assert_eq!(a.eol_server(), b.eol_server());
+        }
+    }
+
+    #[test]
+    fn extra_fields_are_preserved() {
+        let csv = "version,codename,series,created,mirror\n1.0,Foo,foo,2020-01-01,http://m/\n";
+        let expected: Vec<(String, String)> = vec![("mirror".into(), "http://m/".into())];
+        let from_str = DebianDistroInfo::from_csv_str(csv).unwrap();
+        let from_reader =
+            DebianDistroInfo::from_csv_reader(csv::Reader::from_reader(csv.as_bytes())).unwrap();
+        for distro_info in [from_str, from_reader] {
+            let extra_fields = distro_info.releases()[0].extra_fields();
+            assert_eq!(
+                expected,
+                extra_fields
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect::<Vec<_>>()
+            );
+        }
+        assert!(test_support::ubuntu().releases()[0]
+            .extra_fields()
+            .is_empty());
+    }
+
+    #[test]
+    fn columns_are_mapped_by_name() {
+        let csv = "\u{feff}series, eol ,version,codename,release\n\
+                   bullseye,2024-08-14,11,Bullseye,2021-08-14\n";
+        let debian_distro_info = DebianDistroInfo::from_csv_str(csv).unwrap();
+        let bullseye = debian_distro_info.iter().next().unwrap();
+        assert_eq!("bullseye", bullseye.series());
+        assert_eq!(&Some("11".to_string()), bullseye.version());
+        assert_eq!(&Some(test_support::date("2024-08-14")), bullseye.eol());
+        assert_eq!(&Some(test_support::date("2021-08-14")), bullseye.release());
+    }
+
+    #[test]
+    fn from_csv_str_errors() {
+        assert_eq!(
+            Some(ParseError::MissingColumn("series")),
+            DebianDistroInfo::from_csv_str("version,codename\n").err()
+        );
+        assert_eq!(
+            Some(ParseError::MissingHeader),
+            DebianDistroInfo::from_csv_str("11,Bullseye,bullseye\n").err()
+        );
+        assert_eq!(
+            Some(ParseError::DuplicateColumn("eol".to_string())),
+            DebianDistroInfo::from_csv_str("version,codename,series,eol,eol\n").err()
+        );
+        assert_eq!(
+            Some(ParseError::InvalidDate {
+                line: 2,
+                value: "2021-13-01".to_string()
+            }),
+            DebianDistroInfo::from_csv_str(
+                "version,codename,series,created\n11,Bullseye,bullseye,2021-13-01\n"
+            )
+            .err()
+        );
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let header = "version,codename,series,created\n";
+        let parse = |rows: &str| {
+            let csv = format!("{}{}", header, rows);
+            let from_str = DebianDistroInfo::from_csv_str(&csv).err();
+            let from_reader =
+                DebianDistroInfo::from_csv_reader(csv::Reader::from_reader(csv.as_bytes()))
+                    .err()
+                    .map(|e| e.downcast::<ParseError>().unwrap());
+            assert_eq!(from_str, from_reader);
+            from_str
+        };
+        let invalid_date = |value: &str| {
+            Some(ParseError::InvalidDate {
+                line: 2,
+                value: value.to_string(),
+            })
+        };
+        assert_eq!(invalid_date("9999-01-01"), parse("1,A,a,9999-01-01\n"));
+        assert_eq!(invalid_date("+2020-01-01"), parse("1,A,a,+2020-01-01\n"));
+        assert_eq!(invalid_date("2020-1-01"), parse("1,A,a,2020-1-01\n"));
+        assert_eq!(
+            Some(ParseError::FieldTooLong { line: 2 }),
+            parse(&format!("1,{},a,\n", "A".repeat(2000)))
+        );
+        assert_eq!(
+            Some(ParseError::NulCharacter { line: 2 }),
+            parse("1,A\0,a,\n")
+        );
+        assert_eq!(
+            Some(ParseError::DuplicateSeries {
+                line: 3,
+                series: "a".to_string()
+            }),
+            parse("1,A,a,\n2,A,a,\n")
+        );
+        assert_eq!(None, parse("1,A,a,2020-01-01\n2,B,b,\n"));
+        assert!(matches!(
+            DebianDistroInfo::from_csv_reader(csv::Reader::from_reader(
+                &b"version,codename,series\n1,\xff,a\n"[..]
+            ))
+            .err()
+            .map(|e| e.downcast::<ParseError>().unwrap()),
+            Some(ParseError::Malformed { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn row_filters() {
+        let input = "version,codename,series,created\n\
+                     1,A,a,2020-01-01\n\
+                     1,A (ports),a,2020-01-01\n\
+                     2,B,b-ports,2021-01-01\n\
+                     3,C,c,2022-01-01\n";
+        let filter = RowFilter::new()
+            .exclude(|distro_release| distro_release.codename().ends_with("(ports)"))
+            .exclude_series(&["b-ports"]);
+        let series = |distro_info: &DebianDistroInfo| -> Vec<String> {
+            distro_info
+                .all_series()
+                .into_iter()
+                .map(String::from)
+                .collect()
+        };
+        assert_eq!(
+            vec!["a", "c"],
+            series(&DebianDistroInfo::from_csv_str_filtered(input, &filter).unwrap())
+        );
+        let from_reader = DebianDistroInfo::from_csv_reader_filtered(
+            csv::Reader::from_reader(input.as_bytes()),
+            &filter,
+        )
+        .unwrap();
+        assert_eq!(vec!["a", "c"], series(&from_reader));
+        let from_2021 = RowFilter::new().include(|distro_release| {
+            distro_release
+                .created()
+                .is_some_and(|created| created >= NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
+        });
+        assert_eq!(
+            vec!["b-ports", "c"],
+            series(&DebianDistroInfo::from_csv_str_filtered(input, &from_2021).unwrap())
+        );
+        assert!(DebianDistroInfo::from_csv_str(input).is_err());
+    }
+
+    #[test]
+    fn parse_warnings() {
+        let input = "version,codename,series,created,release,notes\n\
+                     1,A ,a,2020-01-01,2020-06-01,x\n\
+                     2,B,b,2021-01-01\n\
+                     ,C,c,2022-01-01,,\n";
+        let (debian_distro_info, warnings) =
+            DebianDistroInfo::from_csv_reader_with_report(crate::csv_reader(input.as_bytes()))
+                .unwrap();
+        assert_eq!(3, debian_distro_info.releases().len());
+        assert_eq!(
+            vec![
+                ParseWarning::UnknownColumn("notes".to_string()),
+                ParseWarning::TrailingWhitespace {
+                    line: 2,
+                    column: "codename".to_string()
+                },
+                ParseWarning::BlankField {
+                    line: 4,
+                    column: "release".to_string()
+                },
+                ParseWarning::BlankField {
+                    line: 4,
+                    column: "notes".to_string()
+                },
+            ],
+            warnings
+        );
+        assert_eq!(
+            "line 4: no value for column 'release'",
+            warnings[2].to_string()
+        );
+        let (_, warnings) = UbuntuDistroInfo::from_csv_reader_with_report(crate::csv_reader(
+            test_support::UBUNTU_CSV.as_bytes(),
+        ))
+        .unwrap();
+        assert_eq!(Vec::<ParseWarning>::new(), warnings);
+    }
+
+    #[test]
+    fn load_options_validation() {
+        use crate::parser::{LoadOptions, Strictness};
+        let input = "version,codename,series,created,release,eol\n\
+                     1,A,a,2020-01-01,2020-06-01,2020-05-01\n\
+                     2,B,b,2021-01-01,2020-12-01,2022-01-01\n\
+                     3,A2,a,2022-01-01,2022-06-01,2023-01-01\n";
+        let load = |validate| {
+            DebianDistroInfo::from_csv_reader_with_options(
+                crate::csv_reader(input.as_bytes()),
+                &LoadOptions::new(validate),
+            )
+        };
+        let error = load(Strictness::Strict).err().unwrap();
+        assert_eq!(
+            Some(&ParseError::OutOfOrder {
+                line: 2,
+                earlier: "release",
+                later: "eol"
+            }),
+            error.downcast_ref::<ParseError>()
+        );
+        assert_eq!("line 2: 'eol' is before 'release'", error.to_string());
+        let (debian_distro_info, warnings) = load(Strictness::Lenient).unwrap();
+        assert_eq!(
+            vec![
+                ParseWarning::OutOfOrder {
+                    line: 2,
+                    earlier: "release",
+                    later: "eol"
+                },
+                ParseWarning::OutOfOrder {
+                    line: 3,
+                    earlier: "created",
+                    later: "release"
+                },
+                ParseWarning::DuplicateSeries {
+                    line: 4,
+                    series: "a".to_string()
+                },
+            ],
+            warnings
+        );
+        assert_eq!(2, debian_distro_info.releases().len());
+        assert_eq!("A", debian_distro_info.lookup("a").unwrap().codename());
+        // The real data is in order
+        assert_eq!(Strictness::Strict, LoadOptions::default().validate);
+        for data in [test_support::UBUNTU_CSV, test_support::DEBIAN_CSV] {
+            let (_, warnings) = DebianDistroInfo::from_csv_reader_with_options(
+                crate::csv_reader(data.as_bytes()),
+                &LoadOptions::default(),
+            )
+            .unwrap();
+            assert!(!warnings
+                .iter()
+                .any(|warning| matches!(warning, ParseWarning::OutOfOrder { .. })));
+        }
+    }
+
+    #[test]
+    fn debian_distro_info_item() {
+        let distro_release = test_support::debian().into_iter().next().unwrap();
+        assert_eq!(Some("1.1".to_string()), distro_release.version);
+        assert_eq!("Buzz", *distro_release.codename);
+        assert_eq!("buzz", *distro_release.series);
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(1993, 8, 16).unwrap()),
+            distro_release.created
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(1996, 6, 17).unwrap()),
+            distro_release.release
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(1997, 6, 5).unwrap()),
+            distro_release.eol
+        );
+        assert_eq!(None, distro_release.eol_server);
+    }
+
+    #[test]
+    fn ubuntu_distro_info_item() {
+        let distro_release = test_support::ubuntu().into_iter().next().unwrap();
+        assert_eq!(Some("4.10".to_string()), distro_release.version);
+        assert_eq!("Warty Warthog", *distro_release.codename);
+        assert_eq!("warty", *distro_release.series);
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(2004, 3, 5).unwrap()),
+            distro_release.created
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(2004, 10, 20).unwrap()),
+            distro_release.release
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(2006, 4, 30).unwrap()),
+            distro_release.eol
+        );
+        assert_eq!(None, distro_release.eol_server);
+    }
+
+    #[test]
+    fn ubuntu_distro_info_eol_server() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        for distro_release in ubuntu_distro_info {
+            match distro_release.series.as_str() {
+                "breezy" => assert_eq!(None, distro_release.eol_server),
+                "dapper" => {
                     assert_eq!(
                         Some(NaiveDate::from_ymd_opt(2011, 6, 1).unwrap()),
                         distro_release.eol_server
@@ -549,13 +2500,13 @@ mod tests {
     }
     #[test]
     fn ubuntu_distro_info_released() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         // Use dapper's release date to confirm we don't have a boundary issue
         let date = NaiveDate::from_ymd_opt(2006, 6, 1).unwrap();
         let released_series: Vec<String> = ubuntu_distro_info
             .released(date)
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         assert_eq!(
             vec![
@@ -570,13 +2521,13 @@ mod tests {
 
     #[test]
     fn ubuntu_distro_info_supported() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         // Use bionic's release date to confirm we don't have a boundary issue
         let date = NaiveDate::from_ymd_opt(2018, 4, 26).unwrap();
         let supported_series: Vec<String> = ubuntu_distro_info
             .supported(date)
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         assert_eq!(
             vec![
@@ -592,13 +2543,13 @@ mod tests {
 
     #[test]
     fn ubuntu_distro_info_unsupported() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         // Use bionic's release date to confirm we don't have a boundary issue
         let date = NaiveDate::from_ymd_opt(2006, 11, 1).unwrap();
         let unsupported_series: Vec<String> = ubuntu_distro_info
             .unsupported(date)
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         assert_eq!(
             vec!["warty".to_string(), "hoary".to_string()],
@@ -608,13 +2559,13 @@ mod tests {
 
     #[test]
     fn ubuntu_distro_info_supported_on_eol_day() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         // Use artful's EOL date to confirm we don't have a boundary issue
         let date = NaiveDate::from_ymd_opt(2018, 7, 19).unwrap();
         let supported_series: Vec<String> = ubuntu_distro_info
             .supported(date)
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         assert_eq!(
             vec![
@@ -630,36 +2581,36 @@ mod tests {
 
     #[test]
     fn ubuntu_distro_info_supported_with_server_eol() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         let date = NaiveDate::from_ymd_opt(2011, 5, 14).unwrap();
         let supported_series: Vec<String> = ubuntu_distro_info
             .supported(date)
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         assert!(supported_series.contains(&"dapper".to_string()));
     }
 
     #[test]
     fn ubuntu_distro_info_devel() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         let date = NaiveDate::from_ymd_opt(2018, 4, 26).unwrap();
         let devel_series: Vec<String> = ubuntu_distro_info
             .ubuntu_devel(date)
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         assert_eq!(vec!["cosmic".to_string()], devel_series);
     }
 
     #[test]
     fn ubuntu_distro_info_all_at() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         let date = NaiveDate::from_ymd_opt(2005, 4, 8).unwrap();
         let all_series: Vec<String> = ubuntu_distro_info
             .all_at(date)
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         assert_eq!(
             vec![
@@ -673,29 +2624,788 @@ mod tests {
 
     #[test]
     fn ubuntu_distro_info_latest() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         let date = NaiveDate::from_ymd_opt(2005, 4, 8).unwrap();
-        let latest_series = ubuntu_distro_info.latest(date).unwrap().series.clone();
+        let latest_series = ubuntu_distro_info.latest(date).unwrap().series().clone();
         assert_eq!("hoary".to_string(), latest_series);
     }
 
+    #[test]
+    fn stable_on_release_days() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let stable = |date| {
+            ubuntu_distro_info
+                .stable(test_support::date(date))
+                .map(|distro_release| distro_release.series().as_str())
+        };
+        assert_eq!(Some("impish"), stable("2022-04-20"));
+        assert_eq!(Some("jammy"), stable("2022-04-21"));
+        assert_eq!(None, stable("2004-10-19"));
+        assert_eq!(Some("warty"), stable("2004-10-20"));
+        let debian_distro_info = test_support::debian();
+        let stable = |date| {
+            debian_distro_info
+                .stable(test_support::date(date))
+                .map(|distro_release| distro_release.series().as_str())
+        };
+        assert_eq!(Some("bullseye"), stable("2023-06-09"));
+        assert_eq!(Some("bookworm"), stable("2023-06-10"));
+        // trixie was created (and so in its supported window) long before its release
+        assert_eq!(Some("bookworm"), stable("2025-08-08"));
+        assert_eq!(Some("trixie"), stable("2025-08-09"));
+        // A devel release with no EOL date is supported, but not stable
+        let releases = [
+            test_support::fake_release("1", "a", "2020-01-01", Some("2020-06-01"), None),
+            test_support::fake_release("2", "b", "2020-05-01", Some("2020-12-01"), None),
+        ];
+        let distro_info = DebianDistroInfo::from_vec(releases.to_vec());
+        assert_eq!(
+            2,
+            distro_info
+                .supported(test_support::date("2020-11-30"))
+                .len()
+        );
+        assert_eq!(
+            "a",
+            distro_info
+                .stable(test_support::date("2020-11-30"))
+                .unwrap()
+                .series()
+        );
+    }
+
+    #[test]
+    fn ubuntu_distro_info_lts() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let date = NaiveDate::from_ymd_opt(2008, 1, 1).unwrap();
+        let lts_series: Vec<_> = ubuntu_distro_info
+            .lts(date)
+            .iter()
+            .map(|distro_release| distro_release.series().as_str())
+            .collect();
+        assert_eq!(vec!["dapper", "hardy"], lts_series);
+        assert_eq!(
+            "hardy",
+            ubuntu_distro_info.latest_lts(date).unwrap().series()
+        );
+        let before_first_lts = NaiveDate::from_ymd_opt(2005, 1, 1).unwrap();
+        assert!(ubuntu_distro_info.latest_lts(before_first_lts).is_none());
+    }
+
+    #[test]
+    fn ubuntu_distro_info_previous_lts() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let previous_lts = |date| {
+            ubuntu_distro_info
+                .previous_lts(test_support::date(date))
+                .map(|distro_release| distro_release.series().as_str())
+        };
+        assert_eq!(Some("dapper"), previous_lts("2008-01-01"));
+        assert_eq!(Some("focal"), previous_lts("2022-01-01"));
+        assert_eq!(None, previous_lts("2007-01-01"));
+    }
+
+    #[test]
+    fn ubuntu_distro_info_lazy_iterators() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let date = NaiveDate::from_ymd_opt(2018, 7, 19).unwrap();
+        let series = |releases: Vec<&DistroRelease>| -> Vec<String> {
+            releases.iter().map(|r| r.series().clone()).collect()
+        };
+        assert_eq!(
+            series(ubuntu_distro_info.supported(date)),
+            series(ubuntu_distro_info.iter_supported(date).collect())
+        );
+        assert_eq!(
+            series(ubuntu_distro_info.unsupported(date)),
+            series(ubuntu_distro_info.iter_unsupported(date).collect())
+        );
+        assert_eq!(
+            Some("bionic"),
+            ubuntu_distro_info
+                .iter_released(date)
+                .next_back()
+                .map(|r| r.series().as_str())
+        );
+        assert_eq!(
+            "trusty",
+            ubuntu_distro_info
+                .iter_supported(date)
+                .find(|r| r.is_lts())
+                .unwrap()
+                .series()
+        );
+    }
+
     #[test]
     fn ubuntu_distro_info_iter() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         let iter_suites: Vec<String> = ubuntu_distro_info
             .iter()
-            .map(|distro_release| distro_release.series.clone())
+            .map(|distro_release| distro_release.series().clone())
             .collect();
         let mut for_loop_suites = vec![];
         for distro_release in ubuntu_distro_info {
-            for_loop_suites.push(distro_release.series.clone());
+            for_loop_suites.push(distro_release.series().clone());
         }
         assert_eq!(for_loop_suites, iter_suites);
     }
 
+    #[test]
+    fn container_accessors() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        assert_eq!(ubuntu_distro_info.iter().count(), ubuntu_distro_info.len());
+        assert!(!ubuntu_distro_info.is_empty());
+        assert_eq!("warty", ubuntu_distro_info.get(0).unwrap().series());
+        assert!(ubuntu_distro_info.get(ubuntu_distro_info.len()).is_none());
+        // The releases are a slice (in order of creation), so slice algorithms apply
+        let created_by_2020 = ubuntu_distro_info
+            .releases()
+            .partition_point(|distro_release| {
+                distro_release.created_at(test_support::date("2020-01-01"))
+            });
+        assert_eq!(
+            "focal",
+            ubuntu_distro_info
+                .get(created_by_2020 - 1)
+                .unwrap()
+                .series()
+        );
+        let (first, rest) = ubuntu_distro_info.releases().split_first().unwrap();
+        assert_eq!("warty", first.series());
+        assert_eq!(ubuntu_distro_info.len() - 1, rest.len());
+        assert!(UbuntuDistroInfo::from_vec(vec![]).is_empty());
+    }
+
+    #[test]
+    fn ubuntu_distro_info_nearest_release() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let nearest = |date| {
+            ubuntu_distro_info
+                .nearest_release(test_support::date(date))
+                .unwrap()
+                .series()
+                .clone()
+        };
+        // wily was released 2015-10-22 and xenial 2016-04-21
+        assert_eq!("wily", nearest("2016-01-01"));
+        assert_eq!("xenial", nearest("2016-03-02"));
+        assert_eq!("xenial", nearest("2016-04-21"));
+    }
+
+    #[test]
+    fn ubuntu_distro_info_release_active_during() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let active_series: Vec<String> = ubuntu_distro_info
+            .release_active_during(
+                test_support::date("2006-04-30")..=test_support::date("2006-06-01"),
+            )
+            .iter()
+            .map(|distro_release| distro_release.series().clone())
+            .collect();
+        assert_eq!(
+            vec![
+                "warty".to_string(),
+                "hoary".to_string(),
+                "breezy".to_string(),
+                "dapper".to_string(),
+                "edgy".to_string(),
+            ],
+            active_series
+        );
+    }
+
+    #[test]
+    fn distro_release_windows() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let xenial = ubuntu_distro_info
+            .iter()
+            .find(|distro_release| distro_release.series() == "xenial")
+            .unwrap();
+        assert_eq!(
+            Some((
+                test_support::date("2015-10-22"),
+                test_support::date("2016-04-20")
+            )),
+            xenial.development_window()
+        );
+        assert_eq!(
+            Some((
+                test_support::date("2016-04-21"),
+                test_support::date("2021-04-30")
+            )),
+            xenial.supported_window()
+        );
+        let sid = test_support::fake_release("", "sid", "1993-08-16", None, None);
+        assert_eq!(None, sid.development_window());
+        assert_eq!(None, sid.supported_window());
+    }
+
+    #[test]
+    fn time_to_milestones() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info.lookup("jammy").unwrap();
+        let date = test_support::date("2027-01-01");
+        assert_eq!(Some(chrono::Duration::days(151)), jammy.time_to_eol(date));
+        assert_eq!(
+            Some(-chrono::Duration::days(1716)),
+            jammy.time_to_release(date)
+        );
+        assert!(jammy.time_to_eol_esm(date).unwrap() > chrono::Duration::zero());
+        let kinetic = ubuntu_distro_info.lookup("kinetic").unwrap();
+        assert_eq!(None, kinetic.time_to_eol_server(date));
+    }
+
+    #[test]
+    fn eol_age() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let mantic = ubuntu_distro_info.lookup("mantic").unwrap();
+        assert_eq!(
+            Some(test_support::date("2024-07-12")),
+            mantic.unsupported_since()
+        );
+        assert_eq!(None, mantic.eol_age(test_support::date("2024-07-11")));
+        assert_eq!(
+            Some(chrono::Duration::zero()),
+            mantic.eol_age(test_support::date("2024-07-12"))
+        );
+        assert_eq!(
+            Some(chrono::Duration::days(31)),
+            mantic.eol_age(test_support::date("2024-08-12"))
+        );
+        let date = test_support::date("2024-08-12");
+        let by_age = ubuntu_distro_info.unsupported_sorted_by_age(date);
+        assert_eq!(ubuntu_distro_info.unsupported(date).len(), by_age.len());
+        assert_eq!("warty", by_age[0].series());
+        assert_eq!("mantic", by_age[by_age.len() - 1].series());
+        assert!(by_age
+            .windows(2)
+            .all(|pair| pair[0].eol_age(date) >= pair[1].eol_age(date)));
+    }
+
+    #[test]
+    fn support_duration_and_class() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let series = |series| ubuntu_distro_info.lookup(series).unwrap();
+        assert_eq!(
+            Some(chrono::Duration::days(273)),
+            series("mantic").support_duration()
+        );
+        assert_eq!(
+            Some(SupportClass::Interim),
+            series("mantic").support_class()
+        );
+        assert_eq!(Some(SupportClass::Lts), series("lucid").support_class());
+        assert_eq!(Some(SupportClass::Esm), series("precise").support_class());
+        assert_eq!(Some(SupportClass::Elts), series("trusty").support_class());
+        let debian_distro_info = test_support::debian();
+        let debian = |series| debian_distro_info.lookup(series).unwrap();
+        assert_eq!(Some(SupportClass::Elts), debian("buster").support_class());
+        assert_eq!(None, debian("sid").support_class());
+    }
+
+    #[test]
+    fn codename_forms() {
+        let debian_distro_info = test_support::debian();
+        for codename in ["bookworm", "Bookworm", "BOOKWORM"] {
+            assert_eq!(
+                "bookworm",
+                debian_distro_info
+                    .release_by_codename(codename)
+                    .unwrap()
+                    .series()
+            );
+        }
+        let bookworm = debian_distro_info.release_by_codename("bookworm").unwrap();
+        assert_eq!("Bookworm", bookworm.display_codename());
+        assert_eq!(None, bookworm.codename_animal());
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info.release_by_codename("jellyfish").unwrap();
+        assert_eq!("jammy", jammy.series());
+        assert_eq!(Some("Jammy"), jammy.codename_adjective());
+        assert_eq!(Some("Jellyfish"), jammy.codename_animal());
+        let ubuntu_distro_info = test_support::ubuntu();
+        for codename in ["jammy", "Jammy", "Jammy Jellyfish", "jammy jellyfish"] {
+            assert_eq!(
+                "jammy",
+                ubuntu_distro_info
+                    .release_by_codename(codename)
+                    .unwrap()
+                    .series()
+            );
+        }
+        assert!(ubuntu_distro_info.release_by_codename("22.04").is_none());
+        assert_eq!(
+            "jammy",
+            ubuntu_distro_info.lookup("Jammy").unwrap().series()
+        );
+        let unnamed = test_support::fake_release("", "sid", "1993-08-16", None, None);
+        assert_eq!("Sid", unnamed.display_codename());
+    }
+
+    #[test]
+    fn support_overlap() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let series = |series| ubuntu_distro_info.lookup(series).unwrap();
+        assert_eq!(
+            Some((
+                test_support::date("2018-04-26"),
+                test_support::date("2021-04-30")
+            )),
+            series("xenial").support_overlap(series("bionic"))
+        );
+        assert_eq!(
+            series("xenial").support_overlap(series("bionic")),
+            series("bionic").support_overlap(series("xenial"))
+        );
+        assert_eq!(None, series("xenial").support_overlap(series("jammy")));
+
+        let debian_distro_info = test_support::debian();
+        let date = test_support::date("2024-06-01");
+        let pairs: Vec<_> = debian_distro_info
+            .concurrently_supported_pairs(date..=date)
+            .iter()
+            .map(|(older, newer)| (older.series().as_str(), newer.series().as_str()))
+            .collect();
+        assert_eq!(vec![("bullseye", "bookworm")], pairs);
+    }
+
+    #[test]
+    fn ubuntu_distro_info_in_development() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let series = |releases: Vec<&DistroRelease>| -> Vec<String> {
+            releases
+                .iter()
+                .map(|distro_release| distro_release.series().clone())
+                .collect()
+        };
+        assert_eq!(
+            vec!["xenial".to_string()],
+            series(ubuntu_distro_info.in_development_at(test_support::date("2016-04-20")))
+        );
+        assert_eq!(
+            vec!["yakkety".to_string()],
+            series(ubuntu_distro_info.in_development_at(test_support::date("2016-04-21")))
+        );
+        assert_eq!(
+            vec!["xenial".to_string(), "yakkety".to_string()],
+            series(ubuntu_distro_info.in_development_during(
+                test_support::date("2016-04-20")..=test_support::date("2016-04-21")
+            ))
+        );
+    }
+
+    #[test]
+    fn batch_resolver_matches_latest() {
+        for distro_info in [
+            &test_support::ubuntu() as &dyn DistroInfo,
+            &test_support::debian(),
+        ] {
+            let resolver = BatchResolver::new(distro_info);
+            let mut date = test_support::date("1993-01-01");
+            let mut dates = vec![];
+            while date < test_support::date("2040-01-01") {
+                assert_eq!(
+                    distro_info.latest(date).map(|r| r.series()),
+                    resolver.resolve(date).map(|r| r.series())
+                );
+                dates.push(date);
+                date += chrono::Duration::days(1);
+            }
+            let resolved = resolver.resolve_many(&dates);
+            assert_eq!(dates.len(), resolved.len());
+            assert_eq!(
+                distro_info.latest(dates[5000]).map(|r| r.series()),
+                resolved[5000].map(|r| r.series())
+            );
+        }
+    }
+
+    #[test]
+    fn support_status_for_codename() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let status = SupportStatus::for_codename(
+            &ubuntu_distro_info,
+            "xenial",
+            test_support::date("2022-01-01"),
+        )
+        .unwrap();
+        assert!(!status.supported());
+        assert!(status.esm_available());
+        assert_eq!(Some(-246), status.days_to_eol());
+        assert_eq!("yakkety", status.successor().unwrap().series());
+
+        let debian_distro_info = test_support::debian();
+        let status = SupportStatus::for_codename(
+            &debian_distro_info,
+            "Bookworm",
+            test_support::date("2024-01-01"),
+        )
+        .unwrap();
+        assert!(status.supported());
+        assert_eq!("trixie", status.successor().unwrap().series());
+
+        let status = SupportStatus::for_codename(
+            &ubuntu_distro_info,
+            "Jammy",
+            test_support::date("2024-01-01"),
+        )
+        .unwrap();
+        assert_eq!("jammy", status.release().series());
+        assert!(SupportStatus::for_codename_with(
+            &ubuntu_distro_info,
+            "Jammy",
+            crate::matching::MatchMode::Strict,
+            test_support::date("2024-01-01")
+        )
+        .is_none());
+
+        assert!(SupportStatus::for_codename(
+            &ubuntu_distro_info,
+            "nonesuch",
+            test_support::date("2024-01-01")
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn os_release_codename() {
+        assert_eq!(
+            Some("jammy".to_string()),
+            status::os_release_codename("NAME=\"Ubuntu\"\nVERSION_CODENAME=jammy\nID=ubuntu\n")
+        );
+        assert_eq!(
+            Some("bookworm".to_string()),
+            status::os_release_codename("VERSION_CODENAME=\"bookworm\"\n")
+        );
+        assert_eq!(
+            None,
+            status::os_release_codename("ID=debian\nVERSION_CODENAME=\n")
+        );
+    }
+
+    #[test]
+    fn successor_and_predecessor() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let releases = ubuntu_distro_info.releases();
+        assert_eq!(
+            "hoary",
+            ubuntu_distro_info.successor(&releases[0]).unwrap().series()
+        );
+        assert!(ubuntu_distro_info.predecessor(&releases[0]).is_none());
+        assert_eq!(
+            "warty",
+            ubuntu_distro_info
+                .predecessor(&releases[1])
+                .unwrap()
+                .series()
+        );
+        assert!(ubuntu_distro_info
+            .successor(releases.last().unwrap())
+            .is_none());
+        let debian_distro_info = test_support::debian();
+        let duke = debian_distro_info.lookup("duke").unwrap();
+        assert!(debian_distro_info.successor(duke).is_none());
+        assert_eq!(
+            "forky",
+            debian_distro_info.predecessor(duke).unwrap().series()
+        );
+        let sid = debian_distro_info.lookup("sid").unwrap();
+        assert!(debian_distro_info.successor(sid).is_none());
+        assert!(debian_distro_info.predecessor(sid).is_none());
+    }
+
+    #[test]
+    fn upgrade_supported() {
+        let check = |distro_info: &dyn DistroInfo, from, to| {
+            distro_info.upgrade_supported(
+                distro_info.lookup(from).unwrap(),
+                distro_info.lookup(to).unwrap(),
+            )
+        };
+        let ubuntu_distro_info = test_support::ubuntu();
+        assert_eq!(
+            UpgradeSupport::Supported,
+            check(&ubuntu_distro_info, "focal", "jammy")
+        );
+        assert_eq!(
+            UpgradeSupport::Supported,
+            check(&ubuntu_distro_info, "jammy", "kinetic")
+        );
+        assert_eq!(
+            UpgradeSupport::SkipsReleases,
+            check(&ubuntu_distro_info, "kinetic", "mantic")
+        );
+        assert_eq!(
+            UpgradeSupport::SkipsReleases,
+            check(&ubuntu_distro_info, "focal", "noble")
+        );
+        assert_eq!(
+            UpgradeSupport::NotAnUpgrade,
+            check(&ubuntu_distro_info, "jammy", "focal")
+        );
+        let debian_distro_info = test_support::debian();
+        assert_eq!(
+            UpgradeSupport::Supported,
+            check(&debian_distro_info, "bullseye", "bookworm")
+        );
+        assert_eq!(
+            UpgradeSupport::SkipsReleases,
+            check(&debian_distro_info, "buster", "bookworm")
+        );
+        assert_eq!(
+            UpgradeSupport::Unknown,
+            check(&debian_distro_info, "bookworm", "sid")
+        );
+    }
+
+    #[test]
+    fn upgrade_path() {
+        let series = |path: Option<Vec<&DistroRelease>>| -> Vec<String> {
+            path.unwrap()
+                .iter()
+                .map(|distro_release| distro_release.series().clone())
+                .collect()
+        };
+        let ubuntu_distro_info = test_support::ubuntu();
+        assert_eq!(
+            vec!["jammy", "noble"],
+            series(ubuntu_distro_info.upgrade_path("focal", "noble"))
+        );
+        assert_eq!(
+            vec!["jammy", "kinetic", "lunar"],
+            series(ubuntu_distro_info.upgrade_path("focal", "lunar"))
+        );
+        assert_eq!(
+            vec!["lunar", "mantic", "noble"],
+            series(ubuntu_distro_info.upgrade_path("kinetic", "noble"))
+        );
+        assert!(ubuntu_distro_info.upgrade_path("noble", "focal").is_none());
+        assert!(ubuntu_distro_info
+            .upgrade_path("focal", "nonesuch")
+            .is_none());
+
+        let debian_distro_info = test_support::debian();
+        assert_eq!(
+            vec!["bullseye", "bookworm"],
+            series(debian_distro_info.upgrade_path("buster", "bookworm"))
+        );
+        assert!(debian_distro_info.upgrade_path("duke", "sid").is_none());
+    }
+
+    #[test]
+    fn all_series_codenames_and_versions() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let all_series = ubuntu_distro_info.all_series();
+        assert_eq!(ubuntu_distro_info.releases().len(), all_series.len());
+        assert_eq!(&["warty", "hoary"], &all_series[..2]);
+        assert_eq!("Warty Warthog", ubuntu_distro_info.all_codenames()[0]);
+        assert_eq!("6.06 LTS", ubuntu_distro_info.all_versions()[3]);
+        let debian_distro_info = test_support::debian();
+        // sid and experimental have no version
+        assert_eq!(
+            debian_distro_info.releases().len() - 2,
+            debian_distro_info.all_versions().len()
+        );
+    }
+
+    #[test]
+    fn ubuntu_distro_info_lookup() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let lookup = |needle| {
+            ubuntu_distro_info
+                .lookup(needle)
+                .map(|distro_release| distro_release.series().as_str())
+        };
+        assert_eq!(Some("jammy"), lookup("jammy"));
+        assert_eq!(Some("jammy"), lookup("jammy jellyfish"));
+        assert_eq!(Some("jammy"), lookup("JAMMY"));
+        assert_eq!(Some("jammy"), lookup("22.04 lts"));
+        assert!(ubuntu_distro_info
+            .lookup_with("Jammy", crate::matching::MatchMode::Strict)
+            .is_none());
+        assert_eq!(Some("jammy"), lookup("22.04"));
+        assert_eq!(Some("jammy"), lookup("22.04 LTS"));
+        assert_eq!(Some("kinetic"), lookup("22.10"));
+        assert_eq!(None, lookup("22.11"));
+    }
+
+    #[test]
+    fn planned_releases() {
+        let debian_distro_info = DebianDistroInfo::from_csv_str(
+            "version,codename,series,created,release,eol\n\
+             12,Bookworm,bookworm,2021-08-14,2023-06-10,\n\
+             13,Trixie,trixie,2023-06-10,,\n\
+             14,Forky,forky,,,\n\
+             ,Sid,sid,1993-08-16,,\n",
+        )
+        .unwrap();
+        let planned: Vec<_> = debian_distro_info
+            .planned(test_support::date("2024-01-01"))
+            .into_iter()
+            .map(|distro_release| distro_release.series().as_str())
+            .collect();
+        assert_eq!(vec!["trixie", "forky"], planned);
+        assert!(debian_distro_info
+            .planned(test_support::date("2022-01-01"))
+            .iter()
+            .any(|distro_release| distro_release.series() == "bookworm"));
+    }
+
+    #[test]
+    fn ubuntu_distro_info_upcoming_milestones() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let upcoming: Vec<_> = ubuntu_distro_info
+            .upcoming_milestones(test_support::date("2024-04-01"), 60)
+            .into_iter()
+            .map(|(milestone, distro_release, date)| {
+                (milestone.name(), distro_release.series().as_str(), date)
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("eol-esm", "trusty", test_support::date("2024-04-25")),
+                ("release", "noble", test_support::date("2024-04-25")),
+                ("created", "oracular", test_support::date("2024-04-25")),
+            ],
+            upcoming
+        );
+        assert!(ubuntu_distro_info
+            .upcoming_milestones(test_support::date("2024-04-01"), 0)
+            .is_empty());
+    }
+
+    #[test]
+    fn is_probably_stale() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let newest = ubuntu_distro_info
+            .iter()
+            .filter_map(|distro_release| *distro_release.created())
+            .max()
+            .unwrap();
+        assert!(!ubuntu_distro_info.is_probably_stale(newest + chrono::Duration::days(214)));
+        assert!(ubuntu_distro_info.is_probably_stale(newest + chrono::Duration::days(215)));
+        assert!(UbuntuDistroInfo::from_vec(vec![]).is_probably_stale(newest));
+        assert!(!test_support::debian().is_probably_stale(test_support::date("2024-01-01")));
+    }
+
+    #[test]
+    fn archive_opened() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let noble = ubuntu_distro_info.lookup("noble").unwrap();
+        assert_eq!(noble.created(), noble.archive_opened());
+        assert_ne!(noble.release(), noble.archive_opened());
+        assert_eq!(
+            None,
+            noble.since_devel_open(test_support::date("2023-10-11"))
+        );
+        assert_eq!(
+            Some(chrono::Duration::zero()),
+            noble.since_devel_open(test_support::date("2023-10-12"))
+        );
+        assert_eq!(
+            Some(chrono::Duration::days(196)),
+            noble.since_devel_open(test_support::date("2024-04-25"))
+        );
+    }
+
+    #[test]
+    fn rolling_and_permanent_suites() {
+        let debian_distro_info = test_support::debian();
+        let permanent: Vec<_> = debian_distro_info
+            .iter()
+            .filter(|distro_release| distro_release.is_permanent())
+            .map(|distro_release| distro_release.series().as_str())
+            .collect();
+        assert_eq!(vec!["sid", "experimental"], permanent);
+        assert!(!test_support::ubuntu()
+            .iter()
+            .any(|distro_release| distro_release.is_rolling()));
+
+        let date = test_support::date("2024-01-01");
+        let series = |distro_releases: Vec<&DistroRelease>| -> Vec<String> {
+            distro_releases
+                .iter()
+                .map(|distro_release| distro_release.series().clone())
+                .collect()
+        };
+        assert_eq!(
+            vec!["bullseye", "bookworm", "trixie", "sid", "experimental"],
+            series(debian_distro_info.supported(date))
+        );
+        assert!(!series(debian_distro_info.unsupported(date)).contains(&"sid".to_string()));
+        assert_eq!(vec!["sid"], series(debian_distro_info.debian_devel(date)));
+        assert_eq!(
+            vec!["trixie"],
+            series(debian_distro_info.ubuntu_devel(date))
+        );
+        // Before sid was created, there was no development suite
+        assert!(debian_distro_info
+            .debian_devel(test_support::date("1993-08-15"))
+            .is_empty());
+    }
+
+    #[test]
+    fn heterogeneous_distros() {
+        let all: Vec<Box<dyn DistroInfo>> = Distro::ALL
+            .iter()
+            .map(|distro| match distro {
+                Distro::Ubuntu => distro.distro_info(test_support::ubuntu().releases().to_vec()),
+                Distro::Debian => distro.distro_info(test_support::debian().releases().to_vec()),
+                Distro::Derivative(_) => unreachable!("Distro::ALL only has built-in distros"),
+            })
+            .collect();
+        let date = test_support::date("2024-01-01");
+        let latest: Vec<_> = all
+            .iter()
+            .map(|distro_info| {
+                (
+                    *distro_info.distro(),
+                    distro_info.latest(date).unwrap().series().as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![(Distro::Debian, "bookworm"), (Distro::Ubuntu, "mantic")],
+            latest
+        );
+    }
+
+    #[test]
+    fn ubuntu_distro_info_merge() {
+        let overlay = UbuntuDistroInfo::from_csv_str(
+            "version,codename,series,created,release,eol,eol-esm,support-contract\n\
+             ,,xenial,,,,2030-04-30,internal-42\n\
+             99.04,Imaginary Ibex,imaginary,2098-10-01,2099-04-20,2100-01-01,,\n",
+        )
+        .unwrap();
+        let base = test_support::ubuntu();
+        let merged = base.merge(overlay);
+        assert_eq!(base.releases().len() + 1, merged.releases().len());
+        let xenial = merged.lookup("xenial").unwrap();
+        assert_eq!(&Some(test_support::date("2030-04-30")), xenial.eol_esm());
+        assert_eq!(&Some(test_support::date("2021-04-30")), xenial.eol());
+        assert_eq!("Xenial Xerus", xenial.codename());
+        assert_eq!(
+            Some(&"internal-42".to_string()),
+            xenial.extra_fields().get("support-contract")
+        );
+        assert_eq!("imaginary", merged.releases().last().unwrap().series());
+    }
+
+    #[test]
+    fn ubuntu_distro_info_suggest_series() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        assert_eq!(vec!["focal"], ubuntu_distro_info.suggest_series("foca"));
+        assert_eq!(vec!["focal"], ubuntu_distro_info.suggest_series("focla"));
+        assert_eq!(vec!["jammy"], ubuntu_distro_info.suggest_series("JAMY"));
+        assert!(ubuntu_distro_info.suggest_series("focal").is_empty());
+        assert!(ubuntu_distro_info.suggest_series("zzzzzzzz").is_empty());
+    }
+
     #[test]
     fn ubuntu_distro_info_iters_are_separate() {
-        let ubuntu_distro_info = UbuntuDistroInfo::new().unwrap();
+        let ubuntu_distro_info = test_support::ubuntu();
         let mut iter1 = ubuntu_distro_info.iter();
         let mut iter2 = ubuntu_distro_info.iter();
         assert_eq!(iter1.next().unwrap().series, iter2.next().unwrap().series);
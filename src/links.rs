@@ -0,0 +1,145 @@
+//! Links to the announcement, release notes and upgrade notes for a release.
+//!
+//! The URLs are generated from per-distro templates, so releases added to distro-info-data after
+//! this crate was built get links too.  Templates may contain these placeholders:
+//!
+//! * `{series}`: the series, e.g. "jammy"
+//! * `{Codename}`: the codename without spaces, e.g. "JammyJellyfish"
+//! * `{Series}`: the series with its first letter capitalised, e.g. "Jammy"
+//! * `{year}` and `{yyyymmdd}`: from the release date
+use alloc::string::{String, ToString};
+
+use crate::{Distro, DistroRelease};
+
+const UBUNTU_ANNOUNCEMENT: &str = "https://wiki.ubuntu.com/{Codename}/ReleaseAnnouncement";
+const UBUNTU_RELEASE_NOTES: &str = "https://wiki.ubuntu.com/{Codename}/ReleaseNotes";
+const UBUNTU_UPGRADE_NOTES: &str = "https://help.ubuntu.com/community/{Series}Upgrades";
+const DEBIAN_ANNOUNCEMENT: &str = "https://www.debian.org/News/{year}/{yyyymmdd}";
+const DEBIAN_RELEASE_NOTES: &str = "https://www.debian.org/releases/{series}/releasenotes";
+const DEBIAN_UPGRADE_NOTES: &str =
+    "https://www.debian.org/releases/{series}/amd64/release-notes/ch-upgrading.html";
+
+/// The documentation links for a release
+///
+/// A link is `None` if its template needs information the release lacks (e.g. the release date
+/// of a release still in development), or if the release is a rolling suite with no releases of
+/// its own (e.g. Debian's sid).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReleaseLinks {
+    pub announcement: Option<String>,
+    pub release_notes: Option<String>,
+    pub upgrade_notes: Option<String>,
+}
+
+impl ReleaseLinks {
+    /// The links which are known, in the order announcement, release notes, upgrade notes
+    pub fn known(&self) -> impl Iterator<Item = &str> {
+        [&self.announcement, &self.release_notes, &self.upgrade_notes]
+            .into_iter()
+            .flatten()
+            .map(|link| link.as_str())
+    }
+}
+
+/// Returns the links for `distro_release`, a release of `distro`
+pub fn links(distro: &Distro, distro_release: &DistroRelease) -> ReleaseLinks {
+    let (announcement, release_notes, upgrade_notes) = match distro {
+        Distro::Ubuntu => (
+            UBUNTU_ANNOUNCEMENT,
+            UBUNTU_RELEASE_NOTES,
+            UBUNTU_UPGRADE_NOTES,
+        ),
+        Distro::Debian => (
+            DEBIAN_ANNOUNCEMENT,
+            DEBIAN_RELEASE_NOTES,
+            DEBIAN_UPGRADE_NOTES,
+        ),
+        Distro::Derivative(_) => return ReleaseLinks::default(),
+    };
+    if distro_release.version().is_none() {
+        return ReleaseLinks::default();
+    }
+    ReleaseLinks {
+        announcement: expand(announcement, distro_release),
+        release_notes: expand(release_notes, distro_release),
+        upgrade_notes: expand(upgrade_notes, distro_release),
+    }
+}
+
+/// Expand the placeholders in `template` for `distro_release`
+///
+/// Returns `None` if a placeholder's value is unknown.
+pub fn expand(template: &str, distro_release: &DistroRelease) -> Option<String> {
+    let mut expanded = template.to_string();
+    if expanded.contains("{series}") {
+        expanded = expanded.replace("{series}", distro_release.series());
+    }
+    if expanded.contains("{Series}") {
+        let mut series = distro_release.series().clone();
+        if let Some(first) = series.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        expanded = expanded.replace("{Series}", &series);
+    }
+    if expanded.contains("{Codename}") {
+        let codename: String = distro_release
+            .codename()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        expanded = expanded.replace("{Codename}", &codename);
+    }
+    if expanded.contains("{year}") || expanded.contains("{yyyymmdd}") {
+        let release = (*distro_release.release())?;
+        expanded = expanded
+            .replace("{year}", &release.format("%Y").to_string())
+            .replace("{yyyymmdd}", &release.format("%Y%m%d").to_string());
+    }
+    Some(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::links;
+    use crate::{test_support, Distro, DistroInfo};
+
+    #[test]
+    fn ubuntu_links() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info
+            .iter()
+            .find(|distro_release| distro_release.series() == "jammy")
+            .unwrap();
+        let jammy_links = links(&Distro::Ubuntu, jammy);
+        assert_eq!(
+            Some("https://wiki.ubuntu.com/JammyJellyfish/ReleaseNotes"),
+            jammy_links.release_notes.as_deref()
+        );
+        assert_eq!(
+            Some("https://help.ubuntu.com/community/JammyUpgrades"),
+            jammy_links.upgrade_notes.as_deref()
+        );
+    }
+
+    #[test]
+    fn debian_links() {
+        let debian_distro_info = test_support::debian();
+        let bookworm = debian_distro_info
+            .iter()
+            .find(|distro_release| distro_release.series() == "bookworm")
+            .unwrap();
+        assert_eq!(
+            vec![
+                "https://www.debian.org/News/2023/20230610",
+                "https://www.debian.org/releases/bookworm/releasenotes",
+                "https://www.debian.org/releases/bookworm/amd64/release-notes/ch-upgrading.html",
+            ],
+            links(&Distro::Debian, bookworm).known().collect::<Vec<_>>()
+        );
+        let sid = debian_distro_info
+            .iter()
+            .find(|distro_release| distro_release.series() == "sid")
+            .unwrap();
+        assert_eq!(0, links(&Distro::Debian, sid).known().count());
+    }
+}
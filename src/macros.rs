@@ -0,0 +1,130 @@
+//! [`define_distro!`](../macro.define_distro.html), for defining `DistroInfo` types.
+
+/// Re-exports for use in macro expansions, so that they work in crates without `extern crate
+/// alloc`
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::{IntoIter, Vec};
+}
+
+/// Define a `DistroInfo` type for a distro's release data
+///
+/// This generates the struct, its `DistroQueries` implementation (with a lazily-built
+/// [lookup index](index/index.html)), its `DistroDataSource` implementation and `IntoIterator`.
+/// `policy` gives the distro's `DistroPolicy` (as a `&'static dyn DistroPolicy`), whose
+/// `display_name` names it, and `csv_path` the file `DistroDataSource::new` reads.  The type's
+/// `distro` is then a `Distro::Derivative`, so it doesn't pass for any of the built-in distros.
+/// Columns are mapped by the names in the file's header, so no column schema is needed: columns
+/// beyond the standard ones are kept in each release's `extra_fields`.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use distro_info::policy::{DebianPolicy, DistroPolicy};
+/// use distro_info::{define_distro, Distro, DistroDataSource, DistroInfo, DistroRelease};
+///
+/// /// Kali Linux, whose development works as Debian's does
+/// struct KaliPolicy;
+///
+/// impl DistroPolicy for KaliPolicy {
+///     fn display_name(&self) -> &str {
+///         "Kali"
+///     }
+///
+///     fn devel<'a>(
+///         &self,
+///         distro_info: &'a dyn DistroInfo,
+///         date: NaiveDate,
+///     ) -> Vec<&'a DistroRelease> {
+///         DebianPolicy.devel(distro_info, date)
+///     }
+/// }
+///
+/// define_distro! {
+///     /// Kali Linux's releases
+///     pub struct KaliDistroInfo {
+///         policy: &KaliPolicy,
+///         csv_path: "/usr/share/distro-info/kali.csv",
+///     }
+/// }
+///
+/// let kali = KaliDistroInfo::from_csv_str(
+///     "version,codename,series,created,release,eol\n\
+///      2024.1,Kali 2024.1,kali-2024-1,2024-01-01,2024-02-27,2024-05-15\n",
+/// )
+/// .unwrap();
+/// assert_eq!("Kali", kali.distro().display_name());
+/// assert_ne!(&Distro::Debian, kali.distro());
+/// let release = kali.lookup("kali-2024-1").unwrap();
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+/// assert_eq!(None, release.archive_url(kali.distro(), date));
+/// assert!(release.links(kali.distro()).announcement.is_none());
+/// ```
+///
+/// The crate's own types are defined with `distro:` and their built-in `Distro` instead of
+/// `policy:`.
+#[macro_export]
+macro_rules! define_distro {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            policy: $policy:expr,
+            csv_path: $csv_path:expr $(,)?
+        }
+    ) => {
+        $crate::define_distro! {
+            $(#[$attr])*
+            $vis struct $name {
+                distro: $crate::Distro::Derivative($policy),
+                csv_path: $csv_path,
+            }
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            distro: $distro:expr,
+            csv_path: $csv_path:expr $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            releases: $crate::macros::__private::Vec<$crate::DistroRelease>,
+            index: $crate::index::LazyIndex,
+        }
+
+        impl $crate::DistroQueries for $name {
+            fn distro(&self) -> &$crate::Distro {
+                const DISTRO: $crate::Distro = $distro;
+                &DISTRO
+            }
+            fn releases(&self) -> &[$crate::DistroRelease] {
+                &self.releases
+            }
+            fn index(&self) -> Option<&$crate::index::ReleaseIndex> {
+                self.index.get(&self.releases)
+            }
+        }
+
+        impl $crate::DistroDataSource for $name {
+            fn csv_path() -> &'static str {
+                $csv_path
+            }
+            #[doc = concat!("Initialise a ", stringify!($name), " struct from a vector of DistroReleases")]
+            fn from_vec(releases: $crate::macros::__private::Vec<$crate::DistroRelease>) -> Self {
+                Self {
+                    releases,
+                    index: Default::default(),
+                }
+            }
+        }
+
+        impl IntoIterator for $name {
+            type Item = $crate::DistroRelease;
+            type IntoIter = $crate::macros::__private::IntoIter<$crate::DistroRelease>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.releases.into_iter()
+            }
+        }
+    };
+}
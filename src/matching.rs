@@ -0,0 +1,77 @@
+//! Matching user input against series and codenames.
+//!
+//! Input from web forms and the like rarely matches the data exactly, so by default matching
+//! ignores case and (common Latin) diacritics: "Focal", "FOCAL" and "fócal" all match "focal".
+//! [`MatchMode::Strict`](enum.MatchMode.html#variant.Strict) requires an exact match instead.
+use alloc::string::String;
+
+/// How user input is compared with the data
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Values must be identical
+    Strict,
+    /// Values are compared after `fold`ing
+    #[default]
+    Relaxed,
+}
+
+impl MatchMode {
+    /// Whether `needle` matches `value`
+    pub fn matches(&self, value: &str, needle: &str) -> bool {
+        match self {
+            MatchMode::Strict => value == needle,
+            MatchMode::Relaxed => value == needle || fold(value) == fold(needle),
+        }
+    }
+}
+
+/// The series a codename refers to, in any of the forms users give it
+///
+/// Debian's codenames are capitalised series ("Bookworm" for "bookworm"), and Ubuntu's are the
+/// series followed by an animal ("Jammy Jellyfish" for "jammy"), so this `fold`s `codename` and
+/// keeps its first word.
+pub fn normalize_codename(codename: &str) -> String {
+    fold(codename.split_whitespace().next().unwrap_or(""))
+}
+
+/// Lower-case `value` and strip the diacritics from any Latin-1 letters in it
+pub fn fold(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'ç' => 'c',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ñ' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ý' | 'ÿ' => 'y',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fold, normalize_codename, MatchMode};
+
+    #[test]
+    fn relaxed_ignores_case_and_diacritics() {
+        assert_eq!("focal fossa", fold("Fócal FOSSA"));
+        assert!(MatchMode::Relaxed.matches("focal", "FOCAL"));
+        assert!(MatchMode::Relaxed.matches("Jammy Jellyfish", "jammy jellyfísh"));
+        assert!(!MatchMode::Relaxed.matches("focal", "foca"));
+        assert!(!MatchMode::Strict.matches("focal", "Focal"));
+        assert!(MatchMode::Strict.matches("focal", "focal"));
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!("bookworm", normalize_codename("Bookworm"));
+        assert_eq!("jammy", normalize_codename(" Jammy Jellyfish"));
+        assert_eq!("jammy", normalize_codename("jammy"));
+        assert_eq!("", normalize_codename(""));
+    }
+}
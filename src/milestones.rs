@@ -0,0 +1,227 @@
+//! The dated milestones in a release's lifecycle, e.g. for reminders of upcoming EOLs.
+//!
+//! [`DistroQueries::upcoming_milestones`](../trait.DistroQueries.html#method.upcoming_milestones)
+//! lists the milestones of all releases which fall within a number of days of a date.
+//!
+//! Where `DistroRelease::created_at` and friends answer `false` whenever a date is missing,
+//! [`Milestone::state`](enum.Milestone.html#method.state) returns a
+//! [`KnownState`](enum.KnownState.html), which tells a date not yet being known apart from the
+//! milestone never applying (as a release never applies to Debian's sid).
+use alloc::vec::Vec;
+
+use chrono::naive::NaiveDate;
+
+use crate::DistroRelease;
+
+/// Whether a release had reached a milestone at a date, distinguishing the reasons it may be
+/// impossible to say
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KnownState {
+    /// The milestone's date had been reached
+    Reached,
+    /// The milestone's date had not been reached
+    NotReached,
+    /// The milestone's date is not (yet) in the data
+    Unknown,
+    /// The milestone never applies to the release, e.g. the release of a rolling suite
+    NotApplicable,
+}
+
+impl KnownState {
+    /// Whether the milestone had been reached, or `None` if that isn't known
+    pub fn known(self) -> Option<bool> {
+        match self {
+            KnownState::Reached => Some(true),
+            KnownState::NotReached => Some(false),
+            KnownState::Unknown | KnownState::NotApplicable => None,
+        }
+    }
+}
+
+/// A dated event in the lifecycle of a release
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Milestone {
+    Created,
+    Release,
+    Eol,
+    EolServer,
+    EolLts,
+    EolElts,
+    EolEsm,
+    EolLegacy,
+}
+
+impl Milestone {
+    /// All milestones, in the order they usually occur
+    pub const ALL: [Milestone; 8] = [
+        Milestone::Created,
+        Milestone::Release,
+        Milestone::Eol,
+        Milestone::EolServer,
+        Milestone::EolLts,
+        Milestone::EolEsm,
+        Milestone::EolElts,
+        Milestone::EolLegacy,
+    ];
+
+    /// The name of the milestone, as used on the command line (e.g. "eol-server")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Milestone::Created => "created",
+            Milestone::Release => "release",
+            Milestone::Eol => "eol",
+            Milestone::EolServer => "eol-server",
+            Milestone::EolLts => "eol-lts",
+            Milestone::EolElts => "eol-elts",
+            Milestone::EolEsm => "eol-esm",
+            Milestone::EolLegacy => "eol-legacy",
+        }
+    }
+
+    /// The milestone with the given `name`, if any
+    pub fn from_name(name: &str) -> Option<Milestone> {
+        Milestone::ALL
+            .iter()
+            .find(|milestone| milestone.name() == name)
+            .copied()
+    }
+
+    /// The date of this milestone for `distro_release`, if known
+    pub fn date(&self, distro_release: &DistroRelease) -> Option<NaiveDate> {
+        *match self {
+            Milestone::Created => distro_release.created(),
+            Milestone::Release => distro_release.release(),
+            Milestone::Eol => distro_release.eol(),
+            Milestone::EolServer => distro_release.eol_server(),
+            Milestone::EolLts => distro_release.eol_lts(),
+            Milestone::EolElts => distro_release.eol_elts(),
+            Milestone::EolEsm => distro_release.eol_esm(),
+            Milestone::EolLegacy => distro_release.eol_legacy(),
+        }
+    }
+
+    /// Whether `distro_release` had reached this milestone at `date`
+    ///
+    /// Rolling suites are never released, and permanent ones (such as sid) never reach their end
+    /// of life, so those milestones are `NotApplicable` to them rather than `Unknown`.
+    pub fn state(&self, distro_release: &DistroRelease, date: NaiveDate) -> KnownState {
+        match self.date(distro_release) {
+            Some(milestone_date) if date >= milestone_date => KnownState::Reached,
+            Some(_) => KnownState::NotReached,
+            None => match self {
+                Milestone::Release if distro_release.is_rolling() => KnownState::NotApplicable,
+                Milestone::Eol if distro_release.is_permanent() => KnownState::NotApplicable,
+                _ => KnownState::Unknown,
+            },
+        }
+    }
+}
+
+impl DistroRelease {
+    /// The milestones of this release whose dates are known, in chronological order
+    pub fn milestones(&self) -> Vec<(Milestone, NaiveDate)> {
+        let mut milestones: Vec<_> = Milestone::ALL
+            .iter()
+            .filter_map(|milestone| milestone.date(self).map(|date| (*milestone, date)))
+            .collect();
+        milestones.sort_by_key(|(_, date)| *date);
+        milestones
+    }
+
+    /// Set the date of `milestone` for this release (or clear it, if `date` is `None`)
+    pub fn with_field(mut self, milestone: Milestone, date: Option<NaiveDate>) -> Self {
+        *match milestone {
+            Milestone::Created => &mut self.created,
+            Milestone::Release => &mut self.release,
+            Milestone::Eol => &mut self.eol,
+            Milestone::EolServer => &mut self.eol_server,
+            Milestone::EolLts => &mut self.eol_lts,
+            Milestone::EolElts => &mut self.eol_elts,
+            Milestone::EolEsm => &mut self.eol_esm,
+            Milestone::EolLegacy => &mut self.eol_legacy,
+        } = date;
+        self
+    }
+
+    /// Like `created_at`, but distinguishing an unknown creation date; see `Milestone::state`
+    pub fn created_state(&self, date: NaiveDate) -> KnownState {
+        Milestone::Created.state(self, date)
+    }
+
+    /// Like `released_at`, but distinguishing an unknown release date from a release which never
+    /// happens; see `Milestone::state`
+    pub fn released_state(&self, date: NaiveDate) -> KnownState {
+        Milestone::Release.state(self, date)
+    }
+
+    /// Whether the (desktop) end of life had been reached at `date`, distinguishing an unknown
+    /// date from a suite which never reaches its end of life; see `Milestone::state`
+    pub fn eol_state(&self, date: NaiveDate) -> KnownState {
+        Milestone::Eol.state(self, date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KnownState, Milestone};
+    use crate::test_support;
+
+    #[test]
+    fn milestones_are_chronological() {
+        let xenial = test_support::fake_release(
+            "16.04 LTS",
+            "xenial",
+            "2015-10-22",
+            Some("2016-04-21"),
+            Some("2021-04-30"),
+        );
+        assert_eq!(
+            vec![
+                (Milestone::Created, test_support::date("2015-10-22")),
+                (Milestone::Release, test_support::date("2016-04-21")),
+                (Milestone::Eol, test_support::date("2021-04-30")),
+            ],
+            xenial.milestones()
+        );
+    }
+
+    #[test]
+    fn with_field_sets_milestones() {
+        let xenial = crate::DistroRelease::named(
+            "16.04 LTS".to_string(),
+            "Xenial Xerus".to_string(),
+            "xenial".to_string(),
+        );
+        assert!(xenial.milestones().is_empty());
+        let xenial = Milestone::ALL.iter().fold(xenial, |xenial, milestone| {
+            xenial.with_field(*milestone, Some(test_support::date("2016-04-21")))
+        });
+        for milestone in Milestone::ALL {
+            assert_eq!(
+                Some(test_support::date("2016-04-21")),
+                milestone.date(&xenial)
+            );
+        }
+        let xenial = xenial.with_field(Milestone::EolLegacy, None);
+        assert_eq!(None, *xenial.eol_legacy());
+        assert_eq!(Some("16.04 LTS"), xenial.version().as_deref());
+    }
+
+    #[test]
+    fn known_states() {
+        let date = test_support::date("2024-01-01");
+        let sid = test_support::fake_release("", "sid", "1993-08-16", None, None);
+        assert_eq!(KnownState::Reached, sid.created_state(date));
+        assert_eq!(KnownState::NotApplicable, sid.released_state(date));
+        assert_eq!(KnownState::NotApplicable, sid.eol_state(date));
+        let forky = test_support::fake_release("14", "forky", "2025-08-09", None, None);
+        assert_eq!(KnownState::NotReached, forky.created_state(date));
+        assert_eq!(KnownState::Unknown, forky.released_state(date));
+        assert_eq!(None, forky.released_state(date).known());
+        let bookworm =
+            test_support::fake_release("12", "bookworm", "2021-08-14", Some("2023-06-10"), None);
+        assert_eq!(Some(true), bookworm.released_state(date).known());
+        assert_eq!(KnownState::Unknown, bookworm.eol_state(date));
+    }
+}
@@ -0,0 +1,507 @@
+//! A dependency-light parser for distro-info-data CSV files.
+//!
+//! This only needs `core` and `alloc`, so it is available even when the `std` feature is disabled
+//! (for example, for firmware provisioning tools which embed the release data in their image).
+//! It takes the whole file as a `&str`; the distro-info-data files contain no quoted fields, so
+//! a full CSV implementation is not required.
+//!
+//! Parsing is defensive, as the input may come from untrusted sources: malformed input results
+//! in a [`ParseError`](enum.ParseError.html), never a panic, and implausible dates, overly long
+//! fields, NUL characters and duplicate series are all rejected.
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use chrono::naive::NaiveDate;
+
+use crate::DistroRelease;
+
+/// The columns which must be present in the header of a distro-info-data CSV file
+const REQUIRED_COLUMNS: [&str; 3] = ["version", "codename", "series"];
+
+/// The columns which are parsed into `DistroRelease` fields; any others are kept in its
+/// `extra_fields`
+pub const KNOWN_COLUMNS: [&str; 11] = [
+    "version",
+    "codename",
+    "series",
+    "created",
+    "release",
+    "eol",
+    "eol-lts",
+    "eol-elts",
+    "eol-esm",
+    "eol-server",
+    "eol-legacy",
+];
+
+/// The longest field accepted, in bytes
+pub const MAX_FIELD_LENGTH: usize = 1024;
+
+/// The range of years accepted in dates
+pub const YEARS: core::ops::RangeInclusive<i32> = 1990..=2199;
+
+/// An error encountered while parsing distro-info-data CSV
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The input contained no header line
+    MissingHeader,
+    /// The header does not contain the named column
+    MissingColumn(&'static str),
+    /// The given (1-indexed) line has no value for the named column
+    MissingField { line: usize, column: &'static str },
+    /// The given (1-indexed) line contains a value that is not a YYYY-MM-DD date within `YEARS`
+    InvalidDate { line: usize, value: String },
+    /// The given (1-indexed) line contains a field longer than `MAX_FIELD_LENGTH`
+    FieldTooLong { line: usize },
+    /// The given (1-indexed) line contains a NUL character
+    NulCharacter { line: usize },
+    /// The given (1-indexed) line repeats the series of an earlier line
+    DuplicateSeries { line: usize, series: String },
+    /// The given (1-indexed) line is not valid CSV (e.g. it has an unterminated quote)
+    Malformed { line: usize, message: String },
+    /// The header names the same column more than once
+    DuplicateColumn(String),
+    /// The given (1-indexed) line's date for the column `later` is before that for `earlier`
+    /// (only checked when loading with `LoadOptions`)
+    OutOfOrder {
+        line: usize,
+        earlier: &'static str,
+        later: &'static str,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingHeader => write!(f, "no header line found"),
+            ParseError::MissingColumn(column) => write!(f, "missing required column '{}'", column),
+            ParseError::MissingField { line, column } => {
+                write!(f, "line {}: missing value for column '{}'", line, column)
+            }
+            ParseError::InvalidDate { line, value } => {
+                write!(f, "line {}: invalid date '{}'", line, value)
+            }
+            ParseError::FieldTooLong { line } => write!(
+                f,
+                "line {}: field longer than {} bytes",
+                line, MAX_FIELD_LENGTH
+            ),
+            ParseError::NulCharacter { line } => write!(f, "line {}: NUL character", line),
+            ParseError::DuplicateSeries { line, series } => {
+                write!(f, "line {}: duplicate series '{}'", line, series)
+            }
+            ParseError::Malformed { line, message } => write!(f, "line {}: {}", line, message),
+            ParseError::DuplicateColumn(column) => write!(f, "duplicate column '{}'", column),
+            ParseError::OutOfOrder {
+                line,
+                earlier,
+                later,
+            } => write!(f, "line {}: '{}' is before '{}'", line, later, earlier),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// A recoverable issue in distro-info-data CSV, which doesn't prevent it being loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// The header names a column which isn't one of `KNOWN_COLUMNS` (its values are kept in
+    /// `extra_fields`)
+    UnknownColumn(String),
+    /// The given (1-indexed) line has an empty value for the named optional column (lines
+    /// shorter than the header are not warned about)
+    BlankField { line: usize, column: String },
+    /// The given (1-indexed) line's value for the named column ends with whitespace
+    TrailingWhitespace { line: usize, column: String },
+    /// As `ParseError::OutOfOrder`, when loading with `Strictness::Lenient`
+    OutOfOrder {
+        line: usize,
+        earlier: &'static str,
+        later: &'static str,
+    },
+    /// As `ParseError::DuplicateSeries`, when loading with `Strictness::Lenient`; the earlier
+    /// line is kept
+    DuplicateSeries { line: usize, series: String },
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseWarning::UnknownColumn(column) => write!(f, "unknown column '{}'", column),
+            ParseWarning::BlankField { line, column } => {
+                write!(f, "line {}: no value for column '{}'", line, column)
+            }
+            ParseWarning::TrailingWhitespace { line, column } => {
+                write!(
+                    f,
+                    "line {}: trailing whitespace in column '{}'",
+                    line, column
+                )
+            }
+            ParseWarning::OutOfOrder {
+                line,
+                earlier,
+                later,
+            } => write!(f, "line {}: '{}' is before '{}'", line, later, earlier),
+            ParseWarning::DuplicateSeries { line, series } => {
+                write!(f, "line {}: duplicate series '{}'", line, series)
+            }
+        }
+    }
+}
+
+/// Parse a date in the YYYY-MM-DD format used by distro-info-data
+///
+/// Returns `None` unless `value` is exactly of that form, and its year is within `YEARS`.
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    let bytes = value.as_bytes();
+    let well_formed = bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+    if !well_formed {
+        return None;
+    }
+    let year = value[0..4]
+        .parse()
+        .ok()
+        .filter(|year| YEARS.contains(year))?;
+    let month = value[5..7].parse().ok()?;
+    let day = value[8..10].parse().ok()?;
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Check that `value`, from the given line, is neither too long nor contains a NUL
+pub(crate) fn check_field(value: &str, line: usize) -> Result<&str, ParseError> {
+    if value.len() > MAX_FIELD_LENGTH {
+        Err(ParseError::FieldTooLong { line })
+    } else if value.contains('\0') {
+        Err(ParseError::NulCharacter { line })
+    } else {
+        Ok(value)
+    }
+}
+
+/// Callbacks deciding which rows of a file are loaded as releases, e.g. to leave out the ports or
+/// unofficial variants some derivatives' files include
+///
+/// A row is kept if it matches every `include` callback and no `exclude` callback.  Rows are
+/// filtered as they are parsed, before duplicate series are rejected, so rows which are left out
+/// may reuse the series of others.
+#[derive(Default)]
+pub struct RowFilter {
+    includes: Vec<RowPredicate>,
+    excludes: Vec<RowPredicate>,
+}
+
+/// A callback given a parsed row
+type RowPredicate = Box<dyn Fn(&DistroRelease) -> bool>;
+
+impl RowFilter {
+    /// A filter which keeps every row
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only rows for which `include` returns `true`
+    pub fn include<F: Fn(&DistroRelease) -> bool + 'static>(mut self, include: F) -> Self {
+        self.includes.push(Box::new(include));
+        self
+    }
+
+    /// Leave out rows for which `exclude` returns `true`
+    pub fn exclude<F: Fn(&DistroRelease) -> bool + 'static>(mut self, exclude: F) -> Self {
+        self.excludes.push(Box::new(exclude));
+        self
+    }
+
+    /// Leave out the rows whose series is one of `series`
+    pub fn exclude_series(self, series: &[&str]) -> Self {
+        let series: Vec<String> = series.iter().map(|series| series.to_string()).collect();
+        self.exclude(move |distro_release| series.contains(distro_release.series()))
+    }
+
+    /// Whether `distro_release` is kept
+    pub fn keeps(&self, distro_release: &DistroRelease) -> bool {
+        self.includes.iter().all(|include| include(distro_release))
+            && !self.excludes.iter().any(|exclude| exclude(distro_release))
+    }
+}
+
+impl fmt::Debug for RowFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RowFilter")
+            .field("includes", &self.includes.len())
+            .field("excludes", &self.excludes.len())
+            .finish()
+    }
+}
+
+/// How issues in the data which don't prevent it being parsed are treated when loading with
+/// `LoadOptions`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Fail to load the data
+    #[default]
+    Strict,
+    /// Load the data, reporting the issues as `ParseWarning`s
+    Lenient,
+}
+
+/// Options for loading distro-info-data CSV, e.g. with
+/// [`DistroDataSource::from_csv_reader_with_options`](../trait.DistroDataSource.html#method.from_csv_reader_with_options)
+///
+/// Loading this way also checks that the dates of each row are in order (no release before its
+/// series was created, and no EOL before the release), as well as that no series is repeated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// How rows with dates out of order or a repeated series are treated
+    pub validate: Strictness,
+}
+
+impl LoadOptions {
+    /// Options validating the data as `validate` says
+    pub fn new(validate: Strictness) -> Self {
+        Self { validate }
+    }
+}
+
+/// The pairs of columns (earlier, later) whose dates are out of order in `distro_release`
+#[cfg(feature = "std")]
+pub(crate) fn out_of_order(distro_release: &DistroRelease) -> Vec<(&'static str, &'static str)> {
+    let mut out_of_order = Vec::new();
+    if let (Some(created), Some(release)) = (distro_release.created, distro_release.release) {
+        if release < created {
+            out_of_order.push(("created", "release"));
+        }
+    }
+    if let (Some(release), Some(eol)) = (distro_release.release, distro_release.eol) {
+        if eol < release {
+            out_of_order.push(("release", "eol"));
+        }
+    }
+    out_of_order
+}
+
+/// Check that the series of `distro_release`, from the given line, is not among those `seen`
+/// already, and record it
+pub(crate) fn check_unique(
+    seen: &mut BTreeSet<String>,
+    distro_release: &DistroRelease,
+    line: usize,
+) -> Result<(), ParseError> {
+    if seen.insert(distro_release.series().clone()) {
+        Ok(())
+    } else {
+        Err(ParseError::DuplicateSeries {
+            line,
+            series: distro_release.series().clone(),
+        })
+    }
+}
+
+/// The date columns, in the order `Columns::release` reads them
+const DATE_COLUMNS: [&str; 8] = [
+    "created",
+    "release",
+    "eol",
+    "eol-lts",
+    "eol-elts",
+    "eol-esm",
+    "eol-server",
+    "eol-legacy",
+];
+
+/// The positions of each column in a distro-info-data CSV header
+///
+/// This is computed once per file, so that each row can be parsed without looking up columns by
+/// name.
+pub(crate) struct Columns {
+    #[cfg(feature = "std")]
+    names: Vec<String>,
+    version: usize,
+    codename: usize,
+    series: usize,
+    dates: [Option<usize>; 8],
+    extra: Vec<(usize, String)>,
+}
+
+impl Columns {
+    /// Index the columns of the given header
+    ///
+    /// Columns are found by name, so they may be in any order.  A byte-order mark and whitespace
+    /// around names are ignored.  If none of the `KNOWN_COLUMNS` is present, the first line is
+    /// taken to be data rather than a header, and `MissingHeader` is returned.
+    pub(crate) fn new<'h>(header: impl Iterator<Item = &'h str>) -> Result<Self, ParseError> {
+        let header = header
+            .map(|column| check_field(column.trim_start_matches('\u{feff}').trim(), 1))
+            .collect::<Result<Vec<&str>, _>>()?;
+        if !header.iter().any(|column| KNOWN_COLUMNS.contains(column)) {
+            return Err(ParseError::MissingHeader);
+        }
+        for (i, column) in header.iter().enumerate() {
+            if header[..i].contains(column) {
+                return Err(ParseError::DuplicateColumn(column.to_string()));
+            }
+        }
+        let position = |name: &str| header.iter().position(|column| *column == name);
+        let required = |name: &'static str| position(name).ok_or(ParseError::MissingColumn(name));
+        let (version, codename, series) = (
+            required(REQUIRED_COLUMNS[0])?,
+            required(REQUIRED_COLUMNS[1])?,
+            required(REQUIRED_COLUMNS[2])?,
+        );
+        let mut dates = [None; 8];
+        for (date, name) in dates.iter_mut().zip(DATE_COLUMNS.iter()) {
+            *date = position(name);
+        }
+        let extra = header
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !KNOWN_COLUMNS.contains(column))
+            .map(|(i, column)| (i, column.to_string()))
+            .collect();
+        Ok(Self {
+            #[cfg(feature = "std")]
+            names: header.iter().map(|column| column.to_string()).collect(),
+            version,
+            codename,
+            series,
+            dates,
+            extra,
+        })
+    }
+
+    /// The recoverable issues in the header
+    #[cfg(feature = "std")]
+    pub(crate) fn header_warnings(&self) -> impl Iterator<Item = ParseWarning> + '_ {
+        self.extra
+            .iter()
+            .map(|(_, column)| ParseWarning::UnknownColumn(column.clone()))
+    }
+
+    /// The recoverable issues in a row, where `field(i)` returns the value of the `i`th column
+    #[cfg(feature = "std")]
+    pub(crate) fn row_warnings<'f>(
+        &self,
+        field: impl Fn(usize) -> Option<&'f str>,
+        line: usize,
+    ) -> Vec<ParseWarning> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter_map(|(i, column)| {
+                let value = field(i)?;
+                let column = column.clone();
+                if value.is_empty() && !REQUIRED_COLUMNS.contains(&column.as_str()) {
+                    Some(ParseWarning::BlankField { line, column })
+                } else if value.ends_with(char::is_whitespace) {
+                    Some(ParseWarning::TrailingWhitespace { line, column })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Build a release from a row, where `field(i)` returns the value of the `i`th column
+    ///
+    /// `line` is the (1-indexed) line number of the row, for error messages.
+    pub(crate) fn release<'f>(
+        &self,
+        field: impl Fn(usize) -> Option<&'f str>,
+        line: usize,
+    ) -> Result<DistroRelease, ParseError> {
+        let field = |i: usize| field(i).map(|value| check_field(value, line)).transpose();
+        let required = |i: usize, column: &'static str| {
+            field(i)?
+                .map(|value| value.to_string())
+                .ok_or(ParseError::MissingField { line, column })
+        };
+        let mut dates = [None; 8];
+        for (date, i) in dates.iter_mut().zip(self.dates.iter()) {
+            let value = match i {
+                Some(i) => field(*i)?,
+                None => None,
+            };
+            *date = match value {
+                None | Some("") => None,
+                Some(value) => Some(parse_date(value).ok_or_else(|| ParseError::InvalidDate {
+                    line,
+                    value: value.to_string(),
+                })?),
+            };
+        }
+        let [created, release, eol, eol_lts, eol_elts, eol_esm, eol_server, eol_legacy] = dates;
+        Ok(DistroRelease::new(
+            required(self.version, REQUIRED_COLUMNS[0])?,
+            required(self.codename, REQUIRED_COLUMNS[1])?,
+            required(self.series, REQUIRED_COLUMNS[2])?,
+            created,
+            release,
+            eol,
+            eol_lts,
+            eol_elts,
+            eol_esm,
+            eol_server,
+        )
+        .with_eol_legacy(eol_legacy)
+        .with_extra_fields(
+            self.extra
+                .iter()
+                .filter_map(|(i, column)| {
+                    field(*i)
+                        .map(|value| Some((column.clone(), value?.to_string())))
+                        .transpose()
+                })
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+/// Parse the contents of a debian.csv/ubuntu.csv file into `DistroRelease`s
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(len = input.len()), err(Display))
+)]
+pub fn parse_csv(input: &str) -> Result<Vec<DistroRelease>, ParseError> {
+    parse_csv_filtered(input, &RowFilter::new())
+}
+
+/// Like `parse_csv`, but only keeping the rows `filter` keeps
+pub fn parse_csv_filtered(
+    input: &str,
+    filter: &RowFilter,
+) -> Result<Vec<DistroRelease>, ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.is_empty());
+    let (_, header) = lines.next().ok_or(ParseError::MissingHeader)?;
+    let columns = Columns::new(header.split(','))?;
+
+    let mut releases = Vec::with_capacity(input.bytes().filter(|b| *b == b'\n').count());
+    let mut fields: Vec<&str> = Vec::new();
+    let mut seen = BTreeSet::new();
+    for (line_number, line) in lines {
+        fields.clear();
+        fields.extend(line.split(','));
+        let distro_release = columns.release(|i| fields.get(i).copied(), line_number)?;
+        if !filter.keeps(&distro_release) {
+            continue;
+        }
+        check_unique(&mut seen, &distro_release, line_number)?;
+        releases.push(distro_release);
+    }
+    trace_event!(debug, releases = releases.len(), "parsed CSV");
+    Ok(releases)
+}
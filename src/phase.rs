@@ -0,0 +1,209 @@
+//! The phases a release's support passes through, from full support to end of life.
+//!
+//! Debian supports its stable release fully until the next is released, after which the security
+//! team supports it for about another year (until `eol`).  The LTS team then takes over (until
+//! `eol_lts`), followed by the commercial ELTS (until `eol_elts`).  A
+//! [`SupportTimeline`](struct.SupportTimeline.html) lays these out for a release, so that e.g. a
+//! vulnerability scanner can tell a release only receiving security updates from one no longer
+//! supported by Debian itself.
+//!
+//! Ubuntu has no security-only phase: its releases are fully supported until `eol` (or
+//! `eol_server`), and its ESM and Legacy support are treated as the LTS and ELTS phases, as with
+//! [`SupportTier`](../enum.SupportTier.html).
+//!
+//! A derivative's releases are taken to be fully supported until `eol`, followed by the LTS and
+//! ELTS phases if its data has `eol-lts` and `eol-elts` columns.
+use alloc::vec::Vec;
+
+use chrono::naive::NaiveDate;
+
+use crate::{Distro, DistroRelease, SupportTier};
+
+/// A phase of a release's support
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SupportPhase {
+    /// Full support: point releases with bug fixes as well as security updates
+    Full,
+    /// Security updates only, once a newer release is stable (Debian only)
+    Security,
+    /// Debian LTS, or Ubuntu ESM
+    Lts,
+    /// Debian ELTS, or Ubuntu's Legacy add-on
+    Elts,
+    /// No support of any kind
+    Eol,
+}
+
+impl SupportPhase {
+    /// The name of the phase, e.g. "security"
+    pub fn name(&self) -> &'static str {
+        match self {
+            SupportPhase::Full => "full",
+            SupportPhase::Security => "security",
+            SupportPhase::Lts => "lts",
+            SupportPhase::Elts => "elts",
+            SupportPhase::Eol => "eol",
+        }
+    }
+}
+
+/// The dates a release is in a phase of its support
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhaseSpan {
+    pub phase: SupportPhase,
+    /// The first day of the phase
+    pub start: NaiveDate,
+    /// The last day of the phase, or `None` if it is open-ended (or its end is unknown)
+    pub end: Option<NaiveDate>,
+}
+
+/// The phases of a release's support, in order, from its release on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SupportTimeline {
+    spans: Vec<PhaseSpan>,
+}
+
+impl SupportTimeline {
+    /// The timeline of `distro_release`, a release of `distro`, which is followed by `successor`
+    ///
+    /// The successor's release date ends Debian's full support; it isn't used for Ubuntu.  The
+    /// timeline is empty if the release date isn't known.  A full or security support phase whose
+    /// end isn't known is open-ended; the later phases are left out if theirs isn't.
+    pub fn new(
+        distro: &Distro,
+        distro_release: &DistroRelease,
+        successor: Option<&DistroRelease>,
+    ) -> Self {
+        let mut spans: Vec<PhaseSpan> = Vec::new();
+        let start = match distro_release.release {
+            Some(release) => release,
+            None => return Self { spans },
+        };
+        let full_end = match distro {
+            Distro::Debian => successor
+                .and_then(|successor| successor.release)
+                .and_then(|release| release.pred_opt())
+                .filter(|end| *end >= start)
+                .map(|end| match distro_release.eol {
+                    Some(eol) => ::core::cmp::min(end, eol),
+                    None => end,
+                })
+                .or(distro_release.eol),
+            Distro::Ubuntu => distro_release.support_end(SupportTier::Standard),
+            Distro::Derivative(_) => distro_release.eol,
+        };
+        let phases = match distro {
+            Distro::Debian => vec![
+                (SupportPhase::Full, full_end),
+                (SupportPhase::Security, distro_release.eol),
+                (SupportPhase::Lts, distro_release.eol_lts),
+                (SupportPhase::Elts, distro_release.eol_elts),
+            ],
+            Distro::Ubuntu => vec![
+                (SupportPhase::Full, full_end),
+                (SupportPhase::Lts, distro_release.eol_esm),
+                (SupportPhase::Elts, distro_release.eol_legacy),
+            ],
+            Distro::Derivative(_) => vec![
+                (SupportPhase::Full, full_end),
+                (SupportPhase::Lts, distro_release.eol_lts),
+                (SupportPhase::Elts, distro_release.eol_elts),
+            ],
+        };
+        let mut start = Some(start);
+        for (phase, end) in phases {
+            let phase_start = match start {
+                Some(phase_start) => phase_start,
+                None => break,
+            };
+            match end {
+                Some(end) if end >= phase_start => {
+                    spans.push(PhaseSpan {
+                        phase,
+                        start: phase_start,
+                        end: Some(end),
+                    });
+                    start = end.succ_opt();
+                }
+                // Over by the end of the phase before, e.g. when Debian's full support is taken
+                // to end at `eol`, with no newer release yet
+                Some(_) => {}
+                // Every release has these phases, so they continue until their end is known
+                None if phase == SupportPhase::Full || phase == SupportPhase::Security => {
+                    spans.push(PhaseSpan {
+                        phase,
+                        start: phase_start,
+                        end: None,
+                    });
+                    start = None;
+                }
+                None => {}
+            }
+        }
+        if let Some(start) = start {
+            spans.push(PhaseSpan {
+                phase: SupportPhase::Eol,
+                start,
+                end: None,
+            });
+        }
+        Self { spans }
+    }
+
+    /// The phases, in order
+    pub fn spans(&self) -> &[PhaseSpan] {
+        &self.spans
+    }
+
+    /// The phase the release is in at `date`, or `None` if it hadn't been released by then
+    pub fn phase_at(&self, date: NaiveDate) -> Option<SupportPhase> {
+        self.spans
+            .iter()
+            .find(|span| span.start <= date && span.end.is_none_or(|end| date <= end))
+            .map(|span| span.phase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SupportPhase;
+    use crate::{test_support, DistroInfo};
+
+    #[test]
+    fn debian_phases() {
+        let debian_distro_info = test_support::debian();
+        let buster = debian_distro_info.lookup("buster").unwrap();
+        let timeline = debian_distro_info.support_timeline(buster);
+        let phase_at = |date| timeline.phase_at(test_support::date(date));
+        assert_eq!(None, phase_at("2019-01-01"));
+        assert_eq!(Some(SupportPhase::Full), phase_at("2019-07-06"));
+        assert_eq!(Some(SupportPhase::Full), phase_at("2021-08-13"));
+        assert_eq!(Some(SupportPhase::Security), phase_at("2021-08-14"));
+        assert_eq!(Some(SupportPhase::Lts), phase_at("2023-01-01"));
+        assert_eq!(Some(SupportPhase::Elts), phase_at("2025-01-01"));
+        assert_eq!(Some(SupportPhase::Eol), phase_at("2040-01-01"));
+        let sid = debian_distro_info.lookup("sid").unwrap();
+        assert!(debian_distro_info.support_timeline(sid).spans().is_empty());
+    }
+
+    #[test]
+    fn ubuntu_phases() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info.lookup("jammy").unwrap();
+        let timeline = ubuntu_distro_info.support_timeline(jammy);
+        let phases: Vec<_> = timeline.spans().iter().map(|span| span.phase).collect();
+        assert_eq!(
+            vec![
+                SupportPhase::Full,
+                SupportPhase::Lts,
+                SupportPhase::Elts,
+                SupportPhase::Eol
+            ],
+            phases
+        );
+        assert_eq!(
+            Some(SupportPhase::Lts),
+            timeline.phase_at(test_support::date("2030-01-01"))
+        );
+    }
+}
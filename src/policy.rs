@@ -0,0 +1,207 @@
+//! Per-distro behaviour which is not captured by the release data itself.
+//!
+//! Each [`Distro`](../enum.Distro.html) has a built-in [`DistroPolicy`](trait.DistroPolicy.html),
+//! returned by `Distro::policy`; derivative distros can implement the trait themselves to change
+//! how their releases are named, which of them are LTS releases and so on.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use chrono::naive::NaiveDate;
+
+use crate::milestones::Milestone;
+use crate::{DistroInfo, DistroRelease};
+
+/// Distro-specific rules for interpreting release data
+pub trait DistroPolicy {
+    /// The human-readable name of the distro, e.g. "Ubuntu"
+    fn display_name(&self) -> &str;
+
+    /// The full name of a release, e.g. `Ubuntu 22.04 LTS "Jammy Jellyfish"`
+    fn full_name(&self, distro_release: &DistroRelease) -> String {
+        let codename = match (
+            distro_release.codename_adjective(),
+            distro_release.codename_animal(),
+        ) {
+            (Some(adjective), Some(animal)) => format!("{} {}", adjective, animal),
+            _ => distro_release.codename().clone(),
+        };
+        format!(
+            "{} {} \"{}\"",
+            self.display_name(),
+            distro_release.version().as_deref().unwrap_or(""),
+            codename
+        )
+    }
+
+    /// Whether `distro_release` is a long-term support release
+    fn is_lts(&self, distro_release: &DistroRelease) -> bool {
+        distro_release.is_lts()
+    }
+
+    /// The release(s) in development at `date`
+    fn devel<'a>(&self, distro_info: &'a dyn DistroInfo, date: NaiveDate)
+        -> Vec<&'a DistroRelease>;
+
+    /// The number of days after the newest series was created by which another is expected to
+    /// have been, used by `DistroInfo::is_probably_stale`
+    ///
+    /// `None` (the default) disables staleness detection.
+    fn max_series_gap(&self) -> Option<u32> {
+        None
+    }
+
+    /// The milestones the distro's data records, in the order they usually occur
+    ///
+    /// The default is just those common to all distros: creation, release and EOL.
+    fn supported_milestones(&self) -> &'static [Milestone] {
+        &[Milestone::Created, Milestone::Release, Milestone::Eol]
+    }
+
+    /// The release an alias (such as Debian's "testing") refers to at `date`, if it is one
+    fn resolve_alias<'a>(
+        &self,
+        _distro_info: &'a dyn DistroInfo,
+        _alias: &str,
+        _date: NaiveDate,
+    ) -> Option<&'a DistroRelease> {
+        None
+    }
+}
+
+/// The policy for Ubuntu
+pub struct UbuntuPolicy;
+
+impl DistroPolicy for UbuntuPolicy {
+    fn display_name(&self) -> &str {
+        "Ubuntu"
+    }
+
+    fn devel<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+    ) -> Vec<&'a DistroRelease> {
+        distro_info.ubuntu_devel(date)
+    }
+
+    /// A new series is created every six months, on the release of the previous one
+    fn max_series_gap(&self) -> Option<u32> {
+        Some(214)
+    }
+
+    fn supported_milestones(&self) -> &'static [Milestone] {
+        &[
+            Milestone::Created,
+            Milestone::Release,
+            Milestone::Eol,
+            Milestone::EolServer,
+            Milestone::EolEsm,
+        ]
+    }
+}
+
+/// The policy for Debian
+///
+/// This resolves the "stable", "testing", "unstable" and "experimental" aliases.
+pub struct DebianPolicy;
+
+impl DistroPolicy for DebianPolicy {
+    fn display_name(&self) -> &str {
+        "Debian"
+    }
+
+    fn devel<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        date: NaiveDate,
+    ) -> Vec<&'a DistroRelease> {
+        distro_info.debian_devel(date)
+    }
+
+    /// Releases are roughly two-yearly, with the next-but-one series named well in advance
+    fn max_series_gap(&self) -> Option<u32> {
+        Some(1096)
+    }
+
+    fn supported_milestones(&self) -> &'static [Milestone] {
+        &[
+            Milestone::Created,
+            Milestone::Release,
+            Milestone::Eol,
+            Milestone::EolLts,
+            Milestone::EolElts,
+        ]
+    }
+
+    fn resolve_alias<'a>(
+        &self,
+        distro_info: &'a dyn DistroInfo,
+        alias: &str,
+        date: NaiveDate,
+    ) -> Option<&'a DistroRelease> {
+        let by_series = |series: &str| distro_info.iter().find(|r| r.series() == series);
+        match alias {
+            "stable" => distro_info.stable(date),
+            "testing" => distro_info.ubuntu_devel(date).last().copied(),
+            "unstable" => by_series("sid"),
+            "experimental" => by_series("experimental"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DebianPolicy, DistroPolicy, UbuntuPolicy};
+    use crate::{test_support, DistroInfo};
+
+    #[test]
+    fn full_name() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let jammy = ubuntu_distro_info
+            .iter()
+            .find(|r| r.series() == "jammy")
+            .unwrap();
+        assert_eq!(
+            "Ubuntu 22.04 LTS \"Jammy Jellyfish\"",
+            UbuntuPolicy.full_name(jammy)
+        );
+    }
+
+    #[test]
+    fn supported_milestones() {
+        let names = |policy: &dyn DistroPolicy| -> Vec<&str> {
+            policy
+                .supported_milestones()
+                .iter()
+                .map(|milestone| milestone.name())
+                .collect()
+        };
+        assert_eq!(
+            vec!["created", "release", "eol", "eol-server", "eol-esm"],
+            names(&UbuntuPolicy)
+        );
+        assert_eq!(
+            vec!["created", "release", "eol", "eol-lts", "eol-elts"],
+            names(&DebianPolicy)
+        );
+    }
+
+    #[test]
+    fn debian_aliases() {
+        let debian_distro_info = test_support::debian();
+        let date = test_support::date("2024-01-01");
+        let alias = |alias| {
+            DebianPolicy
+                .resolve_alias(&debian_distro_info, alias, date)
+                .map(|r| r.series().as_str())
+        };
+        assert_eq!(Some("bookworm"), alias("stable"));
+        assert_eq!(Some("trixie"), alias("testing"));
+        assert_eq!(Some("sid"), alias("unstable"));
+        assert_eq!(None, alias("bookworm"));
+        assert!(UbuntuPolicy
+            .resolve_alias(&debian_distro_info, "stable", date)
+            .is_none());
+    }
+}
@@ -0,0 +1,103 @@
+//! Predicting Ubuntu versions which are not yet in the data, from the release cadence.
+//!
+//! Ubuntu releases every six months, in April and October, with an LTS release in April of even
+//! years.  When distro-info-data hasn't yet been updated for a new cycle, this allows tools to
+//! guess the version in development; the result is a
+//! [`PredictedVersion`](struct.PredictedVersion.html), so it can't be mistaken for real data.
+use core::fmt;
+
+use chrono::naive::NaiveDate;
+use chrono::Months;
+
+use crate::version::ReleaseVersion;
+use crate::{DistroInfo, UbuntuDistroInfo};
+
+/// A version predicted from the release cadence, rather than read from the data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PredictedVersion {
+    /// The predicted version
+    pub version: ReleaseVersion,
+    /// Roughly when the release is expected (six months after its predecessor)
+    pub expected_release: NaiveDate,
+}
+
+impl fmt::Display for PredictedVersion {
+    /// Formats the version as Ubuntu does, e.g. "26.10" or "28.04 LTS"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{:02}", self.version.major, self.version.minor)?;
+        if self.version.lts {
+            write!(f, " LTS")?;
+        }
+        Ok(())
+    }
+}
+
+/// The Ubuntu version which follows `version`
+fn successor(version: ReleaseVersion) -> ReleaseVersion {
+    let (major, minor) = if version.minor < 10 {
+        (version.major, 10)
+    } else {
+        (version.major + 1, 4)
+    };
+    ReleaseVersion {
+        major,
+        minor,
+        lts: minor == 4 && major % 2 == 0,
+    }
+}
+
+/// Predict the version of the Ubuntu release in development at `date`
+///
+/// This steps through the cadence from the newest release in `distro_info` with a version and
+/// release date, so it is the data's own version if that release was still in development at
+/// `date`.  Returns `None` if no such release exists.
+pub fn next_expected_ubuntu_version(
+    distro_info: &dyn DistroInfo,
+    date: NaiveDate,
+) -> Option<PredictedVersion> {
+    let (mut version, mut expected_release) =
+        distro_info.iter().rev().find_map(|distro_release| {
+            Some((
+                distro_release.parsed_version()?,
+                (*distro_release.release())?,
+            ))
+        })?;
+    while expected_release <= date {
+        version = successor(version);
+        expected_release = expected_release.checked_add_months(Months::new(6))?;
+    }
+    Some(PredictedVersion {
+        version,
+        expected_release,
+    })
+}
+
+impl UbuntuDistroInfo {
+    /// Predict the version of the release in development at `date`; see
+    /// [`next_expected_ubuntu_version`](prediction/fn.next_expected_ubuntu_version.html)
+    pub fn next_expected_version(&self, date: NaiveDate) -> Option<PredictedVersion> {
+        next_expected_ubuntu_version(self, date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support;
+
+    #[test]
+    fn next_expected_version() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let predict = |date| {
+            ubuntu_distro_info
+                .next_expected_version(test_support::date(date))
+                .unwrap()
+                .to_string()
+        };
+        // The data's own development release
+        assert_eq!("26.04 LTS", predict("2026-01-01"));
+        // ... and beyond it
+        assert_eq!("26.10", predict("2026-05-01"));
+        assert_eq!("27.04", predict("2026-11-01"));
+        assert_eq!("28.04 LTS", predict("2027-11-01"));
+    }
+}
@@ -0,0 +1,222 @@
+//! Composable queries over a distro's releases.
+//!
+//! [`DistroQueries::query`](../trait.DistroQueries.html#method.query) starts a
+//! [`Query`](struct.Query.html) over all of the releases, which filters can be chained onto, e.g.
+//!
+//! ```
+//! # use distro_info::{DistroDataSource, DistroInfo, UbuntuDistroInfo};
+//! # use distro_info::milestones::Milestone;
+//! # let ubuntu_distro_info = UbuntuDistroInfo::from_csv_str("version,codename,series,created,release,eol\n").unwrap();
+//! # let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+//! let supported_lts = ubuntu_distro_info
+//!     .query()
+//!     .supported(date)
+//!     .lts()
+//!     .sorted_by(Milestone::Eol)
+//!     .collect();
+//! ```
+//!
+//! Nothing is filtered until the query is collected (or iterated over), which returns the releases
+//! matching every filter, rather than each filter allocating a `Vec` to be intersected with the
+//! others.
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::slice;
+
+use chrono::naive::NaiveDate;
+
+use crate::milestones::Milestone;
+use crate::{Distro, DistroRelease};
+
+/// A predicate on releases, as chained onto a `Query`
+type QueryPredicate<'a> = Box<dyn Fn(&DistroRelease) -> bool + 'a>;
+
+/// A query for the releases of a distro which match all of a number of filters
+pub struct Query<'a> {
+    distro: Distro,
+    releases: slice::Iter<'a, DistroRelease>,
+    predicates: Vec<QueryPredicate<'a>>,
+    order: Option<Milestone>,
+}
+
+impl<'a> Query<'a> {
+    /// A query over `releases` of `distro`, which (until filtered) matches all of them
+    pub fn new(distro: Distro, releases: slice::Iter<'a, DistroRelease>) -> Self {
+        Self {
+            distro,
+            releases,
+            predicates: Vec::new(),
+            order: None,
+        }
+    }
+
+    /// Only match releases for which `predicate` holds
+    pub fn filter<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&DistroRelease) -> bool + 'a,
+    {
+        self.predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Only match releases which had been created at `date`
+    pub fn created(self, date: NaiveDate) -> Self {
+        self.filter(move |distro_release| distro_release.created_at(date))
+    }
+
+    /// Only match releases which had been released at `date`
+    pub fn released(self, date: NaiveDate) -> Self {
+        self.filter(move |distro_release| distro_release.released_at(date))
+    }
+
+    /// Only match releases which were supported at `date`, as `DistroInfo::supported` reports
+    pub fn supported(self, date: NaiveDate) -> Self {
+        self.filter(move |distro_release| distro_release.supported_at(date))
+    }
+
+    /// Only match releases which had been created but were no longer supported at `date`, as
+    /// `DistroInfo::unsupported` reports
+    pub fn unsupported(self, date: NaiveDate) -> Self {
+        self.filter(move |distro_release| {
+            distro_release.created_at(date) && !distro_release.supported_at(date)
+        })
+    }
+
+    /// Only match long term support releases, as the distro's policy defines them
+    pub fn lts(self) -> Self {
+        let policy = self.distro.policy();
+        self.filter(move |distro_release| policy.is_lts(distro_release))
+    }
+
+    /// Only match releases released after (not on) `date`
+    pub fn released_after(self, date: NaiveDate) -> Self {
+        self.filter(move |distro_release| {
+            distro_release
+                .release()
+                .is_some_and(|release| release > date)
+        })
+    }
+
+    /// Only match releases released before (not on) `date`
+    pub fn released_before(self, date: NaiveDate) -> Self {
+        self.filter(move |distro_release| {
+            distro_release
+                .release()
+                .is_some_and(|release| release < date)
+        })
+    }
+
+    /// Order the matching releases by the date of `milestone`, rather than as in the data
+    ///
+    /// The order of releases with the same date is kept, and those whose date for the milestone
+    /// is unknown come last.
+    pub fn sorted_by(mut self, milestone: Milestone) -> Self {
+        self.order = Some(milestone);
+        self
+    }
+
+    /// The releases matching all of the filters
+    pub fn collect(self) -> Vec<&'a DistroRelease> {
+        let predicates = self.predicates;
+        let mut releases: Vec<_> = self
+            .releases
+            .filter(|distro_release| predicates.iter().all(|predicate| predicate(distro_release)))
+            .collect();
+        if let Some(milestone) = self.order {
+            releases.sort_by_key(|distro_release| {
+                let date = milestone.date(distro_release);
+                (date.is_none(), date)
+            });
+        }
+        releases
+    }
+}
+
+impl<'a> IntoIterator for Query<'a> {
+    type Item = &'a DistroRelease;
+    type IntoIter = alloc::vec::IntoIter<&'a DistroRelease>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.collect().into_iter()
+    }
+}
+
+impl<'a> fmt::Debug for Query<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Query")
+            .field("distro", &self.distro)
+            .field("predicates", &self.predicates.len())
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::Query;
+    use crate::milestones::Milestone;
+    use crate::{test_support, Distro, DistroInfo, DistroRelease};
+
+    fn series(releases: Vec<&DistroRelease>) -> Vec<&str> {
+        releases
+            .into_iter()
+            .map(|distro_release| distro_release.series().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn chained_filters() {
+        let ubuntu_distro_info = test_support::ubuntu();
+        let date = test_support::date("2024-01-01");
+        let expected: Vec<_> = ubuntu_distro_info
+            .supported(date)
+            .into_iter()
+            .filter(|distro_release| distro_release.is_lts())
+            .collect();
+        assert_eq!(
+            series(expected),
+            series(ubuntu_distro_info.query().supported(date).lts().collect())
+        );
+        let recent_lts = ubuntu_distro_info
+            .query()
+            .lts()
+            .released_after(test_support::date("2016-01-01"))
+            .released_before(test_support::date("2022-04-21"))
+            .collect();
+        assert_eq!(vec!["xenial", "bionic", "focal"], series(recent_lts));
+        assert!(ubuntu_distro_info
+            .query()
+            .released(date)
+            .unsupported(date)
+            .into_iter()
+            .all(|distro_release| !distro_release.supported_at(date)));
+    }
+
+    #[test]
+    fn sorted_by_milestone() {
+        let releases = [
+            test_support::fake_release("1", "a", "2020-01-01", Some("2020-06-01"), None),
+            test_support::fake_release(
+                "2",
+                "b",
+                "2020-02-01",
+                Some("2020-07-01"),
+                Some("2021-01-01"),
+            ),
+            test_support::fake_release(
+                "3",
+                "c",
+                "2020-03-01",
+                Some("2020-08-01"),
+                Some("2020-12-01"),
+            ),
+        ];
+        let sorted = Query::new(Distro::Debian, releases.iter())
+            .sorted_by(Milestone::Eol)
+            .collect();
+        assert_eq!(vec!["c", "b", "a"], series(sorted));
+    }
+}
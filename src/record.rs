@@ -0,0 +1,171 @@
+//! A plain-data copy of a release, for FFI and for passing across process boundaries.
+//!
+//! [`ReleaseRecord`](struct.ReleaseRecord.html) is `#[repr(C)]` and holds no pointers: its strings
+//! are fixed-size, NUL-padded UTF-8, and its dates are `YYYYMMDD` integers, with
+//! [`NO_DATE`](constant.NO_DATE.html) for an unknown date.  It can be copied into shared memory or
+//! written out as bytes, and its layout will only change with the crate's major version.
+use alloc::string::String;
+
+use chrono::naive::NaiveDate;
+use chrono::Datelike;
+
+use crate::DistroRelease;
+
+/// The date field value for an unknown date
+pub const NO_DATE: u32 = 0;
+
+/// The size of the version field, including its terminating NUL
+pub const VERSION_LEN: usize = 16;
+/// The size of the codename field, including its terminating NUL
+pub const CODENAME_LEN: usize = 64;
+/// The size of the series field, including its terminating NUL
+pub const SERIES_LEN: usize = 32;
+
+/// A release as plain data; see the [module documentation](index.html)
+///
+/// Strings too long for their fields are truncated (at a character boundary).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReleaseRecord {
+    pub version: [u8; VERSION_LEN],
+    pub codename: [u8; CODENAME_LEN],
+    pub series: [u8; SERIES_LEN],
+    pub created: u32,
+    pub release: u32,
+    pub eol: u32,
+    pub eol_server: u32,
+    pub eol_lts: u32,
+    pub eol_elts: u32,
+    pub eol_esm: u32,
+    pub eol_legacy: u32,
+}
+
+/// `value` as a NUL-padded field of `N` bytes, truncated to leave at least one NUL
+fn to_field<const N: usize>(value: &str) -> [u8; N] {
+    let mut field = [0; N];
+    let mut len = value.len().min(N - 1);
+    while !value.is_char_boundary(len) {
+        len -= 1;
+    }
+    field[..len].copy_from_slice(&value.as_bytes()[..len]);
+    field
+}
+
+/// The string in a NUL-padded field
+fn from_field(field: &[u8]) -> &str {
+    let len = field
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(field.len());
+    core::str::from_utf8(&field[..len]).unwrap_or("")
+}
+
+/// `date` as a `YYYYMMDD` integer, or `NO_DATE`
+pub fn to_date_field(date: Option<NaiveDate>) -> u32 {
+    match date {
+        Some(date) if date.year() > 0 => {
+            date.year() as u32 * 10000 + date.month() * 100 + date.day()
+        }
+        _ => NO_DATE,
+    }
+}
+
+/// The date in a `YYYYMMDD` field, or `None` for `NO_DATE` (or an invalid date)
+pub fn from_date_field(value: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt((value / 10000) as i32, value / 100 % 100, value % 100)
+}
+
+impl ReleaseRecord {
+    /// The version, or "" if the release has none
+    pub fn version(&self) -> &str {
+        from_field(&self.version)
+    }
+
+    pub fn codename(&self) -> &str {
+        from_field(&self.codename)
+    }
+
+    pub fn series(&self) -> &str {
+        from_field(&self.series)
+    }
+
+    /// The release this describes (without any `extra_fields`, which records don't hold)
+    pub fn to_release(&self) -> DistroRelease {
+        DistroRelease::new(
+            String::from(self.version()),
+            String::from(self.codename()),
+            String::from(self.series()),
+            from_date_field(self.created),
+            from_date_field(self.release),
+            from_date_field(self.eol),
+            from_date_field(self.eol_lts),
+            from_date_field(self.eol_elts),
+            from_date_field(self.eol_esm),
+            from_date_field(self.eol_server),
+        )
+        .with_eol_legacy(from_date_field(self.eol_legacy))
+    }
+}
+
+impl DistroRelease {
+    /// This release as a [`ReleaseRecord`](record/struct.ReleaseRecord.html)
+    pub fn to_record(&self) -> ReleaseRecord {
+        ReleaseRecord {
+            version: to_field(self.version.as_deref().unwrap_or("")),
+            codename: to_field(&self.codename),
+            series: to_field(&self.series),
+            created: to_date_field(self.created),
+            release: to_date_field(self.release),
+            eol: to_date_field(self.eol),
+            eol_server: to_date_field(self.eol_server),
+            eol_lts: to_date_field(self.eol_lts),
+            eol_elts: to_date_field(self.eol_elts),
+            eol_esm: to_date_field(self.eol_esm),
+            eol_legacy: to_date_field(self.eol_legacy),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_date_field, to_date_field, ReleaseRecord, NO_DATE};
+    use crate::{test_support, DistroInfo};
+
+    #[test]
+    fn records_round_trip() {
+        for distro_release in test_support::ubuntu()
+            .iter()
+            .chain(test_support::debian().iter())
+        {
+            let record = distro_release.to_record();
+            let round_tripped = record.to_release();
+            assert_eq!(distro_release.series(), round_tripped.series());
+            assert_eq!(distro_release.codename(), round_tripped.codename());
+            assert_eq!(distro_release.version(), round_tripped.version());
+            assert_eq!(distro_release.milestones(), round_tripped.milestones());
+        }
+        let jammy = test_support::ubuntu().lookup("jammy").unwrap().to_record();
+        assert_eq!("22.04 LTS", jammy.version());
+        assert_eq!(20220421, jammy.release);
+        assert_eq!(20320421, jammy.eol_esm);
+        let sid = test_support::debian().lookup("sid").unwrap().to_record();
+        assert_eq!("", sid.version());
+        assert_eq!(NO_DATE, sid.eol);
+    }
+
+    #[test]
+    fn fields_are_truncated_and_padded() {
+        let long = crate::DistroRelease::named("1".to_string(), "é".repeat(40), "s".repeat(40));
+        let record: ReleaseRecord = long.to_record();
+        // 31 bytes would split a character, so the codename stops at 62
+        assert_eq!("é".repeat(31), record.codename());
+        assert_eq!("s".repeat(31), record.series());
+        assert_eq!(0, record.series[31]);
+        assert_eq!(NO_DATE, to_date_field(None));
+        assert_eq!(None, from_date_field(NO_DATE));
+        assert_eq!(
+            Some(test_support::date("2024-02-29")),
+            from_date_field(to_date_field(Some(test_support::date("2024-02-29"))))
+        );
+    }
+}
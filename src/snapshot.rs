@@ -0,0 +1,139 @@
+//! Immutable, shareable snapshots of a distro's data, for services which answer concurrent
+//! requests.
+//!
+//! A [`SnapshotWatcher`](struct.SnapshotWatcher.html) holds the current
+//! [`DistroSnapshot`](struct.DistroSnapshot.html) and replaces it when the data file changes.
+//! Each request takes an `Arc` of the current snapshot (only a reference count increment), and so
+//! sees one consistent version of the data however long it runs, without further locking.
+use core::ops::Deref;
+use std::sync::{Arc, RwLock};
+
+use failure::Error;
+
+use crate::source::DataSource;
+use crate::DistroDataSource;
+
+/// A distro's data, together with where and when it was read from
+#[derive(Debug)]
+pub struct DistroSnapshot<T> {
+    distro_info: T,
+    source: DataSource,
+}
+
+impl<T: DistroDataSource> DistroSnapshot<T> {
+    /// Wrap data read from `source`
+    pub fn new(distro_info: T, source: DataSource) -> Self {
+        Self {
+            distro_info,
+            source,
+        }
+    }
+
+    /// Load the system's data for `T` with `loader` (e.g. `T::new`)
+    pub fn load_with(loader: fn() -> Result<T, Error>) -> Result<Self, Error> {
+        // Describe the source first, so that a change while loading is seen as one afterwards
+        let source = T::data_source();
+        Ok(Self::new(loader()?, source))
+    }
+
+    pub fn distro_info(&self) -> &T {
+        &self.distro_info
+    }
+
+    /// Where the data was read from, and the file's modification time when it was
+    pub fn source(&self) -> &DataSource {
+        &self.source
+    }
+
+    /// Whether the file the data was read from has changed since
+    pub fn is_outdated(&self) -> bool {
+        let modified = self
+            .source
+            .path
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        modified != self.source.modified
+    }
+}
+
+impl<T> Deref for DistroSnapshot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.distro_info
+    }
+}
+
+/// The current `DistroSnapshot` of a distro's data, reloaded when its file changes
+pub struct SnapshotWatcher<T> {
+    loader: fn() -> Result<T, Error>,
+    current: RwLock<Arc<DistroSnapshot<T>>>,
+}
+
+impl<T: DistroDataSource> SnapshotWatcher<T> {
+    /// Load the system's data for `T`, as `DistroDataSource::new` does
+    pub fn load() -> Result<Self, Error> {
+        Self::load_with(T::new)
+    }
+
+    /// Load the system's data for `T` with `loader` (e.g. `DistroDataSource::new_cached`), which is
+    /// also used to reload it
+    pub fn load_with(loader: fn() -> Result<T, Error>) -> Result<Self, Error> {
+        Ok(Self {
+            loader,
+            current: RwLock::new(Arc::new(DistroSnapshot::load_with(loader)?)),
+        })
+    }
+
+    /// The current snapshot
+    pub fn current(&self) -> Arc<DistroSnapshot<T>> {
+        match self.current.read() {
+            Ok(current) => current.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Reload the data if its file has changed since the current snapshot was loaded, returning
+    /// whether it was
+    ///
+    /// If reloading fails, the current snapshot is kept and the error returned.
+    pub fn refresh(&self) -> Result<bool, Error> {
+        if !self.current().is_outdated() {
+            return Ok(false);
+        }
+        let reloaded = Arc::new(DistroSnapshot::load_with(self.loader)?);
+        match self.current.write() {
+            Ok(mut current) => *current = reloaded,
+            Err(poisoned) => *poisoned.into_inner() = reloaded,
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{DistroSnapshot, SnapshotWatcher};
+    use crate::source::DataSource;
+    use crate::{test_support, DistroInfo, UbuntuDistroInfo};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn snapshots_are_shareable() {
+        assert_send_sync::<Arc<DistroSnapshot<UbuntuDistroInfo>>>();
+        assert_send_sync::<SnapshotWatcher<UbuntuDistroInfo>>();
+        let snapshot = Arc::new(DistroSnapshot::new(
+            test_support::ubuntu(),
+            DataSource::for_csv_path("testdata/ubuntu.csv".as_ref()),
+        ));
+        let handler = {
+            let snapshot = snapshot.clone();
+            std::thread::spawn(move || snapshot.lookup("jammy").unwrap().series().clone())
+        };
+        assert_eq!("jammy", handler.join().unwrap());
+        assert!(!snapshot.is_outdated());
+    }
+}
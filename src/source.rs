@@ -0,0 +1,69 @@
+//! Where the release data was read from, for bug reports and staleness monitoring.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::compression;
+
+/// The package which ships the distro-info-data CSV files
+pub const DATA_PACKAGE: &str = "distro-info-data";
+
+/// The provenance of a distro's release data
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataSource {
+    /// The file the data is read from (which may not exist)
+    pub path: PathBuf,
+    /// When the file was last modified, if it exists
+    pub modified: Option<SystemTime>,
+    /// The installed version of the distro-info-data package, if dpkg knows it
+    pub package_version: Option<String>,
+}
+
+impl DataSource {
+    /// Describe the data read for `csv_path`, as `DistroDataSource::new` would find it
+    pub fn for_csv_path(csv_path: &Path) -> Self {
+        let path = compression::find(csv_path);
+        let modified = path
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        Self {
+            path,
+            modified,
+            package_version: package_version(DATA_PACKAGE),
+        }
+    }
+}
+
+/// The installed version of `package` according to dpkg, if any
+pub fn package_version(package: &str) -> Option<String> {
+    let output = Command::new("dpkg-query")
+        .args(["--show", "--showformat=${Version}", package])
+        .output()
+        .ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    if output.status.success() && !version.is_empty() {
+        Some(version)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{package_version, DataSource};
+
+    #[test]
+    fn missing_file() {
+        let source = DataSource::for_csv_path(Path::new("/nonexistent/ubuntu.csv"));
+        assert_eq!(Path::new("/nonexistent/ubuntu.csv"), source.path);
+        assert_eq!(None, source.modified);
+    }
+
+    #[test]
+    fn unknown_package() {
+        assert_eq!(None, package_version("no-such-package-for-distro-info"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Support status reports for a single release, e.g. the one a system is running.
+use chrono::naive::NaiveDate;
+
+use crate::matching::MatchMode;
+use crate::{DistroInfo, DistroRelease, SupportTier};
+
+/// A summary of the support status of a release at a particular date
+#[derive(Clone, Debug)]
+pub struct SupportStatus<'a> {
+    release: &'a DistroRelease,
+    date: NaiveDate,
+    supported: bool,
+    extended_support_until: Option<NaiveDate>,
+    successor: Option<&'a DistroRelease>,
+}
+
+impl<'a> SupportStatus<'a> {
+    /// Report on the release with the given codename at the given date
+    ///
+    /// `codename` is looked up as by `DistroInfo::lookup`, so may be a series (as in os-release's
+    /// `VERSION_CODENAME`, e.g. "jammy"), full codename or version, in any case.  Returns `None`
+    /// if no release matches.
+    pub fn for_codename<D: DistroInfo + ?Sized>(
+        distro_info: &'a D,
+        codename: &str,
+        date: NaiveDate,
+    ) -> Option<Self> {
+        Self::for_codename_with(distro_info, codename, MatchMode::Relaxed, date)
+    }
+
+    /// Like `for_codename`, but comparing `codename` with the data according to `mode`
+    pub fn for_codename_with<D: DistroInfo + ?Sized>(
+        distro_info: &'a D,
+        codename: &str,
+        mode: MatchMode,
+        date: NaiveDate,
+    ) -> Option<Self> {
+        let release = distro_info.lookup_with(codename, mode)?;
+        let extended_support_until = [release.eol_esm, release.eol_lts, release.eol_elts]
+            .iter()
+            .flatten()
+            .max()
+            .copied();
+        Some(Self {
+            release,
+            date,
+            supported: release.supported_at(date),
+            extended_support_until,
+            successor: distro_info.successor(release),
+        })
+    }
+
+    /// Report on the release named by `VERSION_CODENAME` in /etc/os-release
+    ///
+    /// Returns `Ok(None)` if os-release has no `VERSION_CODENAME` or it names an unknown release.
+    #[cfg(feature = "std")]
+    pub fn for_os_release<D: DistroInfo + ?Sized>(
+        distro_info: &'a D,
+        date: NaiveDate,
+    ) -> Result<Option<Self>, failure::Error> {
+        let os_release = std::fs::read_to_string("/etc/os-release")?;
+        Ok(os_release_codename(&os_release)
+            .and_then(|codename| Self::for_codename(distro_info, &codename, date)))
+    }
+
+    /// The release being reported on
+    pub fn release(&self) -> &'a DistroRelease {
+        self.release
+    }
+    /// Whether the release is supported at the report's date
+    pub fn supported(&self) -> bool {
+        self.supported
+    }
+    /// The number of days from the report's date until the release's end of support
+    ///
+    /// This is negative if support has already ended, and `None` if no EOL date is known.
+    pub fn days_to_eol(&self) -> Option<i64> {
+        self.release
+            .support_end(SupportTier::Standard)
+            .map(|eol| eol.signed_duration_since(self.date).num_days())
+    }
+    /// The last date of any extended support (Ubuntu ESM, Debian LTS/ELTS) for the release
+    pub fn extended_support_until(&self) -> Option<NaiveDate> {
+        self.extended_support_until
+    }
+    /// Whether extended support (Ubuntu ESM, Debian LTS/ELTS) is available at the report's date
+    pub fn esm_available(&self) -> bool {
+        self.extended_support_until
+            .map(|until| self.date <= until)
+            .unwrap_or(false)
+    }
+    /// The release which follows this one, if any
+    pub fn successor(&self) -> Option<&'a DistroRelease> {
+        self.successor
+    }
+}
+
+/// Extract the (unquoted) `VERSION_CODENAME` from the contents of an os-release file
+pub fn os_release_codename(os_release: &str) -> Option<alloc::string::String> {
+    os_release
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("VERSION_CODENAME="))
+        .map(|value| value.trim_matches(|c| c == '"' || c == '\'').into())
+        .find(|value: &alloc::string::String| !value.is_empty())
+}
@@ -0,0 +1,132 @@
+//! The auxiliary suites of Debian releases, e.g. for generating APT sources.
+//!
+//! Besides its own suite, each Debian release has suites for stable updates, proposed updates,
+//! backports and security updates, whose availability and naming have changed over time: most
+//! notably, the security suite was `{series}/updates` until buster and is `{series}-security`
+//! from bullseye on.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{DebianDistroInfo, DistroInfo, DistroRelease};
+
+/// The first major version with each suite
+const FIRST_UPDATES: u32 = 6;
+const FIRST_BACKPORTS: u32 = 6;
+const FIRST_DASH_SECURITY: u32 = 11;
+
+/// A kind of auxiliary suite
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SuiteKind {
+    /// `-updates`: stable updates (formerly volatile)
+    Updates,
+    /// `-proposed-updates`: packages proposed for the next point release
+    ProposedUpdates,
+    /// `-backports`: newer packages rebuilt for the release
+    Backports,
+    /// `-backports-sloppy`: backports from the release after next, for oldstable
+    BackportsSloppy,
+    /// `-security` (or `/updates`): security updates
+    Security,
+}
+
+impl SuiteKind {
+    /// The archive the suite is published in: "debian-security" for security updates, and
+    /// "debian" for the rest
+    pub fn archive(&self) -> &'static str {
+        match self {
+            SuiteKind::Security => "debian-security",
+            _ => "debian",
+        }
+    }
+}
+
+/// An auxiliary suite of a release
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suite {
+    pub kind: SuiteKind,
+    /// The suite's name, e.g. "bookworm-security"
+    pub name: String,
+}
+
+/// The auxiliary suites of a Debian release, named as they are for its version
+///
+/// Rolling suites (sid, experimental) and releases without a version have none.
+pub fn auxiliary_suites(distro_release: &DistroRelease) -> Vec<Suite> {
+    let major = match distro_release.parsed_version() {
+        Some(version) if !distro_release.is_rolling() => version.major,
+        _ => return Vec::new(),
+    };
+    let series = distro_release.series();
+    let mut suites = Vec::new();
+    let mut push = |kind, name| suites.push(Suite { kind, name });
+    if major >= FIRST_UPDATES {
+        push(SuiteKind::Updates, format!("{}-updates", series));
+    }
+    push(
+        SuiteKind::ProposedUpdates,
+        format!("{}-proposed-updates", series),
+    );
+    if major >= FIRST_BACKPORTS {
+        push(SuiteKind::Backports, format!("{}-backports", series));
+        push(
+            SuiteKind::BackportsSloppy,
+            format!("{}-backports-sloppy", series),
+        );
+    }
+    push(
+        SuiteKind::Security,
+        if major >= FIRST_DASH_SECURITY {
+            format!("{}-security", series)
+        } else {
+            format!("{}/updates", series)
+        },
+    );
+    suites
+}
+
+impl DebianDistroInfo {
+    /// The auxiliary suites of the release with the given series; see
+    /// [`auxiliary_suites`](suites/fn.auxiliary_suites.html)
+    pub fn auxiliary_suites(&self, series: &str) -> Option<Vec<Suite>> {
+        self.iter()
+            .find(|distro_release| distro_release.series() == series)
+            .map(auxiliary_suites)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SuiteKind;
+    use crate::test_support;
+
+    #[test]
+    fn auxiliary_suites() {
+        let debian_distro_info = test_support::debian();
+        let names = |series| -> Vec<String> {
+            debian_distro_info
+                .auxiliary_suites(series)
+                .unwrap()
+                .into_iter()
+                .map(|suite| suite.name)
+                .collect()
+        };
+        assert_eq!(
+            vec![
+                "bookworm-updates",
+                "bookworm-proposed-updates",
+                "bookworm-backports",
+                "bookworm-backports-sloppy",
+                "bookworm-security",
+            ],
+            names("bookworm")
+        );
+        assert_eq!(Some(&"buster/updates".to_string()), names("buster").last());
+        assert_eq!(
+            vec!["lenny-proposed-updates", "lenny/updates"],
+            names("lenny")
+        );
+        assert!(names("sid").is_empty());
+        assert_eq!(None, debian_distro_info.auxiliary_suites("jammy"));
+        assert_eq!("debian-security", SuiteKind::Security.archive());
+    }
+}
@@ -0,0 +1,235 @@
+//! Fixtures for testing code which uses this crate.
+//!
+//! The crate's own tests use these rather than the system-installed distro-info-data, so that
+//! they do not break as new releases are added to it.  They are also available to downstream
+//! crates with the `test-support` feature.
+//!
+//! By default the fixtures are snapshots of the distro-info-data CSV files embedded in the crate;
+//! set `DISTRO_INFO_TEST_DATA_DIR` to a directory containing debian.csv and ubuntu.csv to run
+//! against other data instead (for example, `/usr/share/distro-info`).
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::naive::NaiveDate;
+
+use crate::{DebianDistroInfo, DistroDataSource, DistroRelease, UbuntuDistroInfo};
+
+/// The environment variable which overrides the directory fixtures are read from
+pub const DATA_DIR_ENV: &str = "DISTRO_INFO_TEST_DATA_DIR";
+
+/// The embedded snapshot of ubuntu.csv
+pub const UBUNTU_CSV: &str = include_str!("../testdata/ubuntu.csv");
+/// The embedded snapshot of debian.csv
+pub const DEBIAN_CSV: &str = include_str!("../testdata/debian.csv");
+
+/// The path to a file in the `testdata` directory of this crate
+pub fn testdata_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join(name)
+}
+
+fn fixture(name: &str, embedded: &str) -> String {
+    match env::var_os(DATA_DIR_ENV) {
+        Some(dir) => {
+            let path = PathBuf::from(dir).join(name);
+            fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e))
+        }
+        None => embedded.to_string(),
+    }
+}
+
+/// The contents of the ubuntu.csv fixture
+pub fn ubuntu_csv() -> String {
+    fixture("ubuntu.csv", UBUNTU_CSV)
+}
+
+/// The contents of the debian.csv fixture
+pub fn debian_csv() -> String {
+    fixture("debian.csv", DEBIAN_CSV)
+}
+
+/// An `UbuntuDistroInfo` populated from the ubuntu.csv fixture
+pub fn ubuntu() -> UbuntuDistroInfo {
+    UbuntuDistroInfo::from_csv_str(&ubuntu_csv()).expect("invalid ubuntu.csv fixture")
+}
+
+/// A `DebianDistroInfo` populated from the debian.csv fixture
+pub fn debian() -> DebianDistroInfo {
+    DebianDistroInfo::from_csv_str(&debian_csv()).expect("invalid debian.csv fixture")
+}
+
+/// Parse a YYYY-MM-DD date, panicking if it is invalid
+pub fn date(value: &str) -> NaiveDate {
+    crate::parser::parse_date(value).unwrap_or_else(|| panic!("invalid date '{}'", value))
+}
+
+/// Construct a fake release with the given series and milestones
+///
+/// The codename is derived from the series, and dates are given in YYYY-MM-DD format.
+pub fn fake_release(
+    version: &str,
+    series: &str,
+    created: &str,
+    release: Option<&str>,
+    eol: Option<&str>,
+) -> DistroRelease {
+    let mut codename = series.to_string();
+    if let Some(first) = codename.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    DistroRelease::new(
+        version.to_string(),
+        codename,
+        series.to_string(),
+        Some(date(created)),
+        release.map(date),
+        eol.map(date),
+        None,
+        None,
+        None,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use proptest::prelude::*;
+
+    use super::{date, debian, fake_release, testdata_path, ubuntu};
+    use crate::{DebianDistroInfo, DistroDataSource, DistroInfo};
+
+    /// Dates which exercise interesting boundaries in the fixture data
+    const GOLDEN_DATES: [&str; 6] = [
+        "2006-06-01",
+        "2011-05-14",
+        "2018-04-26",
+        "2018-07-19",
+        "2023-06-10",
+        "2025-08-09",
+    ];
+
+    fn series_list(releases: Vec<&crate::DistroRelease>) -> String {
+        releases
+            .iter()
+            .map(|distro_release| distro_release.series().as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn golden_report(distro_info: &impl DistroInfo, devel: bool) -> String {
+        let mut report = String::new();
+        for date_str in GOLDEN_DATES.iter() {
+            let date = date(date_str);
+            let devel_releases = if devel {
+                distro_info.ubuntu_devel(date)
+            } else {
+                distro_info.debian_devel(date)
+            };
+            report.push_str(&format!(
+                "{}\n  supported: {}\n  unsupported: {}\n  devel: {}\n  latest: {}\n",
+                date_str,
+                series_list(distro_info.supported(date)),
+                series_list(distro_info.unsupported(date)),
+                series_list(devel_releases),
+                series_list(distro_info.latest(date).into_iter().collect()),
+            ));
+        }
+        report
+    }
+
+    fn check_golden(name: &str, actual: &str) {
+        let path = testdata_path("golden").join(name);
+        if env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, actual).unwrap();
+        }
+        let expected = fs::read_to_string(&path).unwrap();
+        assert_eq!(expected, actual, "golden file {} differs", path.display());
+    }
+
+    #[test]
+    fn golden_ubuntu_queries() {
+        check_golden("ubuntu-queries.txt", &golden_report(&ubuntu(), true));
+    }
+
+    #[test]
+    fn golden_debian_queries() {
+        check_golden("debian-queries.txt", &golden_report(&debian(), false));
+    }
+
+    #[test]
+    fn fake_release_fields() {
+        let distro_release = fake_release("1.0", "foo", "2020-01-01", Some("2020-06-01"), None);
+        assert_eq!("Foo", distro_release.codename());
+        assert_eq!(&Some(date("2020-06-01")), distro_release.release());
+        assert_eq!(&None, distro_release.eol());
+    }
+
+    fn arbitrary_date() -> impl Strategy<Value = chrono::NaiveDate> {
+        (0i64..20000).prop_map(|days| date("1990-01-01") + chrono::Duration::days(days))
+    }
+
+    fn disjoint(distro_info: &impl DistroInfo, date: chrono::NaiveDate) -> bool {
+        let supported = distro_info.supported(date);
+        distro_info
+            .unsupported(date)
+            .iter()
+            .all(|u| !supported.iter().any(|s| s.series() == u.series()))
+    }
+
+    proptest! {
+        #[test]
+        fn supported_and_unsupported_are_disjoint(date in arbitrary_date()) {
+            prop_assert!(disjoint(&ubuntu(), date));
+            prop_assert!(disjoint(&debian(), date));
+        }
+
+        #[test]
+        fn supported_releases_were_created(date in arbitrary_date()) {
+            let distro_info = ubuntu();
+            let all = distro_info.all_at(date);
+            for distro_release in distro_info.supported(date) {
+                prop_assert!(all.iter().any(|a| a.series() == distro_release.series()));
+            }
+        }
+
+        #[test]
+        fn latest_is_released_and_supported(date in arbitrary_date()) {
+            let distro_info = debian();
+            if let Some(latest) = distro_info.latest(date) {
+                prop_assert!(latest.released_at(date));
+                prop_assert!(latest.supported_at(date));
+            }
+        }
+
+        #[test]
+        fn parsing_never_panics(rows in "[0-9a-zA-Z,\"\\-\n\r\x00]{0,200}") {
+            let input = format!("version,codename,series,created,release,eol\n{}", rows);
+            let _ = DebianDistroInfo::from_csv_str(&input);
+            let _ = DebianDistroInfo::from_csv_reader(csv::Reader::from_reader(input.as_bytes()));
+        }
+
+        #[test]
+        fn csv_round_trip(
+            series in "[a-z]{1,12}",
+            created in arbitrary_date(),
+            release_after in 0i64..1000,
+        ) {
+            let release = created + chrono::Duration::days(release_after);
+            let csv = format!(
+                "version,codename,series,created,release\n1.0,Name,{},{},{}\n",
+                series, created, release
+            );
+            let distro_info = DebianDistroInfo::from_csv_str(&csv).unwrap();
+            let distro_release = &distro_info.releases()[0];
+            prop_assert_eq!(&series, distro_release.series());
+            prop_assert_eq!(&Some(created), distro_release.created());
+            prop_assert_eq!(&Some(release), distro_release.release());
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Timezone-aware evaluation of milestones.
+//!
+//! The dates in distro-info-data are calendar dates, but in practice a milestone takes effect at
+//! a particular wall-clock moment (e.g. when the archive is published).  A
+//! [`Cutoff`](struct.Cutoff.html) records that moment, so that a `DateTime` anywhere in the
+//! world can be mapped to the milestone date it falls on.  This requires the `chrono-tz` feature.
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+
+use crate::{Distro, DistroRelease};
+
+/// The moment at which each day's milestones take effect
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cutoff {
+    /// The timezone the milestone dates are in
+    pub timezone: Tz,
+    /// The local time in `timezone` at which a milestone date begins
+    pub time: NaiveTime,
+}
+
+impl Cutoff {
+    /// Milestones take effect at `time` in `timezone`
+    pub fn new(timezone: Tz, time: NaiveTime) -> Self {
+        Self { timezone, time }
+    }
+
+    /// The convention for `distro`
+    ///
+    /// Both Ubuntu and Debian date their milestones in UTC, so these begin at midnight UTC; so do
+    /// derivatives', for want of anything better.
+    pub fn for_distro(distro: &Distro) -> Self {
+        match distro {
+            Distro::Ubuntu | Distro::Debian | Distro::Derivative(_) => {
+                Self::new(Tz::UTC, NaiveTime::MIN)
+            }
+        }
+    }
+
+    /// The milestone date that `at` falls on
+    pub fn milestone_date<T: TimeZone>(&self, at: &DateTime<T>) -> NaiveDate {
+        let local = at.with_timezone(&self.timezone).naive_local();
+        (local - self.time.signed_duration_since(NaiveTime::MIN)).date()
+    }
+}
+
+impl DistroRelease {
+    /// Like `created_at`, but for a moment in time evaluated against `cutoff`
+    pub fn created_at_datetime<T: TimeZone>(&self, at: &DateTime<T>, cutoff: &Cutoff) -> bool {
+        self.created_at(cutoff.milestone_date(at))
+    }
+
+    /// Like `released_at`, but for a moment in time evaluated against `cutoff`
+    pub fn released_at_datetime<T: TimeZone>(&self, at: &DateTime<T>, cutoff: &Cutoff) -> bool {
+        self.released_at(cutoff.milestone_date(at))
+    }
+
+    /// Like `supported_at`, but for a moment in time evaluated against `cutoff`
+    pub fn supported_at_datetime<T: TimeZone>(&self, at: &DateTime<T>, cutoff: &Cutoff) -> bool {
+        self.supported_at(cutoff.milestone_date(at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveTime, TimeZone};
+    use chrono_tz::{America, Tz};
+
+    use super::Cutoff;
+    use crate::{test_support, Distro};
+
+    #[test]
+    fn milestone_date_across_timezones() {
+        let cutoff = Cutoff::for_distro(&Distro::Ubuntu);
+        // 20:00 on the 20th in New York is already the 21st in UTC
+        let evening = America::New_York
+            .with_ymd_and_hms(2016, 4, 20, 20, 0, 0)
+            .unwrap();
+        assert_eq!(
+            test_support::date("2016-04-21"),
+            cutoff.milestone_date(&evening)
+        );
+    }
+
+    #[test]
+    fn supported_at_datetime_with_cutoff() {
+        let xenial = test_support::fake_release(
+            "16.04 LTS",
+            "xenial",
+            "2015-10-22",
+            Some("2016-04-21"),
+            Some("2021-04-30"),
+        );
+        let morning = Tz::UTC.with_ymd_and_hms(2016, 4, 21, 9, 0, 0).unwrap();
+        assert!(xenial.released_at_datetime(&morning, &Cutoff::for_distro(&Distro::Ubuntu)));
+        // With a noon cutoff, the release date has not yet begun at 09:00
+        let noon = Cutoff::new(Tz::UTC, NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+        assert!(!xenial.released_at_datetime(&morning, &noon));
+        // ... and the EOL date lasts until noon the following day
+        let after_eol = Tz::UTC.with_ymd_and_hms(2021, 5, 1, 11, 0, 0).unwrap();
+        assert!(xenial.supported_at_datetime(&after_eol, &noon));
+        assert!(!xenial.supported_at_datetime(&after_eol, &Cutoff::for_distro(&Distro::Ubuntu)));
+    }
+}
@@ -0,0 +1,132 @@
+//! The distributions Debian packages may be uploaded to, as named in `debian/changelog`.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use chrono::naive::NaiveDate;
+
+use crate::policy::{DebianPolicy, DistroPolicy};
+use crate::suites::{auxiliary_suites, Suite, SuiteKind};
+use crate::{DebianDistroInfo, DistroInfo, DistroRelease, SupportTier};
+
+/// Targets which are valid at any date
+const PERMANENT_TARGETS: [&str; 4] = ["unstable", "sid", "experimental", "UNRELEASED"];
+
+/// The name of `suite`, of `distro_release`, as a changelog `Distribution:`
+///
+/// Uploads for security updates are always to `{series}-security`, even for the releases whose
+/// security suite was `{series}/updates`.
+fn upload_target(distro_release: &DistroRelease, suite: &Suite) -> String {
+    match suite.kind {
+        SuiteKind::Security => format!("{}-security", distro_release.series()),
+        _ => suite.name.clone(),
+    }
+}
+
+impl DebianDistroInfo {
+    /// Returns the values which the `Distribution:` field of a changelog entry may take at the
+    /// given date
+    ///
+    /// These are:
+    ///
+    /// * `unstable`, `sid`, `experimental` and `UNRELEASED`
+    /// * the testing release, `testing` and `testing-proposed-updates`
+    /// * for each release still in standard support, its codename and those of its
+    ///   [`auxiliary_suites`](suites/fn.auxiliary_suites.html) which take uploads (security,
+    ///   proposed updates and backports, plus sloppy backports for all but the newest)
+    /// * `stable` and `oldstable`, and their `-proposed-updates` suites
+    /// * for each release in LTS, its `-security` suite
+    pub fn valid_upload_targets(&self, date: NaiveDate) -> Vec<String> {
+        let mut targets: Vec<String> = PERMANENT_TARGETS.iter().map(|t| t.to_string()).collect();
+        if let Some(testing) = DebianPolicy.resolve_alias(self, "testing", date) {
+            targets.push(testing.series().to_string());
+            targets.push("testing".to_string());
+            targets.push("testing-proposed-updates".to_string());
+        }
+        let released: Vec<_> = self
+            .iter_released(date)
+            .filter(|distro_release| !distro_release.is_rolling())
+            .collect();
+        let standard: Vec<_> = released
+            .iter()
+            .filter(|distro_release| distro_release.supported_at(date))
+            .collect();
+        for (age, distro_release) in standard.iter().rev().enumerate() {
+            targets.push(distro_release.series().to_string());
+            targets.extend(
+                auxiliary_suites(distro_release)
+                    .iter()
+                    .filter(|suite| match suite.kind {
+                        SuiteKind::Updates => false,
+                        SuiteKind::BackportsSloppy => age > 0,
+                        _ => true,
+                    })
+                    .map(|suite| upload_target(distro_release, suite)),
+            );
+            let alias = match age {
+                0 => "stable",
+                1 => "oldstable",
+                _ => continue,
+            };
+            targets.push(alias.to_string());
+            targets.push(format!("{}-proposed-updates", alias));
+        }
+        for distro_release in released.iter().filter(|distro_release| {
+            !distro_release.supported_at(date)
+                && distro_release.supported_at_tier(date, SupportTier::Esm)
+        }) {
+            targets.extend(
+                auxiliary_suites(distro_release)
+                    .iter()
+                    .filter(|suite| suite.kind == SuiteKind::Security)
+                    .map(|suite| upload_target(distro_release, suite)),
+            );
+        }
+        targets
+    }
+
+    /// Whether `name` is a valid `Distribution:` for a changelog entry at the given date
+    pub fn is_valid_target(&self, name: &str, date: NaiveDate) -> bool {
+        self.valid_upload_targets(date)
+            .iter()
+            .any(|target| target == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support;
+
+    #[test]
+    fn valid_upload_targets() {
+        let debian_distro_info = test_support::debian();
+        let date = test_support::date("2024-01-01");
+        let targets = debian_distro_info.valid_upload_targets(date);
+        for target in [
+            "unstable",
+            "UNRELEASED",
+            "trixie",
+            "bookworm",
+            "bookworm-security",
+            "bookworm-backports",
+            "stable-proposed-updates",
+            "bullseye-backports-sloppy",
+            "oldstable",
+            // in LTS
+            "buster-security",
+        ] {
+            assert!(targets.iter().any(|t| t == target), "{} missing", target);
+        }
+        for target in [
+            "buster",
+            "stretch-security",
+            "bookworm-backports-sloppy",
+            "jammy",
+        ] {
+            assert!(
+                !debian_distro_info.is_valid_target(target, date),
+                "{} unexpectedly valid",
+                target
+            );
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Verifying minisign signatures on release data, for systems which download it rather than
+//! installing distro-info-data.
+//!
+//! The signature of a file is read from alongside it, with `.minisig` appended to its name (as
+//! `minisign -S` writes it).  Only uncompressed CSV files can be verified.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use failure::Error;
+use minisign_verify::{PublicKey, Signature};
+
+/// The path of the signature for the file at `path`, e.g. "ubuntu.csv.minisig"
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut signature_path = path.as_os_str().to_os_string();
+    signature_path.push(".minisig");
+    PathBuf::from(signature_path)
+}
+
+/// Check that `signature` (the contents of a `.minisig` file) is a valid signature of `data` by
+/// `public_key`
+///
+/// `public_key` may be the base64-encoded key alone, or the contents of a `minisign.pub` file.
+pub fn verify(data: &[u8], signature: &str, public_key: &str) -> Result<(), Error> {
+    let public_key = PublicKey::from_base64(public_key.trim())
+        .or_else(|_| PublicKey::decode(public_key))
+        .map_err(|e| format_err!("invalid public key: {}", e))?;
+    let signature =
+        Signature::decode(signature).map_err(|e| format_err!("invalid signature: {}", e))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|e| format_err!("signature verification failed: {}", e))?;
+    trace_event!(debug, "verified signature");
+    Ok(())
+}
+
+/// Read the file at `path`, returning its contents only if its signature is valid
+pub fn read_verified(path: &Path, public_key: &str) -> Result<Vec<u8>, Error> {
+    let data = fs::read(path)?;
+    let signature_path = signature_path(path);
+    let signature = fs::read_to_string(&signature_path).map_err(|e| {
+        format_err!(
+            "failed to read signature {}: {}",
+            signature_path.display(),
+            e
+        )
+    })?;
+    verify(&data, &signature, public_key)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::verify;
+    use crate::{DistroDataSource, DistroInfo, UbuntuDistroInfo};
+
+    const CSV: &str = "testdata/signed/ubuntu.csv";
+
+    fn public_key() -> String {
+        fs::read_to_string("testdata/signed/minisign.pub").unwrap()
+    }
+
+    #[test]
+    fn from_path_verified() {
+        let ubuntu_distro_info = UbuntuDistroInfo::from_path_verified(CSV, &public_key()).unwrap();
+        assert_eq!(4, ubuntu_distro_info.releases().len());
+        // The bare base64 key works too
+        let bare_key = public_key().lines().nth(1).unwrap().to_string();
+        UbuntuDistroInfo::from_path_verified(CSV, &bare_key).unwrap();
+    }
+
+    #[test]
+    fn tampered_data_is_rejected() {
+        let data = fs::read(CSV).unwrap();
+        let signature = fs::read_to_string(super::signature_path(Path::new(CSV))).unwrap();
+        verify(&data, &signature, &public_key()).unwrap();
+        let tampered = String::from_utf8(data)
+            .unwrap()
+            .replace("2006-04-30", "2036-04-30");
+        assert!(verify(tampered.as_bytes(), &signature, &public_key()).is_err());
+        assert!(
+            UbuntuDistroInfo::from_path_verified("testdata/ubuntu.csv", &public_key()).is_err()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Release versions parsed into comparable components.
+//!
+//! Versions are strings in distro-info-data, which sort wrongly when compared as such: "9.10"
+//! sorts after "10.04".  [`ReleaseVersion`](struct.ReleaseVersion.html) compares them numerically.
+use crate::DistroRelease;
+
+/// A release version such as "22.04 LTS", "6.0" or "12"
+///
+/// Versions are ordered by major then minor version (a missing minor version is 0), with an LTS
+/// release sorting after an otherwise-equal non-LTS one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReleaseVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub lts: bool,
+}
+
+impl ReleaseVersion {
+    /// Parse a version as found in distro-info-data
+    ///
+    /// Returns `None` if `version` is not of the form `MAJOR[.MINOR][ LTS]`.
+    pub fn parse(version: &str) -> Option<Self> {
+        let (number, lts) = match version.strip_suffix(" LTS") {
+            Some(number) => (number, true),
+            None => (version, false),
+        };
+        let mut parts = number.splitn(2, '.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = match parts.next() {
+            Some(minor) => minor.parse().ok()?,
+            None => 0,
+        };
+        Some(Self { major, minor, lts })
+    }
+}
+
+impl DistroRelease {
+    /// The version of this release, parsed for comparison
+    ///
+    /// Returns `None` for releases without a version (e.g. Debian's sid) or whose version cannot
+    /// be parsed.
+    pub fn parsed_version(&self) -> Option<ReleaseVersion> {
+        self.version().as_deref().and_then(ReleaseVersion::parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReleaseVersion;
+    use crate::{test_support, DistroInfo};
+
+    #[test]
+    fn parse() {
+        let version = |major, minor, lts| Some(ReleaseVersion { major, minor, lts });
+        assert_eq!(version(22, 4, true), ReleaseVersion::parse("22.04 LTS"));
+        assert_eq!(version(12, 0, false), ReleaseVersion::parse("12"));
+        assert_eq!(version(6, 0, false), ReleaseVersion::parse("6.0"));
+        assert_eq!(None, ReleaseVersion::parse("sid"));
+        assert_eq!(None, ReleaseVersion::parse("1.2.3"));
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(ReleaseVersion::parse("9.10") < ReleaseVersion::parse("10.04 LTS"));
+        assert!(ReleaseVersion::parse("6.06 LTS") < ReleaseVersion::parse("6.10"));
+        for distro_info in [
+            &test_support::ubuntu() as &dyn DistroInfo,
+            &test_support::debian(),
+        ] {
+            let versions: Vec<_> = distro_info
+                .iter()
+                .filter_map(|distro_release| distro_release.parsed_version())
+                .collect();
+            let mut sorted = versions.clone();
+            sorted.sort();
+            assert_eq!(sorted, versions);
+        }
+    }
+}